@@ -1,2 +1,3 @@
 pub mod utils;
-pub mod objects;
\ No newline at end of file
+pub mod objects;
+pub mod scene;
\ No newline at end of file
@@ -1,6 +1,8 @@
 use wasm_bindgen::prelude::*;
 
-use crate::{objects::vector_object::VectorObjectBuilder, utils::{bezier::AnchorsAndHandles, point2d::{Path2D, Point2D}}};
+use std::rc::Rc;
+
+use crate::{objects::vector_object::VectorObjectBuilder, scene::value_tracker::ValueTracker, utils::{bezier::AnchorsAndHandles, point2d::{Path2D, Point2D}}};
 
 use super::tipable::Tipable;
 
@@ -16,6 +18,14 @@ pub struct Arc {
     start_angle: f32,
     /// The end angle of the arc in radians.
     end_angle: f32,
+    /// The tracker the center point is read from by tracked builders, if bound.
+    center_tracker: Option<ValueTracker>,
+    /// The tracker the radius is read from by tracked builders, if bound.
+    radius_tracker: Option<ValueTracker>,
+    /// The tracker the start angle is read from by tracked builders, if bound.
+    start_angle_tracker: Option<ValueTracker>,
+    /// The tracker the end angle is read from by tracked builders, if bound.
+    end_angle_tracker: Option<ValueTracker>,
 }
 
 #[wasm_bindgen]
@@ -37,6 +47,10 @@ impl Arc {
             radius,
             start_angle,
             end_angle,
+            center_tracker: None,
+            radius_tracker: None,
+            start_angle_tracker: None,
+            end_angle_tracker: None,
         }
     }
 
@@ -72,6 +86,72 @@ impl Arc {
             .actual_path_as_path(None, None)
     }
 
+    /// Binds the center point of the arc to a value tracker holding a point, so tracked builders read it from the tracker every time they're built.
+    #[wasm_bindgen(return_description = "An error if the tracker doesn't hold a point.")]
+    pub fn bind_center(
+        &mut self,
+        #[wasm_bindgen(param_description = "The tracker holding the center point of the arc.")]
+        tracker: &ValueTracker,
+    ) -> Result<(), JsError> {
+        tracker.point_value().map_err(|error| JsError::new(&error))?;
+        self.center_tracker = Some(tracker.clone());
+        Ok(())
+    }
+
+    /// Binds the radius of the arc to a value tracker holding a number, so tracked builders read it from the tracker every time they're built.
+    #[wasm_bindgen(return_description = "An error if the tracker doesn't hold a number.")]
+    pub fn bind_radius(
+        &mut self,
+        #[wasm_bindgen(param_description = "The tracker holding the radius of the arc.")]
+        tracker: &ValueTracker,
+    ) -> Result<(), JsError> {
+        tracker.number_value().map_err(|error| JsError::new(&error))?;
+        self.radius_tracker = Some(tracker.clone());
+        Ok(())
+    }
+
+    /// Binds the start angle of the arc to a value tracker holding a number in radians, so tracked builders read it from the tracker every time they're built.
+    #[wasm_bindgen(return_description = "An error if the tracker doesn't hold a number.")]
+    pub fn bind_start_angle(
+        &mut self,
+        #[wasm_bindgen(param_description = "The tracker holding the start angle of the arc in radians.")]
+        tracker: &ValueTracker,
+    ) -> Result<(), JsError> {
+        tracker.number_value().map_err(|error| JsError::new(&error))?;
+        self.start_angle_tracker = Some(tracker.clone());
+        Ok(())
+    }
+
+    /// Binds the end angle of the arc to a value tracker holding a number in radians, so tracked builders read it from the tracker every time they're built.
+    #[wasm_bindgen(return_description = "An error if the tracker doesn't hold a number.")]
+    pub fn bind_end_angle(
+        &mut self,
+        #[wasm_bindgen(param_description = "The tracker holding the end angle of the arc in radians.")]
+        tracker: &ValueTracker,
+    ) -> Result<(), JsError> {
+        tracker.number_value().map_err(|error| JsError::new(&error))?;
+        self.end_angle_tracker = Some(tracker.clone());
+        Ok(())
+    }
+
+    /// Creates a new vector object builder with the arc's points, where the properties bound to value trackers are read from them every time the builder is built. Trackers are bound by reference, so they stay usable from JavaScript.
+    #[wasm_bindgen(return_description = "A VectorObjectBuilder representing the arc with the current values of the trackers.")]
+    pub fn tracked_vector_object_builder(
+        &self,
+        #[wasm_bindgen(param_description = "The number of samples to use to create the arc, by default 15.")]
+        samples: Option<usize>
+    ) -> VectorObjectBuilder {
+        let arc = self.clone();
+        VectorObjectBuilder::from_factory(Rc::new(move || {
+            Arc::new(
+                arc.center_tracker.as_ref().and_then(|center| center.point_value().ok()).unwrap_or(arc.center),
+                arc.radius_tracker.as_ref().and_then(|radius| radius.number_value().ok()).map(|radius| radius as f32).unwrap_or(arc.radius),
+                arc.start_angle_tracker.as_ref().and_then(|start_angle| start_angle.number_value().ok()).map(|start_angle| start_angle as f32).unwrap_or(arc.start_angle),
+                arc.end_angle_tracker.as_ref().and_then(|end_angle| end_angle.number_value().ok()).map(|end_angle| end_angle as f32).unwrap_or(arc.end_angle),
+            ).vector_object_builder(samples)
+        }))
+    }
+
     /// Returns the center point of the arc.
     #[wasm_bindgen(getter, return_description = "The center point of the arc as a Point2D.")]
     pub fn center(&self) -> Point2D {
@@ -0,0 +1,217 @@
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+
+use crate::{objects::{geometry::rectangle::Rectangle, vector_object::VectorObjectBuilder}, utils::{bounding_box::BoundingBox, image_library::{ImageData, ImageLibrary}, linear_algebra::TransformationMatrix, point2d::{Path2D, Point2D}, style::{Color, ImageBitmap, ImageResampling, Style}}};
+
+/// An ImageObject is an ImageBitmap placed in the scene with a TransformationMatrix, so it can be moved, rotated, skewed and morphed like any other object.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct ImageObject {
+    /// The bitmap of the image, whose rectangle is given in the image's coordinate system.
+    bitmap: Rc<ImageBitmap>,
+    /// The transformation from the image's coordinate system to the scene.
+    transform: TransformationMatrix,
+}
+
+#[wasm_bindgen]
+impl ImageObject {
+    /// Creates a new ImageObject from an ImageBitmap.
+    #[wasm_bindgen(constructor, return_description = "A new image object.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The bitmap of the image, whose rectangle is given in the image's coordinate system.")]
+        bitmap: ImageBitmap,
+        #[wasm_bindgen(param_description = "The transformation from the image's coordinate system to the scene, default is the identity.")]
+        transform: Option<TransformationMatrix>,
+        #[wasm_bindgen(param_description = "How the image is resampled, default is the resampling of the bitmap.")]
+        resampling: Option<ImageResampling>
+    ) -> ImageObject {
+        let mut bitmap = bitmap;
+        if let Some(resampling) = resampling {
+            bitmap.set_resampling(resampling);
+        }
+        ImageObject {
+            bitmap: Rc::new(bitmap),
+            transform: transform.unwrap_or(TransformationMatrix::identity()),
+        }
+    }
+    /// Creates a new ImageObject by decoding PNG, JPEG or WebP ImageData.
+    #[wasm_bindgen(return_description = "A new image object with the decoded image.")]
+    pub fn from_image_data(
+        #[wasm_bindgen(param_description = "The encoded image data.")]
+        data: &ImageData,
+        #[wasm_bindgen(param_description = "The x coordinate of the top left corner of the image.")]
+        x: f32,
+        #[wasm_bindgen(param_description = "The y coordinate of the top left corner of the image.")]
+        y: f32,
+        #[wasm_bindgen(param_description = "The width of the image, default is the number of pixels in a row of the image.")]
+        width: Option<f32>,
+        #[wasm_bindgen(param_description = "The height of the image, default is the number of pixels in a column of the image.")]
+        height: Option<f32>,
+        #[wasm_bindgen(param_description = "How the image is resampled, default is bilinear.")]
+        resampling: Option<ImageResampling>
+    ) -> Result<ImageObject, JsError> {
+        let bitmap = ImageBitmap::decode(x, y, &data.data(), width, height)?;
+        Ok(ImageObject::new(bitmap, None, resampling))
+    }
+    /// Creates a new ImageObject by decoding an image of an ImageLibrary.
+    #[wasm_bindgen(return_description = "A new image object with the decoded image.")]
+    pub fn from_image_library(
+        #[wasm_bindgen(param_description = "The image library to get the image from.")]
+        image_library: &ImageLibrary,
+        #[wasm_bindgen(param_description = "The name of the image in the library.")]
+        name: &str,
+        #[wasm_bindgen(param_description = "The x coordinate of the top left corner of the image.")]
+        x: f32,
+        #[wasm_bindgen(param_description = "The y coordinate of the top left corner of the image.")]
+        y: f32,
+        #[wasm_bindgen(param_description = "The width of the image, default is the number of pixels in a row of the image.")]
+        width: Option<f32>,
+        #[wasm_bindgen(param_description = "The height of the image, default is the number of pixels in a column of the image.")]
+        height: Option<f32>,
+        #[wasm_bindgen(param_description = "How the image is resampled, default is bilinear.")]
+        resampling: Option<ImageResampling>
+    ) -> Result<ImageObject, JsError> {
+        let data = image_library.get(name).ok_or_else(|| JsError::new(&format!("Image {} not found in the image library.", name)))?;
+        ImageObject::from_image_data(&data, x, y, width, height, resampling)
+    }
+    /// Clones the image object.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn copy(&self) -> ImageObject {
+        self.clone()
+    }
+    /// Gets the bitmap of the image.
+    #[wasm_bindgen(getter, return_description = "The bitmap of the image.")]
+    pub fn bitmap(&self) -> ImageBitmap {
+        (*self.bitmap).clone()
+    }
+    /// Gets the transformation from the image's coordinate system to the scene.
+    #[wasm_bindgen(getter, return_description = "The transform of the image.")]
+    pub fn transform(&self) -> TransformationMatrix {
+        self.transform
+    }
+    /// Sets the transformation from the image's coordinate system to the scene.
+    #[wasm_bindgen(setter)]
+    pub fn set_transform(
+        &mut self,
+        #[wasm_bindgen(param_description = "The transform of the image.")]
+        transform: TransformationMatrix
+    ) {
+        self.transform = transform;
+    }
+    /// Gets how the image is resampled.
+    #[wasm_bindgen(getter, return_description = "The resampling of the image.")]
+    pub fn resampling(&self) -> ImageResampling {
+        self.bitmap.resampling()
+    }
+    /// Sets how the image is resampled.
+    #[wasm_bindgen(setter)]
+    pub fn set_resampling(
+        &mut self,
+        #[wasm_bindgen(param_description = "The resampling of the image.")]
+        resampling: ImageResampling
+    ) {
+        Rc::make_mut(&mut self.bitmap).set_resampling(resampling);
+    }
+    /// Gets the corners of the image in the scene, clockwise from the top left one.
+    #[wasm_bindgen(getter, return_description = "The corners of the image.")]
+    pub fn corners(&self) -> Vec<Point2D> {
+        let (x, y, width, height) = (self.bitmap.x, self.bitmap.y, self.bitmap.width, self.bitmap.height);
+        vec![
+            Point2D::new(x, y),
+            Point2D::new(x + width, y),
+            Point2D::new(x + width, y + height),
+            Point2D::new(x, y + height),
+        ].into_iter().map(|corner| self.transform * corner).collect()
+    }
+    /// Gets the center of the image in the scene.
+    #[wasm_bindgen(getter, return_description = "The center of the image.")]
+    pub fn center(&self) -> Point2D {
+        self.transform * Point2D::new(self.bitmap.x + self.bitmap.width / 2.0, self.bitmap.y + self.bitmap.height / 2.0)
+    }
+    /// Gets the BoundingBox of the image in the scene.
+    #[wasm_bindgen(return_description = "The bounding box of the image.")]
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::from_path(&Path2D::new(self.corners()))
+    }
+    /// Applies a TransformationMatrix to the image after its current transform.
+    #[wasm_bindgen(return_description = "The transformed image object.")]
+    pub fn transformed(
+        &self,
+        #[wasm_bindgen(param_description = "The matrix to transform the image by.")]
+        matrix: TransformationMatrix
+    ) -> ImageObject {
+        ImageObject {
+            bitmap: Rc::clone(&self.bitmap),
+            transform: matrix * self.transform,
+        }
+    }
+    /// Gets the resampled Color of the image at a point of the scene, which is transparent outside of the image.
+    #[wasm_bindgen(return_description = "The color at the given point.")]
+    pub fn color_at(
+        &self,
+        #[wasm_bindgen(param_description = "The point of the scene to get the color at.")]
+        p: Point2D
+    ) -> Color {
+        self.bitmap.sample_inside(self.transform.inverse() * p)
+    }
+    /// Rasterizes the transformed image into an axis-aligned ImageBitmap of the scene, resampling it with its resampling.
+    #[wasm_bindgen(return_description = "The rasterized image.")]
+    pub fn rasterize(
+        &self,
+        #[wasm_bindgen(param_description = "The x coordinate of the bitmap.")]
+        x: f32,
+        #[wasm_bindgen(param_description = "The y coordinate of the bitmap.")]
+        y: f32,
+        #[wasm_bindgen(param_description = "The width of the bitmap.")]
+        width: f32,
+        #[wasm_bindgen(param_description = "The height of the bitmap.")]
+        height: f32,
+        #[wasm_bindgen(param_description = "Number of pixels in a row of the bitmap.")]
+        data_width: usize,
+        #[wasm_bindgen(param_description = "Number of pixels in a column of the bitmap.")]
+        data_height: usize
+    ) -> ImageBitmap {
+        let mut bitmap = ImageBitmap::fill(x, y, width, height, data_width, data_height, &Color::default());
+        for j in 0..data_height {
+            for i in 0..data_width {
+                let p = Point2D::new(
+                    x + (i as f32 + 0.5) / data_width as f32 * width,
+                    y + (j as f32 + 0.5) / data_height as f32 * height,
+                );
+                bitmap.set_pixel(p, &self.color_at(p));
+            }
+        }
+        bitmap
+    }
+    /// Linearly interpolates between two ImageObjects given a progress value. The transforms are interpolated and the bitmaps are cross-faded unless both objects share the same bitmap.
+    #[wasm_bindgen(return_description = "The interpolated image object.")]
+    pub fn lerp(
+        #[wasm_bindgen(param_description = "The first image object.")]
+        image1: &ImageObject,
+        #[wasm_bindgen(param_description = "The second image object.")]
+        image2: &ImageObject,
+        #[wasm_bindgen(param_description = "The progress value.")]
+        t: f32
+    ) -> ImageObject {
+        let bitmap = if Rc::ptr_eq(&image1.bitmap, &image2.bitmap) {
+            Rc::clone(&image1.bitmap)
+        } else {
+            Rc::new(ImageBitmap::lerp(&image1.bitmap, &image2.bitmap, t, None))
+        };
+        ImageObject {
+            bitmap,
+            transform: TransformationMatrix::lerp(&image1.transform, &image2.transform, t),
+        }
+    }
+    /// Gets a VectorObjectBuilder of the image's rectangle filled with the image, so it takes part in layout and rendering like any other vector object.
+    #[wasm_bindgen(return_description = "A vector object builder of the image.")]
+    pub fn vector_object_builder(&self) -> Result<VectorObjectBuilder, JsError> {
+        let bbox = BoundingBox::new(self.bitmap.x, self.bitmap.y, self.bitmap.width, self.bitmap.height)?;
+        Ok(Rectangle::new(bbox, None)
+            .vector_object_builder()
+            .set_fill(Style::from_image((*self.bitmap).clone()), Some(false))
+            .set_stroke_width(0.0, Some(false))
+            .set_transform(self.transform, Some(false)))
+    }
+}
@@ -1,4 +1,6 @@
 pub mod vector_object;
 pub mod geometry;
 pub mod typesetting;
-pub mod plotting;
\ No newline at end of file
+pub mod plotting;
+pub mod three_d;
+pub mod image_object;
\ No newline at end of file
@@ -201,12 +201,13 @@ impl Axis {
                 .move_point(&tick_start)
                 .line_to(&tick_end)
                 .set_stroke(tick.style.clone(), None)
-                .set_stroke_width(tick.stroke_width, None);
+                .set_stroke_width(tick.stroke_width, None)
+                .set_name(Some("tick".to_string()));
             if let Some(label) = &tick.label {
                 let the_child_builder = child_builder.clone();
                 let label_direction = tick_label_direction.unwrap_or(Point2D::new(0.0, 1.0));
                 let label_offset = tick_label_offset.unwrap_or(10.0);
-                child_builder = child_builder.add_child(label.clone().next_to_other(the_child_builder, Some(label_direction), Some(label_offset), None, None));
+                child_builder = child_builder.add_child(label.clone().next_to_other(the_child_builder, Some(label_direction), Some(label_offset), None, None).set_name(Some("label".to_string())));
             }
             builder = builder.add_child(child_builder.clone());
         }
@@ -215,7 +216,7 @@ impl Axis {
                 let the_builder = builder.clone();
                 let label_direction = label_direction.unwrap_or(Point2D::new(1.0, 0.0));
                 let label_offset = label_offset.unwrap_or(20.0);
-                builder = builder.add_child(label.clone().next_to_other(the_builder, Some(label_direction), Some(label_offset), None, None));
+                builder = builder.add_child(label.clone().next_to_other(the_builder, Some(label_direction), Some(label_offset), None, None).set_name(Some("label".to_string())));
             }
         }
         builder
@@ -237,12 +238,12 @@ impl Axis {
         tick_label_offset: Option<f32>,
     ) -> VectorObjectBuilder {
         let mut builder = self.vector_object_builder(label_direction, label_offset, tick_label_direction, tick_label_offset, Some(false));
-        builder = builder.add_child(self.tip_at_end(tip_shape));
+        builder = builder.add_child(self.tip_at_end(tip_shape).set_name(Some("tip".to_string())));
         if let Some(label) = &self.label {
             let the_builder = builder.clone();
             let label_direction = label_direction.unwrap_or(Point2D::new(1.0, 0.0));
             let label_offset = label_offset.unwrap_or(20.0);
-            builder = builder.add_child(label.clone().next_to_other(the_builder, Some(label_direction), Some(label_offset), None, None));
+            builder = builder.add_child(label.clone().next_to_other(the_builder, Some(label_direction), Some(label_offset), None, None).set_name(Some("label".to_string())));
         }
         builder
     }
@@ -263,13 +264,13 @@ impl Axis {
         tick_label_offset: Option<f32>,
     ) -> VectorObjectBuilder {
         let mut builder = self.vector_object_builder(label_direction, label_offset, tick_label_direction, tick_label_offset, Some(false));
-        builder = builder.add_child(self.tip_at_start(tip_shape.clone()));
-        builder = builder.add_child(self.tip_at_end(tip_shape));
+        builder = builder.add_child(self.tip_at_start(tip_shape.clone()).set_name(Some("tip".to_string())));
+        builder = builder.add_child(self.tip_at_end(tip_shape).set_name(Some("tip".to_string())));
         if let Some(label) = &self.label {
             let the_builder = builder.clone();
             let label_direction = label_direction.unwrap_or(Point2D::new(1.0, 0.0));
             let label_offset = label_offset.unwrap_or(20.0);
-            builder = builder.add_child(label.clone().next_to_other(the_builder, Some(label_direction), Some(label_offset), None, None));
+            builder = builder.add_child(label.clone().next_to_other(the_builder, Some(label_direction), Some(label_offset), None, None).set_name(Some("label".to_string())));
         }
         builder
     }
@@ -451,8 +452,8 @@ impl CartesianAxes {
         y_tick_label_offset: Option<f32>,
     ) -> VectorObjectBuilder {
         let mut builder = VectorObjectBuilder::default();
-        builder = builder.add_child(self.x_axis.vector_object_builder(x_label_direction, x_label_offset, x_tick_label_direction, x_tick_label_offset, None));
-        builder = builder.add_child(self.y_axis.vector_object_builder(y_label_direction, y_label_offset, y_tick_label_direction, y_tick_label_offset, None));
+        builder = builder.add_child(self.x_axis.vector_object_builder(x_label_direction, x_label_offset, x_tick_label_direction, x_tick_label_offset, None).set_name(Some("x_axis".to_string())));
+        builder = builder.add_child(self.y_axis.vector_object_builder(y_label_direction, y_label_offset, y_tick_label_direction, y_tick_label_offset, None).set_name(Some("y_axis".to_string())));
         builder
     }
 
@@ -483,8 +484,8 @@ impl CartesianAxes {
         let mut builder = VectorObjectBuilder::default();
         let y_label_direction = y_label_direction.unwrap_or(Point2D::new(0.0, -1.0));
         let y_tick_label_direction = y_tick_label_direction.unwrap_or(Point2D::new(-1.0, 0.0));
-        builder = builder.add_child(self.x_axis.with_tip_at_the_end(tip_shape.clone(), x_label_direction, x_label_offset, x_tick_label_direction, x_tick_label_offset));
-        builder = builder.add_child(self.y_axis.with_tip_at_the_end(tip_shape, Some(y_label_direction), y_label_offset, Some(y_tick_label_direction), y_tick_label_offset));
+        builder = builder.add_child(self.x_axis.with_tip_at_the_end(tip_shape.clone(), x_label_direction, x_label_offset, x_tick_label_direction, x_tick_label_offset).set_name(Some("x_axis".to_string())));
+        builder = builder.add_child(self.y_axis.with_tip_at_the_end(tip_shape, Some(y_label_direction), y_label_offset, Some(y_tick_label_direction), y_tick_label_offset).set_name(Some("y_axis".to_string())));
         builder
     }
 
@@ -512,8 +513,8 @@ impl CartesianAxes {
         y_tick_label_offset: Option<f32>,
     ) -> VectorObjectBuilder {
         let mut builder = VectorObjectBuilder::default();
-        builder = builder.add_child(self.x_axis.with_tips_at_both_ends(tip_shape.clone(), x_label_direction, x_label_offset, x_tick_label_direction, x_tick_label_offset));
-        builder = builder.add_child(self.y_axis.with_tips_at_both_ends(tip_shape, y_label_direction, y_label_offset, y_tick_label_direction, y_tick_label_offset));
+        builder = builder.add_child(self.x_axis.with_tips_at_both_ends(tip_shape.clone(), x_label_direction, x_label_offset, x_tick_label_direction, x_tick_label_offset).set_name(Some("x_axis".to_string())));
+        builder = builder.add_child(self.y_axis.with_tips_at_both_ends(tip_shape, y_label_direction, y_label_offset, y_tick_label_direction, y_tick_label_offset).set_name(Some("y_axis".to_string())));
         builder
     }
 
@@ -2,7 +2,7 @@ use exmex::{parse, ExError, Express, FlatEx, FloatOpsFactory};
 use wasm_bindgen::prelude::*;
 use std::rc::Rc;
 
-use crate::{objects::vector_object::VectorObjectBuilder, utils::{bezier::CubicBezierTuple, console::error, interval::ClosedInterval, point2d::{Path2D, Point2D}}};
+use crate::{objects::vector_object::VectorObjectBuilder, scene::value_tracker::ValueTracker, utils::{bezier::CubicBezierTuple, console::error, interval::ClosedInterval, point2d::{Path2D, Point2D}}};
 
 /// A ParametricFunctionPlot represents a plot of a parametric function (x(t), y(t)).
 #[wasm_bindgen]
@@ -20,6 +20,7 @@ pub struct ParametricFunctionPlot {
     expr_x: FlatEx<f32, FloatOpsFactory<f32>>,
     expr_y: FlatEx<f32, FloatOpsFactory<f32>>,
     composition: Box<&'static dyn Fn(Point2D) -> Point2D>,
+    parameters: Rc<Vec<(String, ValueTracker)>>,
 }
 
 #[wasm_bindgen]
@@ -66,6 +67,7 @@ impl ParametricFunctionPlot {
                 expr_x,
                 expr_y,
                 composition: Box::new(&|point| point),
+                parameters: Rc::new(Vec::new()),
             }),
             _ => Err(JsError::new("Failed to parse parametric function."))
         }
@@ -89,6 +91,29 @@ impl ParametricFunctionPlot {
         format!("({}, {})", self.expression_x, self.expression_y)
     }
 
+    /// Returns the names of the parameters bound to value trackers.
+    #[wasm_bindgen(getter, return_description = "The names of the parameters bound to value trackers.")]
+    pub fn parameters(&self) -> Vec<String> {
+        self.parameters.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    /// Binds a variable of the expressions to a value tracker holding a number, so it's read from the tracker every time the function is evaluated. Any other variable is the parameter of the function.
+    #[wasm_bindgen(return_description = "An error if the tracker doesn't hold a number.")]
+    pub fn bind_parameter(
+        &mut self,
+        #[wasm_bindgen(param_description = "The name of the variable in the expressions.")]
+        name: String,
+        #[wasm_bindgen(param_description = "The tracker holding the value of the variable.")]
+        tracker: &ValueTracker,
+    ) -> Result<(), JsError> {
+        tracker.number_value().map_err(|error| JsError::new(&error))?;
+        let mut parameters = self.parameters.to_vec();
+        parameters.retain(|(parameter, _)| *parameter != name);
+        parameters.push((name, tracker.clone()));
+        self.parameters = Rc::new(parameters);
+        Ok(())
+    }
+
     /// Returns the domain of the parametric function.
     #[wasm_bindgen(getter, return_description = "The domain of the parametric function.")]
     pub fn domain(&self) -> ClosedInterval {
@@ -193,6 +218,18 @@ impl ParametricFunctionPlot {
         Ok(builder)
     }
 
+    /// Gets a VectorObjectBuilder that evaluates the plot every time it's built, so it follows the value trackers bound to its parameters.
+    #[wasm_bindgen(getter, return_description = "A VectorObjectBuilder with the plot's points for the current values of its parameters.")]
+    pub fn tracked_vector_object_builder(&self) -> VectorObjectBuilder {
+        let plot = self.clone();
+        VectorObjectBuilder::from_factory(Rc::new(move || {
+            plot.vector_object_builder().unwrap_or_else(|_| {
+                error("Failed to plot parametric function.");
+                VectorObjectBuilder::default()
+            })
+        }))
+    }
+
     /// Evaluates the parametric function at a given value.
     #[wasm_bindgen(return_description = "The evaluated point.")]
     pub fn evaluate(
@@ -200,8 +237,8 @@ impl ParametricFunctionPlot {
         #[wasm_bindgen(param_description = "The value to evaluate the parametric function at.")]
         t: f32,
     ) -> Option<Point2D> {
-        let x = self.expr_x.eval(&[t.into()]);
-        let y = self.expr_y.eval(&[t.into()]);
+        let x = self.expr_x.eval(&self.variables(&self.expr_x, t));
+        let y = self.expr_y.eval(&self.variables(&self.expr_y, t));
         match (x, y) {
             (Ok(x), Ok(y)) => Some(Point2D::new(x, y)),
             _ => {
@@ -213,6 +250,17 @@ impl ParametricFunctionPlot {
 }
 
 impl ParametricFunctionPlot {
+    /// Gets the values of the variables of an expression, reading bound parameters from their trackers and using t for the rest.
+    pub fn variables(&self, expression: &FlatEx<f32, FloatOpsFactory<f32>>, t: f32) -> Vec<f32> {
+        expression.var_names().iter().map(|name| {
+            self.parameters.iter()
+                .find(|(parameter, _)| parameter == name)
+                .and_then(|(_, tracker)| tracker.number_value().ok())
+                .map(|value| value as f32)
+                .unwrap_or(t)
+        }).collect()
+    }
+
     pub fn subdivide(
         &self,
         path: &mut Path2D,
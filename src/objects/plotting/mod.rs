@@ -1,2 +1,3 @@
 pub mod function_plotter;
-pub mod axes;
\ No newline at end of file
+pub mod axes;
+pub mod surface_plotter;
\ No newline at end of file
@@ -0,0 +1,332 @@
+use exmex::{parse, ExError, Express, FlatEx, FloatOpsFactory};
+use wasm_bindgen::prelude::*;
+use std::rc::Rc;
+
+use crate::{objects::{three_d::{camera::Camera3D, light_source::LightSource, three_d_object::ThreeDObject}, vector_object::VectorObject}, scene::value_tracker::ValueTracker, utils::{interval::ClosedInterval, point3d::Point3D, style::{Color, Style}}};
+
+/// How the faces of a ParametricSurfacePlot are filled.
+#[derive(Clone, Debug)]
+enum SurfaceFill {
+    /// Every face has the same style.
+    Solid(Rc<Style>),
+    /// The faces alternate between two colors like a checkerboard.
+    Checkerboard(Color, Color),
+    /// The faces are colored by the coordinate of their center along a direction, from the first color at the lowest face to the last color at the highest one.
+    Colormap(Rc<Vec<Color>>, Point3D),
+}
+
+/// A ParametricSurfacePlot represents a plot of a parametric surface (x(u, v), y(u, v), z(u, v)) as a mesh of quadrilateral faces.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct ParametricSurfacePlot {
+    expression_x: Rc<String>,
+    expression_y: Rc<String>,
+    expression_z: Rc<String>,
+    u_domain: ClosedInterval,
+    v_domain: ClosedInterval,
+    u_resolution: u32,
+    v_resolution: u32,
+    expr_x: FlatEx<f32, FloatOpsFactory<f32>>,
+    expr_y: FlatEx<f32, FloatOpsFactory<f32>>,
+    expr_z: FlatEx<f32, FloatOpsFactory<f32>>,
+    fill: SurfaceFill,
+    stroke: Style,
+    stroke_width: f32,
+    wireframe: bool,
+    parameters: Rc<Vec<(String, ValueTracker)>>,
+}
+
+#[wasm_bindgen]
+impl ParametricSurfacePlot {
+    /// Creates a new ParametricSurfacePlot from the expressions of the coordinates in the variables u and v, their domains and the resolution of the mesh.
+    #[wasm_bindgen(constructor, return_description = "A new parametric surface plot.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The x expression of the parametric surface.")]
+        expression_x: String,
+        #[wasm_bindgen(param_description = "The y expression of the parametric surface.")]
+        expression_y: String,
+        #[wasm_bindgen(param_description = "The z expression of the parametric surface.")]
+        expression_z: String,
+        #[wasm_bindgen(param_description = "The domain of u.")]
+        u_domain: ClosedInterval,
+        #[wasm_bindgen(param_description = "The domain of v.")]
+        v_domain: ClosedInterval,
+        #[wasm_bindgen(param_description = "The number of faces along u, default is 24.")]
+        u_resolution: Option<u32>,
+        #[wasm_bindgen(param_description = "The number of faces along v, default is 24.")]
+        v_resolution: Option<u32>,
+    ) -> Result<ParametricSurfacePlot, JsError> {
+        let expr_x: Result<FlatEx<_, FloatOpsFactory<f32>>, ExError> = parse(&expression_x);
+        let expr_y: Result<FlatEx<_, FloatOpsFactory<f32>>, ExError> = parse(&expression_y);
+        let expr_z: Result<FlatEx<_, FloatOpsFactory<f32>>, ExError> = parse(&expression_z);
+        match (expr_x, expr_y, expr_z) {
+            (Ok(expr_x), Ok(expr_y), Ok(expr_z)) => Ok(ParametricSurfacePlot {
+                expression_x: Rc::new(expression_x),
+                expression_y: Rc::new(expression_y),
+                expression_z: Rc::new(expression_z),
+                u_domain,
+                v_domain,
+                u_resolution: u_resolution.unwrap_or(24).max(1),
+                v_resolution: v_resolution.unwrap_or(24).max(1),
+                expr_x,
+                expr_y,
+                expr_z,
+                fill: SurfaceFill::Solid(Rc::new(Style::from_color(Color::new(41, 171, 202, 1.0)))),
+                stroke: Style::from_color(Color::new(0, 0, 0, 0.0)),
+                stroke_width: 1.0,
+                wireframe: false,
+                parameters: Rc::new(Vec::new()),
+            }),
+            _ => Err(JsError::new("Failed to parse parametric surface."))
+        }
+    }
+
+    /// Returns the x expression of the parametric surface.
+    #[wasm_bindgen(getter, return_description = "The x expression of the parametric surface.")]
+    pub fn expression_x(&self) -> String {
+        self.expression_x.to_string()
+    }
+
+    /// Returns the y expression of the parametric surface.
+    #[wasm_bindgen(getter, return_description = "The y expression of the parametric surface.")]
+    pub fn expression_y(&self) -> String {
+        self.expression_y.to_string()
+    }
+
+    /// Returns the z expression of the parametric surface.
+    #[wasm_bindgen(getter, return_description = "The z expression of the parametric surface.")]
+    pub fn expression_z(&self) -> String {
+        self.expression_z.to_string()
+    }
+
+    /// Returns the expression of the parametric surface.
+    #[wasm_bindgen(getter, return_description = "The expression of the parametric surface.")]
+    pub fn expression(&self) -> String {
+        format!("({}, {}, {})", self.expression_x, self.expression_y, self.expression_z)
+    }
+
+    /// Returns the domain of u.
+    #[wasm_bindgen(getter, return_description = "The domain of u.")]
+    pub fn u_domain(&self) -> ClosedInterval {
+        self.u_domain.clone()
+    }
+
+    /// Returns the domain of v.
+    #[wasm_bindgen(getter, return_description = "The domain of v.")]
+    pub fn v_domain(&self) -> ClosedInterval {
+        self.v_domain.clone()
+    }
+
+    /// Returns the number of faces along u.
+    #[wasm_bindgen(getter, return_description = "The number of faces along u.")]
+    pub fn u_resolution(&self) -> u32 {
+        self.u_resolution
+    }
+
+    /// Returns the number of faces along v.
+    #[wasm_bindgen(getter, return_description = "The number of faces along v.")]
+    pub fn v_resolution(&self) -> u32 {
+        self.v_resolution
+    }
+
+    /// Returns whether the plot is drawn as a wireframe.
+    #[wasm_bindgen(getter, return_description = "Whether the plot is drawn as a wireframe.")]
+    pub fn wireframe(&self) -> bool {
+        self.wireframe
+    }
+
+    /// Returns a copy of the plot with every face filled with the same style.
+    #[wasm_bindgen(return_description = "The plot with a solid fill.")]
+    pub fn with_solid_fill(
+        &self,
+        #[wasm_bindgen(param_description = "The fill style of the faces.")]
+        fill: Style,
+    ) -> ParametricSurfacePlot {
+        ParametricSurfacePlot { fill: SurfaceFill::Solid(Rc::new(fill)), wireframe: false, ..self.clone() }
+    }
+
+    /// Returns a copy of the plot with the faces alternating between two colors like a checkerboard.
+    #[wasm_bindgen(return_description = "The plot with a checkerboard fill.")]
+    pub fn with_checkerboard(
+        &self,
+        #[wasm_bindgen(param_description = "The color of the faces with an even sum of indices.")]
+        color1: Color,
+        #[wasm_bindgen(param_description = "The color of the faces with an odd sum of indices.")]
+        color2: Color,
+    ) -> ParametricSurfacePlot {
+        ParametricSurfacePlot { fill: SurfaceFill::Checkerboard(color1, color2), wireframe: false, ..self.clone() }
+    }
+
+    /// Returns a copy of the plot with the faces colored by the height of their center along an axis, interpolating the colors evenly from the lowest face to the highest one.
+    #[wasm_bindgen(return_description = "The plot with a colormap fill.")]
+    pub fn with_colormap(
+        &self,
+        #[wasm_bindgen(param_description = "The colors of the colormap, from the lowest to the highest value.")]
+        colors: Vec<Color>,
+        #[wasm_bindgen(param_description = "The direction the height is measured along, default is the y-axis, which points upwards in the projections of Camera3D.")]
+        axis: Option<Point3D>,
+    ) -> Result<ParametricSurfacePlot, JsError> {
+        if colors.is_empty() {
+            return Err(JsError::new("The colormap must have at least one color."));
+        }
+        let axis = axis.unwrap_or(Point3D::new(0.0, 1.0, 0.0));
+        if axis.magnitude() == 0.0 || !axis.is_finite() {
+            return Err(JsError::new("The axis of the colormap must be a nonzero direction."));
+        }
+        Ok(ParametricSurfacePlot { fill: SurfaceFill::Colormap(Rc::new(colors), axis), wireframe: false, ..self.clone() })
+    }
+
+    /// Returns a copy of the plot with the given stroke for the edges of the faces.
+    #[wasm_bindgen(return_description = "The plot with the new stroke.")]
+    pub fn with_stroke(
+        &self,
+        #[wasm_bindgen(param_description = "The stroke style of the edges.")]
+        stroke: Style,
+        #[wasm_bindgen(param_description = "The stroke width of the edges, default is 1.")]
+        stroke_width: Option<f32>,
+    ) -> ParametricSurfacePlot {
+        ParametricSurfacePlot { stroke, stroke_width: stroke_width.unwrap_or(1.0), ..self.clone() }
+    }
+
+    /// Returns a copy of the plot drawn as a wireframe, with only the edges of the faces stroked.
+    #[wasm_bindgen(return_description = "The plot as a wireframe.")]
+    pub fn as_wireframe(
+        &self,
+        #[wasm_bindgen(param_description = "The stroke style of the edges.")]
+        stroke: Style,
+        #[wasm_bindgen(param_description = "The stroke width of the edges, default is 1.")]
+        stroke_width: Option<f32>,
+    ) -> ParametricSurfacePlot {
+        ParametricSurfacePlot { stroke, stroke_width: stroke_width.unwrap_or(1.0), wireframe: true, ..self.clone() }
+    }
+
+    /// Binds a variable of the expressions to a value tracker holding a number, so it's read from the tracker every time the surface is evaluated.
+    #[wasm_bindgen(return_description = "An error if the tracker doesn't hold a number.")]
+    pub fn bind_parameter(
+        &mut self,
+        #[wasm_bindgen(param_description = "The name of the variable in the expressions.")]
+        name: String,
+        #[wasm_bindgen(param_description = "The tracker holding the value of the variable.")]
+        tracker: &ValueTracker,
+    ) -> Result<(), JsError> {
+        tracker.number_value().map_err(|error| JsError::new(&error))?;
+        let mut parameters = self.parameters.to_vec();
+        parameters.retain(|(parameter, _)| *parameter != name);
+        parameters.push((name, tracker.clone()));
+        self.parameters = Rc::new(parameters);
+        Ok(())
+    }
+
+    /// Evaluates the parametric surface at the given values of u and v.
+    #[wasm_bindgen(return_description = "The evaluated point.")]
+    pub fn evaluate(
+        &self,
+        #[wasm_bindgen(param_description = "The value of u.")]
+        u: f32,
+        #[wasm_bindgen(param_description = "The value of v.")]
+        v: f32,
+    ) -> Result<Point3D, JsError> {
+        let x = self.evaluate_expression(&self.expr_x, u, v);
+        let y = self.evaluate_expression(&self.expr_y, u, v);
+        let z = self.evaluate_expression(&self.expr_z, u, v);
+        match (x, y, z) {
+            (Ok(x), Ok(y), Ok(z)) => Ok(Point3D::new(x, y, z)),
+            (Err(error), _, _) | (_, Err(error), _) | (_, _, Err(error)) => Err(JsError::new(&error)),
+        }
+    }
+
+    /// Gets the surface as a ThreeDObject whose children are its faces, ordered by u and then by v. Faces with non-finite vertices are skipped.
+    #[wasm_bindgen(getter, return_description = "A ThreeDObject with the faces of the surface.")]
+    pub fn three_d_object(&self) -> Result<ThreeDObject, JsError> {
+        let grid = self.sample_grid().map_err(|error| JsError::new(&error))?;
+        let mut faces = Vec::with_capacity((self.u_resolution * self.v_resolution) as usize);
+        for i in 0..self.u_resolution as usize {
+            for j in 0..self.v_resolution as usize {
+                let vertices = vec![grid[i][j], grid[i + 1][j], grid[i + 1][j + 1], grid[i][j + 1]];
+                if vertices.iter().all(|vertex| vertex.is_finite()) {
+                    faces.push(((i, j), ThreeDObject::polygon(vertices)));
+                }
+            }
+        }
+        let height = |face: &ThreeDObject| match &self.fill {
+            SurfaceFill::Colormap(_, axis) => face.path().centroid().map(|centroid| centroid.dot(axis)),
+            _ => None,
+        };
+        let (min_height, max_height) = faces.iter()
+            .filter_map(|(_, face)| height(face))
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), height| (min.min(height), max.max(height)));
+        let children = faces.into_iter().map(|((i, j), face)| {
+            let fill = if self.wireframe {
+                Style::from_color(Color::new(0, 0, 0, 0.0))
+            } else {
+                match &self.fill {
+                    SurfaceFill::Solid(style) => style.as_ref().clone(),
+                    SurfaceFill::Checkerboard(color1, color2) => Style::from_color(if (i + j) % 2 == 0 { *color1 } else { *color2 }),
+                    SurfaceFill::Colormap(colors, _) => {
+                        let height = height(&face).unwrap_or(min_height);
+                        let t = if max_height > min_height { (height - min_height) / (max_height - min_height) } else { 0.0 };
+                        Style::from_color(ParametricSurfacePlot::colormap_color(colors, t))
+                    }
+                }
+            };
+            face.with_fill(fill, None)
+                .with_stroke(self.stroke.clone(), None)
+                .with_stroke_width(self.stroke_width, None)
+        }).collect();
+        Ok(ThreeDObject::group(children))
+    }
+
+    /// Projects the surface through a camera into a VectorObject whose children are its faces sorted by depth, shading them if a light source is given.
+    #[wasm_bindgen(return_description = "A VectorObject with the projected faces of the surface.")]
+    pub fn vector_object(
+        &self,
+        #[wasm_bindgen(param_description = "The camera to project the surface with.")]
+        camera: &Camera3D,
+        #[wasm_bindgen(param_description = "The light source to shade the faces with. If not given or in wireframe mode, the faces aren't shaded.")]
+        light_source: Option<LightSource>,
+    ) -> Result<VectorObject, JsError> {
+        let light_source = if self.wireframe { None } else { light_source };
+        Ok(self.three_d_object()?.project_and_shade(camera, light_source))
+    }
+}
+
+impl ParametricSurfacePlot {
+    /// Evaluates an expression, reading u, v and the bound parameters by name.
+    pub fn evaluate_expression(&self, expression: &FlatEx<f32, FloatOpsFactory<f32>>, u: f32, v: f32) -> Result<f32, String> {
+        let variables = expression.var_names().iter().map(|name| match name.as_str() {
+            "u" => Ok(u),
+            "v" => Ok(v),
+            name => self.parameters.iter()
+                .find(|(parameter, _)| parameter == name)
+                .ok_or_else(|| format!("Unknown variable \"{}\" in parametric surface.", name))
+                .and_then(|(_, tracker)| tracker.number_value())
+                .map(|value| value as f32),
+        }).collect::<Result<Vec<f32>, String>>()?;
+        expression.eval(&variables).map_err(|_| "Failed to evaluate parametric surface.".to_string())
+    }
+
+    /// Samples the surface on a grid of (u_resolution + 1) by (v_resolution + 1) points.
+    pub fn sample_grid(&self) -> Result<Vec<Vec<Point3D>>, String> {
+        (0..=self.u_resolution).map(|i| {
+            let u = self.u_domain.start() + self.u_domain.length() * i as f32 / self.u_resolution as f32;
+            (0..=self.v_resolution).map(|j| {
+                let v = self.v_domain.start() + self.v_domain.length() * j as f32 / self.v_resolution as f32;
+                Ok(Point3D::new(
+                    self.evaluate_expression(&self.expr_x, u, v)?,
+                    self.evaluate_expression(&self.expr_y, u, v)?,
+                    self.evaluate_expression(&self.expr_z, u, v)?,
+                ))
+            }).collect()
+        }).collect()
+    }
+
+    /// Gets the color of a colormap at a value between 0 and 1.
+    pub fn colormap_color(colors: &[Color], t: f32) -> Color {
+        if colors.len() == 1 {
+            return colors[0];
+        }
+        let position = t.clamp(0.0, 1.0) * (colors.len() - 1) as f32;
+        let index = (position.floor() as usize).min(colors.len() - 2);
+        Color::lerp(&colors[index], &colors[index + 1], position - index as f32, None)
+    }
+}
@@ -0,0 +1,278 @@
+use wasm_bindgen::prelude::*;
+
+use crate::utils::{interpolation::lerp, point2d::{Path2D, Point2D}, point3d::{Path3D, Point3D}};
+
+/// The depth of the near plane of perspective projections as a fraction of the focal distance. Paths are clipped in front of it so that no point is projected from behind the camera.
+const NEAR_PLANE_FRACTION: f32 = 1e-2;
+
+/// The number of intervals a cubic curve is divided into when looking for the parameters where it crosses the near plane.
+const CROSSING_SEARCH_INTERVALS: usize = 16;
+
+/// Evaluates a cubic Bézier curve of numbers, such as the depths of the control points of a curve, at a parameter.
+fn cubic_value(values: [f32; 4], t: f32) -> f32 {
+    let s = 1.0 - t;
+    s * s * s * values[0] + 3.0 * s * s * t * values[1] + 3.0 * s * t * t * values[2] + t * t * t * values[3]
+}
+
+/// Gets the part of a cubic Bézier curve between two parameters, where the second one is positive, with de Casteljau's algorithm.
+fn cubic_part(curve: [Point3D; 4], t0: f32, t1: f32) -> [Point3D; 4] {
+    let split = |[p0, p1, p2, p3]: [Point3D; 4], t: f32| {
+        let (p01, p12, p23) = (Point3D::lerp(&p0, &p1, t), Point3D::lerp(&p1, &p2, t), Point3D::lerp(&p2, &p3, t));
+        let (p012, p123) = (Point3D::lerp(&p01, &p12, t), Point3D::lerp(&p12, &p23, t));
+        let p0123 = Point3D::lerp(&p012, &p123, t);
+        ([p0, p01, p012, p0123], [p0123, p123, p23, p3])
+    };
+    let (before_t1, _) = split(curve, t1);
+    split(before_t1, t0 / t1).1
+}
+
+/// Gets a straight line as a cubic Bézier curve.
+fn line(start: Point3D, end: Point3D) -> [Point3D; 4] {
+    [start, Point3D::lerp(&start, &end, 1.0 / 3.0), Point3D::lerp(&start, &end, 2.0 / 3.0), end]
+}
+
+/// Gets the parameter intervals where a cubic Bézier curve of numbers isn't negative, found by bisecting the intervals where it changes sign.
+fn non_negative_intervals(values: [f32; 4]) -> Vec<(f32, f32)> {
+    let mut crossings = vec![];
+    for i in 0..CROSSING_SEARCH_INTERVALS {
+        let (mut a, mut b) = (i as f32 / CROSSING_SEARCH_INTERVALS as f32, (i + 1) as f32 / CROSSING_SEARCH_INTERVALS as f32);
+        if (cubic_value(values, a) >= 0.0) == (cubic_value(values, b) >= 0.0) {
+            continue;
+        }
+        for _ in 0..32 {
+            let middle = (a + b) / 2.0;
+            if (cubic_value(values, a) >= 0.0) == (cubic_value(values, middle) >= 0.0) {
+                a = middle;
+            } else {
+                b = middle;
+            }
+        }
+        crossings.push((a + b) / 2.0);
+    }
+    let bounds = std::iter::once(0.0).chain(crossings).chain(std::iter::once(1.0)).collect::<Vec<f32>>();
+    bounds.windows(2)
+        .filter(|bound| bound[1] > bound[0] && cubic_value(values, (bound[0] + bound[1]) / 2.0) >= 0.0)
+        .map(|bound| (bound[0], bound[1]))
+        .collect()
+}
+
+/// A Camera3D projects 3D points onto the plane of VectorObjects. It looks along its negative z-axis, which is rotated by its rotation around the x, y and z axes, in that order.
+/// The projection keeps the y-axis of the 3D scene pointing upwards on the screen.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera3D {
+    /// The position of the camera.
+    position: Point3D,
+    /// The rotation of the camera in radians around the x, y and z axes.
+    rotation: Point3D,
+    /// The distance from the camera to the projection plane.
+    focal_distance: f32,
+    /// The zoom factor, the number of 2D units per 3D unit on the projection plane.
+    zoom: f32,
+    /// Whether the projection is orthographic instead of perspective.
+    orthographic: bool,
+    /// The 2D point the line of sight of the camera is projected to.
+    center: Point2D,
+}
+
+#[wasm_bindgen]
+impl Camera3D {
+    /// Creates a new Camera3D.
+    #[wasm_bindgen(constructor, return_description = "A new 3D camera.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The position of the camera.")]
+        position: Point3D,
+        #[wasm_bindgen(param_description = "The rotation of the camera in radians around the x, y and z axes.")]
+        rotation: Point3D,
+        #[wasm_bindgen(param_description = "The distance from the camera to the projection plane, it must be positive.")]
+        focal_distance: f32,
+        #[wasm_bindgen(param_description = "The zoom factor, the number of 2D units per 3D unit on the projection plane, it must be positive.")]
+        zoom: f32,
+        #[wasm_bindgen(param_description = "Whether the projection is orthographic instead of perspective, default is false.")]
+        orthographic: Option<bool>,
+        #[wasm_bindgen(param_description = "The 2D point the line of sight of the camera is projected to, default is the origin.")]
+        center: Option<Point2D>,
+    ) -> Result<Camera3D, JsError> {
+        if focal_distance <= 0.0 || zoom <= 0.0 {
+            return Err(JsError::new("The focal distance and zoom must be positive."));
+        }
+        Ok(Camera3D {
+            position,
+            rotation,
+            focal_distance,
+            zoom,
+            orthographic: orthographic.unwrap_or(false),
+            center: center.unwrap_or_default(),
+        })
+    }
+    /// Clones the camera.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn copy(&self) -> Camera3D {
+        *self
+    }
+    /// Returns the position of the camera.
+    #[wasm_bindgen(getter, return_description = "The position of the camera.")]
+    pub fn position(&self) -> Point3D {
+        self.position
+    }
+    /// Returns the rotation of the camera in radians around the x, y and z axes.
+    #[wasm_bindgen(getter, return_description = "The rotation of the camera.")]
+    pub fn rotation(&self) -> Point3D {
+        self.rotation
+    }
+    /// Returns the distance from the camera to the projection plane.
+    #[wasm_bindgen(getter, return_description = "The focal distance of the camera.")]
+    pub fn focal_distance(&self) -> f32 {
+        self.focal_distance
+    }
+    /// Returns the zoom factor of the camera.
+    #[wasm_bindgen(getter, return_description = "The zoom factor of the camera.")]
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+    /// Returns whether the projection is orthographic.
+    #[wasm_bindgen(getter, return_description = "Whether the projection is orthographic.")]
+    pub fn orthographic(&self) -> bool {
+        self.orthographic
+    }
+    /// Returns the 2D point the line of sight of the camera is projected to.
+    #[wasm_bindgen(getter, return_description = "The center of the projection.")]
+    pub fn center(&self) -> Point2D {
+        self.center
+    }
+    /// Returns a copy of the camera moved to another position.
+    #[wasm_bindgen(return_description = "The moved camera.")]
+    pub fn with_position(
+        &self,
+        #[wasm_bindgen(param_description = "The new position of the camera.")]
+        position: Point3D,
+    ) -> Camera3D {
+        Camera3D { position, ..*self }
+    }
+    /// Returns a copy of the camera with another rotation.
+    #[wasm_bindgen(return_description = "The rotated camera.")]
+    pub fn with_rotation(
+        &self,
+        #[wasm_bindgen(param_description = "The new rotation of the camera in radians around the x, y and z axes.")]
+        rotation: Point3D,
+    ) -> Camera3D {
+        Camera3D { rotation, ..*self }
+    }
+    /// Returns a copy of the camera with another zoom factor.
+    #[wasm_bindgen(return_description = "The zoomed camera.")]
+    pub fn with_zoom(
+        &self,
+        #[wasm_bindgen(param_description = "The new zoom factor, it must be positive.")]
+        zoom: f32,
+    ) -> Result<Camera3D, JsError> {
+        if zoom <= 0.0 {
+            return Err(JsError::new("The zoom must be positive."));
+        }
+        Ok(Camera3D { zoom, ..*self })
+    }
+    /// Returns a point in the coordinate system of the camera, where the camera is at the origin looking along the negative z-axis.
+    #[wasm_bindgen(return_description = "The point relative to the camera.")]
+    pub fn to_camera_space(
+        &self,
+        #[wasm_bindgen(param_description = "The point in scene coordinates.")]
+        point: Point3D,
+    ) -> Point3D {
+        (point - self.position)
+            .rotate_z(-self.rotation.z)
+            .rotate_y(-self.rotation.y)
+            .rotate_x(-self.rotation.x)
+    }
+    /// Returns the distance of a point in front of the camera along its line of sight.
+    #[wasm_bindgen(return_description = "The depth of the point.")]
+    pub fn depth(
+        &self,
+        #[wasm_bindgen(param_description = "The point in scene coordinates.")]
+        point: Point3D,
+    ) -> f32 {
+        -self.to_camera_space(point).z
+    }
+    /// Returns the direction from the scene towards the camera, the positive z-axis of the camera.
+    #[wasm_bindgen(getter, return_description = "The direction towards the camera.")]
+    pub fn backward(&self) -> Point3D {
+        Point3D::new(0.0, 0.0, 1.0).rotate(&self.rotation)
+    }
+    /// Projects a 3D point onto the plane of VectorObjects. Points behind the camera are clamped to a tiny depth in perspective projections.
+    #[wasm_bindgen(return_description = "The projected point.")]
+    pub fn project(
+        &self,
+        #[wasm_bindgen(param_description = "The point in scene coordinates.")]
+        point: Point3D,
+    ) -> Point2D {
+        let point = self.to_camera_space(point);
+        let factor = if self.orthographic {
+            self.zoom
+        } else {
+            self.zoom * self.focal_distance / (-point.z).max(1e-3)
+        };
+        Point2D::new(self.center.x + point.x * factor, self.center.y - point.y * factor)
+    }
+    /// Projects every point of a Path3D onto the plane of VectorObjects. In perspective projections, the parts of the path in front of the near plane, including those behind the camera, are clipped away first and the cuts are closed along the near plane.
+    #[wasm_bindgen(return_description = "The projected path.")]
+    pub fn project_path(
+        &self,
+        #[wasm_bindgen(param_description = "The path in scene coordinates.")]
+        path: &Path3D,
+    ) -> Path2D {
+        let path = if self.orthographic { path.clone() } else { self.clip_path(path) };
+        Path2D::new(path.points().into_iter().map(|point| self.project(point)).collect())
+    }
+    /// Linearly interpolates two cameras.
+    #[wasm_bindgen(return_description = "The interpolated camera.")]
+    pub fn lerp(
+        #[wasm_bindgen(param_description = "The start camera.")]
+        camera1: &Camera3D,
+        #[wasm_bindgen(param_description = "The end camera.")]
+        camera2: &Camera3D,
+        #[wasm_bindgen(param_description = "The progress value.")]
+        t: f32,
+    ) -> Camera3D {
+        Camera3D {
+            position: Point3D::lerp(&camera1.position, &camera2.position, t),
+            rotation: Point3D::lerp(&camera1.rotation, &camera2.rotation, t),
+            focal_distance: lerp(camera1.focal_distance, camera2.focal_distance, t),
+            zoom: lerp(camera1.zoom, camera2.zoom, t),
+            orthographic: if t < 0.5 { camera1.orthographic } else { camera2.orthographic },
+            center: Point2D::lerp(&camera1.center, &camera2.center, t),
+        }
+    }
+}
+
+impl Camera3D {
+    /// Clips the parts of a path of cubic Bézier curves that are nearer than the near plane. Where a curve was clipped, the path goes on with a straight line on the near plane to the next visible part, and a closed path is closed again the same way.
+    pub fn clip_path(&self, path: &Path3D) -> Path3D {
+        let near = self.focal_distance * NEAR_PLANE_FRACTION;
+        let points = path.points();
+        let closed = points.len() >= 4 && points.first() == points.last();
+        let mut clipped: Vec<Point3D> = Vec::with_capacity(points.len());
+        let mut gap = false;
+        for curve in points.chunks_exact(4) {
+            let curve = [curve[0], curve[1], curve[2], curve[3]];
+            let depths = curve.map(|point| self.depth(point) - near);
+            let intervals = non_negative_intervals(depths);
+            if intervals.first().is_none_or(|(t0, _)| *t0 > 0.0) {
+                gap = true;
+            }
+            for (t0, t1) in intervals {
+                let part = cubic_part(curve, t0, t1);
+                if let (true, Some(&end)) = (gap, clipped.last()) {
+                    clipped.extend(line(end, part[0]));
+                }
+                clipped.extend(part);
+                gap = t1 < 1.0;
+            }
+        }
+        if closed {
+            if let (Some(&start), Some(&end)) = (clipped.first(), clipped.last()) {
+                if start != end {
+                    clipped.extend(line(end, start));
+                }
+            }
+        }
+        Path3D::new(clipped)
+    }
+}
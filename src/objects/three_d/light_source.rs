@@ -0,0 +1,82 @@
+use wasm_bindgen::prelude::*;
+
+use crate::utils::{point3d::Point3D, style::{Color, Style}};
+
+/// A LightSource is a point light used to shade the faces of 3D objects with Lambertian reflectance.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LightSource {
+    /// The position of the light source.
+    position: Point3D,
+    /// The brightness of faces that don't face the light source, between 0 and 1.
+    ambient: f32,
+}
+
+#[wasm_bindgen]
+impl LightSource {
+    /// Creates a new LightSource.
+    #[wasm_bindgen(constructor, return_description = "A new light source.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The position of the light source.")]
+        position: Point3D,
+        #[wasm_bindgen(param_description = "The brightness of faces that don't face the light source, between 0 and 1, default is 0.2.")]
+        ambient: Option<f32>,
+    ) -> LightSource {
+        LightSource {
+            position,
+            ambient: ambient.unwrap_or(0.2).clamp(0.0, 1.0),
+        }
+    }
+    /// Clones the light source.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn copy(&self) -> LightSource {
+        *self
+    }
+    /// Returns the position of the light source.
+    #[wasm_bindgen(getter, return_description = "The position of the light source.")]
+    pub fn position(&self) -> Point3D {
+        self.position
+    }
+    /// Returns the ambient brightness of the light source.
+    #[wasm_bindgen(getter, return_description = "The ambient brightness of the light source.")]
+    pub fn ambient(&self) -> f32 {
+        self.ambient
+    }
+    /// Returns the brightness of a surface point, between the ambient brightness and 1, following Lambert's cosine law.
+    #[wasm_bindgen(return_description = "The brightness of the point.")]
+    pub fn brightness(
+        &self,
+        #[wasm_bindgen(param_description = "The point of the surface.")]
+        point: Point3D,
+        #[wasm_bindgen(param_description = "The unit normal of the surface at the point.")]
+        normal: Point3D,
+    ) -> f32 {
+        let direction = (self.position - point).normalized();
+        self.ambient + (1.0 - self.ambient) * normal.dot(&direction).max(0.0)
+    }
+    /// Shades a Color by a brightness, keeping its alpha.
+    #[wasm_bindgen(return_description = "The shaded color.")]
+    pub fn shade_color(
+        #[wasm_bindgen(param_description = "The color to shade.")]
+        color: &Color,
+        #[wasm_bindgen(param_description = "The brightness, between 0 and 1.")]
+        brightness: f32,
+    ) -> Color {
+        Color::new(
+            (color.red as f32 * brightness).round() as u8,
+            (color.green as f32 * brightness).round() as u8,
+            (color.blue as f32 * brightness).round() as u8,
+            color.alpha,
+        )
+    }
+}
+
+impl LightSource {
+    /// Shades a Style by a brightness. Only solid colors are shaded, gradients and images are kept as they are.
+    pub fn shade_style(style: &Style, brightness: f32) -> Style {
+        match style.color() {
+            Some(color) => Style::from_color(LightSource::shade_color(&color, brightness)),
+            None => style.clone(),
+        }
+    }
+}
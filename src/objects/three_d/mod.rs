@@ -0,0 +1,3 @@
+pub mod camera;
+pub mod light_source;
+pub mod three_d_object;
\ No newline at end of file
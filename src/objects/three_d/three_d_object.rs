@@ -0,0 +1,396 @@
+use std::{cmp::Ordering, rc::Rc};
+
+use wasm_bindgen::prelude::*;
+
+use crate::{objects::vector_object::{VectorObject, VectorObjectBuilder}, utils::{point3d::{Path3D, Point3D}, style::Style}};
+
+use super::{camera::Camera3D, light_source::LightSource};
+
+/// A ThreeDObject is a 3D path made of cubic Bézier curves with fill and stroke styles and children, such as a face of a mesh or the whole mesh.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct ThreeDObject {
+    /// The path of the object.
+    path: Path3D,
+    /// The fill style of the object.
+    fill: Style,
+    /// The stroke style of the object.
+    stroke: Style,
+    /// The stroke width of the object.
+    stroke_width: f32,
+    /// The children of the object.
+    children: Vec<ThreeDObject>,
+    /// The name of the object.
+    name: Option<Rc<String>>,
+}
+
+impl Default for ThreeDObject {
+    fn default() -> Self {
+        ThreeDObject {
+            path: Path3D::default(),
+            fill: Style::default(),
+            stroke: Style::default(),
+            stroke_width: 1.0,
+            children: Vec::new(),
+            name: None,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl ThreeDObject {
+    /// Creates a new ThreeDObject from a Path3D.
+    #[wasm_bindgen(constructor, return_description = "A new 3D object.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The path of the object.")]
+        path: Path3D,
+        #[wasm_bindgen(param_description = "The fill style of the object, default is transparent.")]
+        fill: Option<Style>,
+        #[wasm_bindgen(param_description = "The stroke style of the object, default is transparent.")]
+        stroke: Option<Style>,
+        #[wasm_bindgen(param_description = "The stroke width of the object, default is 1.")]
+        stroke_width: Option<f32>,
+    ) -> ThreeDObject {
+        ThreeDObject {
+            path,
+            fill: fill.unwrap_or_default(),
+            stroke: stroke.unwrap_or_default(),
+            stroke_width: stroke_width.unwrap_or(1.0),
+            ..ThreeDObject::default()
+        }
+    }
+    /// Creates a ThreeDObject without a path that groups other objects.
+    #[wasm_bindgen(return_description = "A 3D object grouping the children.")]
+    pub fn group(
+        #[wasm_bindgen(param_description = "The children of the group.")]
+        children: Vec<ThreeDObject>,
+    ) -> ThreeDObject {
+        ThreeDObject { children, ..ThreeDObject::default() }
+    }
+    /// Creates a flat polygon with straight edges.
+    #[wasm_bindgen(return_description = "A 3D polygon.")]
+    pub fn polygon(
+        #[wasm_bindgen(param_description = "The vertices of the polygon.")]
+        vertices: Vec<Point3D>,
+    ) -> ThreeDObject {
+        ThreeDObject { path: Path3D::polygon(vertices), ..ThreeDObject::default() }
+    }
+    /// Creates a polygon mesh, a group whose children are its faces.
+    #[wasm_bindgen(return_description = "A 3D polygon mesh.")]
+    pub fn mesh(
+        #[wasm_bindgen(param_description = "The vertices of the mesh.")]
+        vertices: Vec<Point3D>,
+        #[wasm_bindgen(param_description = "The indices of the vertices of every face, one face after another.")]
+        faces: Vec<usize>,
+        #[wasm_bindgen(param_description = "The number of vertices of every face, such as 3 for triangles or 4 for quads.")]
+        face_size: usize,
+    ) -> Result<ThreeDObject, JsError> {
+        if face_size == 0 || !faces.len().is_multiple_of(face_size) {
+            return Err(JsError::new(&format!("The number of face indices, {}, must be a multiple of the face size, {}.", faces.len(), face_size)));
+        }
+        ThreeDObject::mesh_from_faces(&vertices, &faces.chunks(face_size).map(|face| face.to_vec()).collect::<Vec<Vec<usize>>>())
+            .map_err(|error| JsError::new(&error))
+    }
+    /// Creates an axis-aligned cube mesh centered at the origin.
+    #[wasm_bindgen(return_description = "A cube mesh.")]
+    pub fn cube(
+        #[wasm_bindgen(param_description = "The length of the sides of the cube.")]
+        side: f32,
+    ) -> ThreeDObject {
+        let h = side / 2.0;
+        let vertices = (0..8).map(|i| Point3D::new(
+            if i & 1 == 0 { -h } else { h },
+            if i & 2 == 0 { -h } else { h },
+            if i & 4 == 0 { -h } else { h },
+        )).collect::<Vec<Point3D>>();
+        let faces = vec![
+            vec![0, 2, 3, 1],
+            vec![4, 5, 7, 6],
+            vec![0, 1, 5, 4],
+            vec![2, 6, 7, 3],
+            vec![0, 4, 6, 2],
+            vec![1, 3, 7, 5],
+        ];
+        ThreeDObject::mesh_from_faces(&vertices, &faces).unwrap()
+    }
+    /// Creates a UV sphere mesh centered at the origin.
+    #[wasm_bindgen(return_description = "A sphere mesh.")]
+    pub fn sphere(
+        #[wasm_bindgen(param_description = "The radius of the sphere.")]
+        radius: f32,
+        #[wasm_bindgen(param_description = "The number of faces around the equator, default is 24.")]
+        u_resolution: Option<usize>,
+        #[wasm_bindgen(param_description = "The number of faces from pole to pole, default is 12.")]
+        v_resolution: Option<usize>,
+    ) -> ThreeDObject {
+        let u_resolution = u_resolution.unwrap_or(24).max(3);
+        let v_resolution = v_resolution.unwrap_or(12).max(2);
+        let point = |i: usize, j: usize| {
+            let theta = std::f32::consts::TAU * i as f32 / u_resolution as f32;
+            let phi = std::f32::consts::PI * j as f32 / v_resolution as f32;
+            Point3D::new(radius * phi.sin() * theta.cos(), radius * phi.cos(), radius * phi.sin() * theta.sin())
+        };
+        let mut faces = Vec::with_capacity(u_resolution * v_resolution);
+        for j in 0..v_resolution {
+            for i in 0..u_resolution {
+                let mut vertices = vec![point(i, j), point(i + 1, j), point(i + 1, j + 1), point(i, j + 1)];
+                // The faces touching the poles are triangles.
+                vertices.dedup_by(|a, b| a.distance(b) < 1e-6);
+                if vertices.len() > 3 && vertices[0].distance(&vertices[vertices.len() - 1]) < 1e-6 {
+                    vertices.pop();
+                }
+                faces.push(ThreeDObject::polygon(vertices));
+            }
+        }
+        ThreeDObject::group(faces)
+    }
+    /// Clones the 3D object.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn copy(&self) -> ThreeDObject {
+        self.clone()
+    }
+    /// Returns the path of the object.
+    #[wasm_bindgen(getter, return_description = "The path of the object.")]
+    pub fn path(&self) -> Path3D {
+        self.path.clone()
+    }
+    /// Returns the fill style of the object.
+    #[wasm_bindgen(getter, return_description = "The fill style of the object.")]
+    pub fn fill(&self) -> Style {
+        self.fill.clone()
+    }
+    /// Returns the stroke style of the object.
+    #[wasm_bindgen(getter, return_description = "The stroke style of the object.")]
+    pub fn stroke(&self) -> Style {
+        self.stroke.clone()
+    }
+    /// Returns the stroke width of the object.
+    #[wasm_bindgen(getter, return_description = "The stroke width of the object.")]
+    pub fn stroke_width(&self) -> f32 {
+        self.stroke_width
+    }
+    /// Returns the children of the object.
+    #[wasm_bindgen(getter, return_description = "The children of the object.")]
+    pub fn children(&self) -> Vec<ThreeDObject> {
+        self.children.clone()
+    }
+    /// Returns the name of the object.
+    #[wasm_bindgen(getter, return_description = "The name of the object.")]
+    pub fn name(&self) -> Option<String> {
+        self.name.as_ref().map(|name| name.to_string())
+    }
+    /// Returns a copy of the object with another path.
+    #[wasm_bindgen(return_description = "The object with the new path.")]
+    pub fn with_path(
+        &self,
+        #[wasm_bindgen(param_description = "The new path.")]
+        path: Path3D,
+    ) -> ThreeDObject {
+        ThreeDObject { path, ..self.clone() }
+    }
+    /// Returns a copy of the object with other children.
+    #[wasm_bindgen(return_description = "The object with the new children.")]
+    pub fn with_children(
+        &self,
+        #[wasm_bindgen(param_description = "The new children.")]
+        children: Vec<ThreeDObject>,
+    ) -> ThreeDObject {
+        ThreeDObject { children, ..self.clone() }
+    }
+    /// Returns a copy of the object with another name.
+    #[wasm_bindgen(return_description = "The object with the new name.")]
+    pub fn with_name(
+        &self,
+        #[wasm_bindgen(param_description = "The new name.")]
+        name: Option<String>,
+    ) -> ThreeDObject {
+        ThreeDObject { name: name.map(Rc::new), ..self.clone() }
+    }
+    /// Returns a copy of the object with another fill style.
+    #[wasm_bindgen(return_description = "The object with the new fill style.")]
+    pub fn with_fill(
+        &self,
+        #[wasm_bindgen(param_description = "The new fill style.")]
+        fill: Style,
+        #[wasm_bindgen(param_description = "Whether to set the fill style of the children too, default is true.")]
+        recursive: Option<bool>,
+    ) -> ThreeDObject {
+        self.map_objects(recursive.unwrap_or(true), &|object| ThreeDObject { fill: fill.clone(), ..object.clone() })
+    }
+    /// Returns a copy of the object with another stroke style.
+    #[wasm_bindgen(return_description = "The object with the new stroke style.")]
+    pub fn with_stroke(
+        &self,
+        #[wasm_bindgen(param_description = "The new stroke style.")]
+        stroke: Style,
+        #[wasm_bindgen(param_description = "Whether to set the stroke style of the children too, default is true.")]
+        recursive: Option<bool>,
+    ) -> ThreeDObject {
+        self.map_objects(recursive.unwrap_or(true), &|object| ThreeDObject { stroke: stroke.clone(), ..object.clone() })
+    }
+    /// Returns a copy of the object with another stroke width.
+    #[wasm_bindgen(return_description = "The object with the new stroke width.")]
+    pub fn with_stroke_width(
+        &self,
+        #[wasm_bindgen(param_description = "The new stroke width.")]
+        stroke_width: f32,
+        #[wasm_bindgen(param_description = "Whether to set the stroke width of the children too, default is true.")]
+        recursive: Option<bool>,
+    ) -> ThreeDObject {
+        self.map_objects(recursive.unwrap_or(true), &|object| ThreeDObject { stroke_width, ..object.clone() })
+    }
+    /// Returns the center of the bounding box of the object and its children.
+    #[wasm_bindgen(getter, return_description = "The center of the object.")]
+    pub fn center(&self) -> Option<Point3D> {
+        let points = self.all_points();
+        let first = *points.first()?;
+        let (min, max) = points.iter().fold((first, first), |(min, max), point| (
+            Point3D::new(min.x.min(point.x), min.y.min(point.y), min.z.min(point.z)),
+            Point3D::new(max.x.max(point.x), max.y.max(point.y), max.z.max(point.z)),
+        ));
+        Some(Point3D::lerp(&min, &max, 0.5))
+    }
+    /// Returns a copy of the object moved by an offset.
+    #[wasm_bindgen(return_description = "The shifted object.")]
+    pub fn shift(
+        &self,
+        #[wasm_bindgen(param_description = "The offset to move the object by.")]
+        offset: Point3D,
+    ) -> ThreeDObject {
+        self.map_points(&|point| point + offset)
+    }
+    /// Returns a copy of the object moved so its center is at a point.
+    #[wasm_bindgen(return_description = "The moved object.")]
+    pub fn move_to(
+        &self,
+        #[wasm_bindgen(param_description = "The new center of the object.")]
+        point: Point3D,
+    ) -> ThreeDObject {
+        match self.center() {
+            Some(center) => self.shift(point - center),
+            None => self.clone(),
+        }
+    }
+    /// Returns a copy of the object scaled by a factor along each axis.
+    #[wasm_bindgen(return_description = "The scaled object.")]
+    pub fn scale(
+        &self,
+        #[wasm_bindgen(param_description = "The factors to scale the object by along each axis.")]
+        factors: Point3D,
+        #[wasm_bindgen(param_description = "The point that stays in place, default is the center of the object.")]
+        about_point: Option<Point3D>,
+    ) -> ThreeDObject {
+        let about_point = about_point.or_else(|| self.center()).unwrap_or_default();
+        self.map_points(&|point| {
+            let point = point - about_point;
+            about_point + Point3D::new(point.x * factors.x, point.y * factors.y, point.z * factors.z)
+        })
+    }
+    /// Returns a copy of the object rotated around the x, y and z axes, in that order.
+    #[wasm_bindgen(return_description = "The rotated object.")]
+    pub fn rotate(
+        &self,
+        #[wasm_bindgen(param_description = "The angles in radians around each axis.")]
+        angles: Point3D,
+        #[wasm_bindgen(param_description = "The point that stays in place, default is the origin.")]
+        about_point: Option<Point3D>,
+    ) -> ThreeDObject {
+        let about_point = about_point.unwrap_or_default();
+        self.map_points(&|point| about_point + (point - about_point).rotate(&angles))
+    }
+    /// Returns the leaves of the object with a path, which are the faces drawn by a projection.
+    #[wasm_bindgen(getter, return_description = "The faces of the object.")]
+    pub fn faces(&self) -> Vec<ThreeDObject> {
+        let mut faces = Vec::new();
+        self.collect_faces(&mut faces);
+        faces
+    }
+    /// Projects the object through a camera into a VectorObject whose children are the faces, sorted from the farthest to the nearest so nearer faces are drawn on top.
+    /// If a light source is given, the solid fill colors of the faces are shaded with Lambertian reflectance, lighting both sides of every face.
+    #[wasm_bindgen(return_description = "The projected and shaded object.")]
+    pub fn project_and_shade(
+        &self,
+        #[wasm_bindgen(param_description = "The camera to project the object with.")]
+        camera: &Camera3D,
+        #[wasm_bindgen(param_description = "The light source to shade the faces with. If not given, the faces keep their fill.")]
+        light_source: Option<LightSource>,
+    ) -> VectorObject {
+        let mut faces = self.faces()
+            .into_iter()
+            .map(|face| {
+                let centroid = face.path.centroid().unwrap_or_default();
+                (camera.depth(centroid), centroid, face)
+            })
+            .collect::<Vec<(f32, Point3D, ThreeDObject)>>();
+        faces.sort_by(|(depth1, _, _), (depth2, _, _)| depth2.partial_cmp(depth1).unwrap_or(Ordering::Equal));
+        let children = faces.into_iter().map(|(_, centroid, face)| {
+            let fill = match (light_source, face.path.normal()) {
+                (Some(light_source), Some(normal)) => {
+                    // The normal facing the camera is used, so both sides of a face are lit.
+                    let towards_camera = if camera.orthographic() { camera.backward() } else { camera.position() - centroid };
+                    let normal = if normal.dot(&towards_camera) < 0.0 { -normal } else { normal };
+                    LightSource::shade_style(&face.fill, light_source.brightness(centroid, normal))
+                }
+                _ => face.fill.clone(),
+            };
+            VectorObjectBuilder::default()
+                .set_path(camera.project_path(&face.path))
+                .set_fill(fill, None)
+                .set_stroke(face.stroke.clone(), None)
+                .set_stroke_width(face.stroke_width, None)
+                .set_name(face.name())
+        }).collect::<Vec<VectorObjectBuilder>>();
+        VectorObjectBuilder::default()
+            .add_children(children)
+            .set_name(self.name())
+            .build()
+    }
+}
+
+impl ThreeDObject {
+    /// Creates a polygon mesh from its vertices and the vertex indices of its faces.
+    pub fn mesh_from_faces(vertices: &[Point3D], faces: &[Vec<usize>]) -> Result<ThreeDObject, String> {
+        let mut children = Vec::with_capacity(faces.len());
+        for face in faces {
+            if face.len() < 3 {
+                return Err("Every face of a mesh must have at least 3 vertices.".to_string());
+            }
+            let face_vertices = face.iter()
+                .map(|&index| vertices.get(index).copied().ok_or_else(|| format!("Vertex index {} is out of bounds.", index)))
+                .collect::<Result<Vec<Point3D>, String>>()?;
+            children.push(ThreeDObject::polygon(face_vertices));
+        }
+        Ok(ThreeDObject::group(children))
+    }
+    /// Returns a copy of the object with a function applied to every point of it and its children.
+    pub fn map_points(&self, function: &dyn Fn(Point3D) -> Point3D) -> ThreeDObject {
+        ThreeDObject {
+            path: self.path.map(function),
+            children: self.children.iter().map(|child| child.map_points(function)).collect(),
+            ..self.clone()
+        }
+    }
+    /// Returns a copy of the object with a function applied to it and, optionally, to its descendants.
+    pub fn map_objects(&self, recursive: bool, function: &dyn Fn(&ThreeDObject) -> ThreeDObject) -> ThreeDObject {
+        let mut object = function(self);
+        if recursive {
+            object.children = self.children.iter().map(|child| child.map_objects(true, function)).collect();
+        }
+        object
+    }
+    fn all_points(&self) -> Vec<Point3D> {
+        let mut points = self.path.points();
+        for child in self.children.iter() {
+            points.extend(child.all_points());
+        }
+        points
+    }
+    fn collect_faces(&self, faces: &mut Vec<ThreeDObject>) {
+        if !self.path.is_empty() {
+            faces.push(ThreeDObject { children: Vec::new(), ..self.clone() });
+        }
+        for child in self.children.iter() {
+            child.collect_faces(faces);
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::rc::Rc;
+
+use typst::syntax::{LinkedNode, Source, SyntaxKind};
+use wasm_bindgen::prelude::*;
+
+use crate::{objects::{typesetting::{tex::Tex, typst::{SourceSpan, Typst}}, vector_object::{VectorObject, VectorObjectBuilder}}, utils::{bounding_box::BoundingBox, point2d::Point2D}};
+
+/// Collects the offsets of the line breaks of a source that split an equation or a paragraph into rows, skipping those in the arguments of functions such as `mat` or `cases`.
+fn row_breaks(node: &LinkedNode, breaks: &mut Vec<usize>) {
+    match node.kind() {
+        SyntaxKind::Linebreak => breaks.push(node.offset()),
+        SyntaxKind::FuncCall => {}
+        _ => {
+            for child in node.children() {
+                row_breaks(&child, breaks);
+            }
+        }
+    }
+}
+
+/// Gets the horizontal position of the alignment point of a rendered formula, which is the left edge of the children within the first span that contains any. If no span contains a child, as with an `&` alignment point, it's the left edge of the first child after the first span.
+fn alignment_x(object: &VectorObject, spans: &[SourceSpan]) -> Option<f32> {
+    let children = object.children()
+        .into_iter()
+        .filter_map(|child| Typst::source_span(&child).map(|span| (child, span)))
+        .collect::<Vec<(VectorObject, SourceSpan)>>();
+    let left_edge = |children: Vec<&VectorObject>| children.into_iter()
+        .filter_map(|child| child.bounding_box(None))
+        .map(|bounding_box| bounding_box.min_x())
+        .reduce(f32::min);
+    spans.iter()
+        .find_map(|key| left_edge(children.iter().filter(|(_, span)| span.is_within(key)).map(|(child, _)| child).collect()))
+        .or_else(|| {
+            let key = spans.first()?;
+            let (child, _) = children.iter()
+                .filter(|(_, span)| span.start >= key.end)
+                .min_by_key(|(_, span)| span.start)?;
+            left_edge(vec![child])
+        })
+}
+
+/// Gets the bounding box of the children of a rendered formula that were rendered from its source, leaving out the page background. If none was, the bounding box of the whole object is used.
+fn content_bounding_box(object: &VectorObject) -> Option<BoundingBox> {
+    object.children()
+        .iter()
+        .filter(|child| Typst::source_span(child).is_some())
+        .map(|child| child.bounding_box(None))
+        .fold(None, BoundingBox::union)
+        .or_else(|| object.bounding_box(None))
+}
+
+/// AlignedFormulas is a column of separately rendered formulas, such as the steps of a derivation, aligned on an alignment key like `=`. Lines are added one at a time below the previous one, so they can be shown one at a time while staying aligned.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct AlignedFormulas {
+    /// The substring or label whose first match is aligned in every line.
+    key: Rc<String>,
+    /// The position of the alignment point of the first line, whose top is at its y coordinate.
+    position: Point2D,
+    /// The vertical space between consecutive lines in pixels.
+    line_spacing: f32,
+    /// The lines that were added, already positioned.
+    lines: Rc<Vec<VectorObject>>,
+}
+
+#[wasm_bindgen]
+impl AlignedFormulas {
+    /// Creates a new empty AlignedFormulas column.
+    #[wasm_bindgen(constructor, return_description = "A new AlignedFormulas column.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The substring of the source, such as `=` or `&`, or the label such as `<name>`, whose first match is aligned in every line.")]
+        key: String,
+        #[wasm_bindgen(param_description = "The position of the alignment point of the first line, whose top is at its y coordinate, default is (0, 0).")]
+        position: Option<Point2D>,
+        #[wasm_bindgen(param_description = "The vertical space between consecutive lines in pixels, default is 10.")]
+        line_spacing: Option<f32>,
+    ) -> AlignedFormulas {
+        AlignedFormulas {
+            key: Rc::new(key),
+            position: position.unwrap_or(Point2D::new(0.0, 0.0)),
+            line_spacing: line_spacing.unwrap_or(10.0),
+            lines: Rc::new(Vec::new()),
+        }
+    }
+    /// Clones the AlignedFormulas column.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn copy(&self) -> AlignedFormulas {
+        self.clone()
+    }
+    /// Returns the substring or label aligned in every line.
+    #[wasm_bindgen(getter, return_description = "The alignment key.")]
+    pub fn key(&self) -> String {
+        self.key.to_string()
+    }
+    /// Returns the position of the alignment point of the first line.
+    #[wasm_bindgen(getter, return_description = "The position of the alignment point.")]
+    pub fn position(&self) -> Point2D {
+        self.position
+    }
+    /// Returns the vertical space between consecutive lines in pixels.
+    #[wasm_bindgen(getter, return_description = "The space between lines in pixels.")]
+    pub fn line_spacing(&self) -> f32 {
+        self.line_spacing
+    }
+    /// Returns the lines that were added, positioned in the column.
+    #[wasm_bindgen(getter, return_description = "The positioned lines.")]
+    pub fn lines(&self) -> Vec<VectorObject> {
+        self.lines.to_vec()
+    }
+    /// Returns the number of lines that were added.
+    #[wasm_bindgen(getter, return_description = "The number of lines.")]
+    pub fn num_lines(&self) -> usize {
+        self.lines.len()
+    }
+    /// Adds a rendered formula below the previous line, leaving the line spacing between their contents, with the children within the given spans of its source at the alignment point. If no span contains a child, as with an `&` alignment point, the first child after the first span is placed there.
+    #[wasm_bindgen(return_description = "The added line, positioned in the column.")]
+    pub fn add_line(
+        &mut self,
+        #[wasm_bindgen(param_description = "The rendered formula, whose children are named after their spans in the source.")]
+        object: &VectorObject,
+        #[wasm_bindgen(param_description = "The spans of the source whose first rendered match is aligned.")]
+        spans: Vec<SourceSpan>,
+    ) -> Result<VectorObject, JsError> {
+        let bounding_box = content_bounding_box(object).ok_or_else(|| JsError::new("The line has nothing to align."))?;
+        let x = alignment_x(object, &spans).ok_or_else(|| JsError::new("The line doesn't contain the alignment key."))?;
+        let top = self.lines.last()
+            .and_then(content_bounding_box)
+            .map(|previous| previous.min_y() + previous.height() + self.line_spacing)
+            .unwrap_or(self.position.y);
+        let line = VectorObjectBuilder::new(object)
+            .shift(self.position.x - x, top - bounding_box.min_y(), None)
+            .build();
+        let mut lines = self.lines.to_vec();
+        lines.push(line.clone());
+        self.lines = Rc::new(lines);
+        Ok(line)
+    }
+    /// Adds a formula rendered from a Typst object below the previous line, aligned on the first match of the alignment key in its source.
+    #[wasm_bindgen(return_description = "The added line, positioned in the column.")]
+    pub fn add_typst(
+        &mut self,
+        #[wasm_bindgen(param_description = "The Typst object the formula was rendered from.")]
+        formula: &Typst,
+        #[wasm_bindgen(param_description = "The vector object rendered from the Typst object.")]
+        object: &VectorObject,
+        #[wasm_bindgen(param_description = "The substring or label to align in this line instead of the alignment key of the column.")]
+        key: Option<String>,
+    ) -> Result<VectorObject, JsError> {
+        let spans = formula.spans_of(key.as_deref().unwrap_or(&self.key));
+        self.add_line(object, spans)
+    }
+    /// Adds a formula rendered from a Tex object below the previous line, aligned on the first match of the alignment key in its LaTeX source.
+    #[wasm_bindgen(return_description = "The added line, positioned in the column.")]
+    pub fn add_tex(
+        &mut self,
+        #[wasm_bindgen(param_description = "The Tex object the formula was rendered from.")]
+        formula: &Tex,
+        #[wasm_bindgen(param_description = "The vector object rendered from the Tex object.")]
+        object: &VectorObject,
+        #[wasm_bindgen(param_description = "The substring to align in this line instead of the alignment key of the column.")]
+        key: Option<String>,
+    ) -> Result<VectorObject, JsError> {
+        let spans = formula.spans_of(key.as_deref().unwrap_or(&self.key));
+        self.add_line(object, spans)
+    }
+    /// Returns a VectorObjectBuilder whose children are the positioned lines, named `line_1`, `line_2` and so on.
+    #[wasm_bindgen(return_description = "A VectorObjectBuilder representing the column.")]
+    pub fn vector_object_builder(&self) -> VectorObjectBuilder {
+        let children = self.lines.iter()
+            .enumerate()
+            .map(|(index, line)| VectorObjectBuilder::new(line).set_name(Some(format!("line_{}", index + 1))))
+            .collect();
+        VectorObjectBuilder::default().set_children(children)
+    }
+    /// Splits a formula rendered from a multi-line Typst block, such as `$ a &= b \ &= c $`, into its rows at the line breaks of its source. Rows keep the positions Typst gave them, so they stay aligned on their `&` alignment points and can be shown one at a time. Line breaks within function arguments, such as those of `mat` or `cases`, don't split rows.
+    #[wasm_bindgen(return_description = "The rows of the formula, named `row_1`, `row_2` and so on.")]
+    pub fn rows(
+        #[wasm_bindgen(param_description = "The Typst object the formula was rendered from.")]
+        formula: &Typst,
+        #[wasm_bindgen(param_description = "The vector object rendered from the Typst object.")]
+        object: &VectorObject,
+    ) -> Vec<VectorObject> {
+        let source = Source::detached(formula.source());
+        let mut breaks = vec![];
+        row_breaks(&LinkedNode::new(source.root()), &mut breaks);
+        let mut rows = vec![vec![]; breaks.len() + 1];
+        // Children without a span, such as those of the settings, belong to the row of the child before them.
+        let mut row = 0;
+        for child in object.children() {
+            if let Some(span) = Typst::source_span(&child) {
+                row = breaks.iter().filter(|offset| **offset < span.start).count();
+            }
+            rows[row].push(VectorObjectBuilder::new(&child));
+        }
+        rows.into_iter()
+            .enumerate()
+            .map(|(index, children)| {
+                VectorObjectBuilder::default()
+                    .set_children(children)
+                    .set_name(Some(format!("row_{}", index + 1)))
+                    .build()
+            })
+            .collect()
+    }
+}
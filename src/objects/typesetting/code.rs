@@ -0,0 +1,545 @@
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+
+use crate::{objects::{geometry::rectangle::Rectangle, typesetting::{text::Text, typst::span_range}, vector_object::{VectorObject, VectorObjectBuilder}}, utils::{bounding_box::BoundingBox, font_face::FontFace, style::{Color, Style}}};
+
+/// The distance between consecutive baselines of a Code object, relative to the font size.
+const LINE_HEIGHT: f32 = 1.4;
+
+/// The kind of a token of a Code object, which decides its color.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    /// A reserved word of the language, such as `fn` or `return`.
+    Keyword,
+    /// The name of a type, such as `String`, or a lifetime.
+    Type,
+    /// The name of a called function or macro.
+    Function,
+    /// A string or character literal.
+    String,
+    /// A number literal.
+    Number,
+    /// A comment.
+    Comment,
+    /// An operator, such as `+` or `=>`.
+    Operator,
+    /// A punctuation mark, such as a bracket or a comma.
+    Punctuation,
+    /// Any other name.
+    Identifier,
+    /// A word of code that isn't highlighted.
+    Text,
+}
+
+impl TokenKind {
+    /// Gets the prefix of the names of tokens of this kind.
+    fn name(&self) -> &'static str {
+        match self {
+            TokenKind::Keyword => "keyword",
+            TokenKind::Type => "type",
+            TokenKind::Function => "function",
+            TokenKind::String => "string",
+            TokenKind::Number => "number",
+            TokenKind::Comment => "comment",
+            TokenKind::Operator => "operator",
+            TokenKind::Punctuation => "punctuation",
+            TokenKind::Identifier => "identifier",
+            TokenKind::Text => "text",
+        }
+    }
+}
+
+/// Creates an opaque Color from a hexadecimal number such as `0xff8800`.
+fn rgb(hex: u32) -> Color {
+    Color::new((hex >> 16) as u8, (hex >> 8) as u8, hex as u8, 1.0)
+}
+
+/// A CodeTheme is the set of colors a Code object is highlighted with.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct CodeTheme {
+    /// The color of the background box.
+    background: Color,
+    /// The color of identifiers, punctuation and code that isn't highlighted.
+    foreground: Color,
+    /// The color of the line numbers.
+    line_number: Color,
+    /// The color of keywords.
+    keyword: Color,
+    /// The color of types.
+    type_name: Color,
+    /// The color of functions.
+    function: Color,
+    /// The color of strings.
+    string: Color,
+    /// The color of numbers.
+    number: Color,
+    /// The color of comments.
+    comment: Color,
+    /// The color of operators.
+    operator: Color,
+}
+
+#[wasm_bindgen]
+impl CodeTheme {
+    /// Creates a new CodeTheme from its colors.
+    #[wasm_bindgen(constructor, return_description = "A new code theme.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The color of the background box.")]
+        background: Color,
+        #[wasm_bindgen(param_description = "The color of identifiers, punctuation and code that isn't highlighted.")]
+        foreground: Color,
+        #[wasm_bindgen(param_description = "The color of the line numbers.")]
+        line_number: Color,
+        #[wasm_bindgen(param_description = "The color of keywords.")]
+        keyword: Color,
+        #[wasm_bindgen(param_description = "The color of types.")]
+        type_name: Color,
+        #[wasm_bindgen(param_description = "The color of functions.")]
+        function: Color,
+        #[wasm_bindgen(param_description = "The color of strings.")]
+        string: Color,
+        #[wasm_bindgen(param_description = "The color of numbers.")]
+        number: Color,
+        #[wasm_bindgen(param_description = "The color of comments.")]
+        comment: Color,
+        #[wasm_bindgen(param_description = "The color of operators.")]
+        operator: Color,
+    ) -> CodeTheme {
+        CodeTheme { background, foreground, line_number, keyword, type_name, function, string, number, comment, operator }
+    }
+    /// Returns a light theme with a white background.
+    #[wasm_bindgen(return_description = "The light code theme.")]
+    pub fn light() -> CodeTheme {
+        CodeTheme::new(rgb(0xffffff), rgb(0x24292f), rgb(0x8c959f), rgb(0xcf222e), rgb(0x953800), rgb(0x8250df), rgb(0x0a3069), rgb(0x0550ae), rgb(0x6e7781), rgb(0xcf222e))
+    }
+    /// Returns a dark theme with a dark gray background.
+    #[wasm_bindgen(return_description = "The dark code theme.")]
+    pub fn dark() -> CodeTheme {
+        CodeTheme::new(rgb(0x282c34), rgb(0xabb2bf), rgb(0x636d83), rgb(0xc678dd), rgb(0xe5c07b), rgb(0x61afef), rgb(0x98c379), rgb(0xd19a66), rgb(0x7f848e), rgb(0x56b6c2))
+    }
+    /// Returns the Monokai theme.
+    #[wasm_bindgen(return_description = "The Monokai code theme.")]
+    pub fn monokai() -> CodeTheme {
+        CodeTheme::new(rgb(0x272822), rgb(0xf8f8f2), rgb(0x90908a), rgb(0xf92672), rgb(0x66d9ef), rgb(0xa6e22e), rgb(0xe6db74), rgb(0xae81ff), rgb(0x75715e), rgb(0xf92672))
+    }
+    /// Returns a built-in theme from its name, which is `light`, `dark` or `monokai`.
+    #[wasm_bindgen(return_description = "The code theme with the name.")]
+    pub fn from_name(
+        #[wasm_bindgen(param_description = "The name of the theme.")]
+        name: &str,
+    ) -> Result<CodeTheme, JsError> {
+        match name {
+            "light" => Ok(CodeTheme::light()),
+            "dark" => Ok(CodeTheme::dark()),
+            "monokai" => Ok(CodeTheme::monokai()),
+            _ => Err(JsError::new(&format!("Unknown code theme `{}`. The built-in themes are `light`, `dark` and `monokai`.", name))),
+        }
+    }
+    /// Clones the code theme.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn copy(&self) -> CodeTheme {
+        self.clone()
+    }
+    /// Returns the color of the background box.
+    #[wasm_bindgen(getter, return_description = "The color of the background box.")]
+    pub fn background(&self) -> Color {
+        self.background
+    }
+    /// Returns the color of the line numbers.
+    #[wasm_bindgen(getter, return_description = "The color of the line numbers.")]
+    pub fn line_number(&self) -> Color {
+        self.line_number
+    }
+    /// Returns the color of the tokens of a kind.
+    #[wasm_bindgen(return_description = "The color of the tokens of the kind.")]
+    pub fn color_of(
+        &self,
+        #[wasm_bindgen(param_description = "The kind of the tokens.")]
+        kind: TokenKind,
+    ) -> Color {
+        match kind {
+            TokenKind::Keyword => self.keyword,
+            TokenKind::Type => self.type_name,
+            TokenKind::Function => self.function,
+            TokenKind::String => self.string,
+            TokenKind::Number => self.number,
+            TokenKind::Comment => self.comment,
+            TokenKind::Operator => self.operator,
+            TokenKind::Punctuation | TokenKind::Identifier | TokenKind::Text => self.foreground,
+        }
+    }
+}
+
+/// The lexical rules of a language that are needed to highlight it.
+struct Language {
+    /// The reserved words of the language, including literals such as `true`.
+    keywords: &'static [&'static str],
+    /// The built-in types of the language.
+    types: &'static [&'static str],
+    /// The start of a comment that lasts until the end of the line.
+    line_comment: &'static str,
+    /// The start and end of a comment that can span several lines, if the language has one.
+    block_comment: Option<(&'static str, &'static str)>,
+    /// The characters that start and end a string.
+    quotes: &'static str,
+    /// Whether tripled quotes start a string that can span several lines, like in Python.
+    triple_quotes: bool,
+    /// Whether a single quote not closed right after a character starts a lifetime, like in Rust.
+    lifetimes: bool,
+}
+
+/// The names of the languages that can be highlighted.
+const LANGUAGES: &str = "`rust`, `python`, `javascript`, `typescript`, `c`, `cpp`, `java` and `text`";
+
+/// Gets the lexical rules of a language from its name, or none to not highlight the code.
+fn language(name: &str) -> Result<Option<Language>, String> {
+    const C_TYPES: &[&str] = &["void", "char", "short", "int", "long", "float", "double", "signed", "unsigned", "bool", "size_t"];
+    Ok(Some(match name.to_ascii_lowercase().as_str() {
+        "rust" | "rs" => Language {
+            keywords: &["as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while"],
+            types: &["bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize", "f32", "f64"],
+            line_comment: "//",
+            block_comment: Some(("/*", "*/")),
+            quotes: "\"'",
+            triple_quotes: false,
+            lifetimes: true,
+        },
+        "python" | "py" => Language {
+            keywords: &["and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif", "else", "except", "False", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda", "None", "nonlocal", "not", "or", "pass", "raise", "return", "True", "try", "while", "with", "yield"],
+            types: &["int", "float", "complex", "str", "bytes", "bool", "list", "tuple", "dict", "set", "frozenset", "object"],
+            line_comment: "#",
+            block_comment: None,
+            quotes: "\"'",
+            triple_quotes: true,
+            lifetimes: false,
+        },
+        "javascript" | "js" | "typescript" | "ts" => Language {
+            keywords: &["abstract", "as", "async", "await", "break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete", "do", "else", "enum", "export", "extends", "false", "finally", "for", "from", "function", "if", "implements", "import", "in", "instanceof", "interface", "let", "new", "null", "of", "private", "protected", "public", "readonly", "return", "static", "super", "switch", "this", "throw", "true", "try", "type", "typeof", "undefined", "var", "void", "while", "yield"],
+            types: &["any", "boolean", "never", "number", "object", "string", "symbol", "unknown", "bigint"],
+            line_comment: "//",
+            block_comment: Some(("/*", "*/")),
+            quotes: "\"'`",
+            triple_quotes: false,
+            lifetimes: false,
+        },
+        "c" | "cpp" | "c++" | "h" | "hpp" => Language {
+            keywords: &["auto", "break", "case", "catch", "class", "const", "constexpr", "continue", "default", "delete", "do", "else", "enum", "extern", "false", "for", "goto", "if", "inline", "namespace", "new", "nullptr", "NULL", "operator", "private", "protected", "public", "register", "return", "sizeof", "static", "struct", "switch", "template", "this", "throw", "true", "try", "typedef", "typename", "union", "using", "virtual", "volatile", "while", "#include", "#define", "#ifdef", "#ifndef", "#endif", "#if", "#else", "#pragma"],
+            types: C_TYPES,
+            line_comment: "//",
+            block_comment: Some(("/*", "*/")),
+            quotes: "\"'",
+            triple_quotes: false,
+            lifetimes: false,
+        },
+        "java" => Language {
+            keywords: &["abstract", "assert", "break", "case", "catch", "class", "const", "continue", "default", "do", "else", "enum", "extends", "false", "final", "finally", "for", "if", "implements", "import", "instanceof", "interface", "native", "new", "null", "package", "private", "protected", "public", "return", "static", "super", "switch", "synchronized", "this", "throw", "throws", "true", "try", "var", "void", "volatile", "while"],
+            types: &["boolean", "byte", "char", "short", "int", "long", "float", "double"],
+            line_comment: "//",
+            block_comment: Some(("/*", "*/")),
+            quotes: "\"'",
+            triple_quotes: false,
+            lifetimes: false,
+        },
+        "text" | "txt" | "plain" => return Ok(None),
+        _ => return Err(format!("Unsupported language `{}`. The supported languages are {}.", name, LANGUAGES)),
+    }))
+}
+
+/// Splits code into tokens, each given by its kind and byte range. Whitespace isn't a token, and tokens such as block comments may span several lines.
+fn tokenize(code: &str, language: Option<&Language>) -> Vec<(TokenKind, usize, usize)> {
+    let mut tokens = vec![];
+    let Some(language) = language else {
+        let mut start = None;
+        for (index, character) in code.char_indices().chain([(code.len(), ' ')]) {
+            match (character.is_whitespace(), start) {
+                (true, Some(word_start)) => {
+                    tokens.push((TokenKind::Text, word_start, index));
+                    start = None;
+                }
+                (false, None) => start = Some(index),
+                _ => {}
+            }
+        }
+        return tokens;
+    };
+    let is_word = |character: char| character.is_alphanumeric() || character == '_';
+    let mut position = 0;
+    while let Some(character) = code[position..].chars().next() {
+        let rest = &code[position..];
+        let start = position;
+        let length_until = |end: &str, skip: usize| rest[skip..].find(end).map_or(rest.len(), |index| skip + index + end.len());
+        let (kind, length) = if character.is_whitespace() {
+            position += character.len_utf8();
+            continue;
+        } else if rest.starts_with(language.line_comment) {
+            (TokenKind::Comment, rest.find('\n').unwrap_or(rest.len()))
+        } else if let Some((open, close)) = language.block_comment.filter(|(open, _)| rest.starts_with(open)) {
+            (TokenKind::Comment, length_until(close, open.len()))
+        } else if language.triple_quotes && (rest.starts_with("\"\"\"") || rest.starts_with("'''")) {
+            (TokenKind::String, length_until(&rest[..3], 3))
+        } else if language.lifetimes && character == '\'' && rest[1..].chars().nth(1).is_some_and(|next| next != '\'') && rest[1..].starts_with(is_word) {
+            (TokenKind::Type, 1 + rest[1..].find(|character: char| !is_word(character)).unwrap_or(rest.len() - 1))
+        } else if language.quotes.contains(character) {
+            // Strings end at the matching unescaped quote, and only backtick strings continue on the next line.
+            let mut escaped = false;
+            let end = rest.char_indices().skip(1).find(|(_, next)| {
+                let found = !escaped && (*next == character || (*next == '\n' && character != '`'));
+                escaped = !escaped && *next == '\\';
+                found
+            });
+            (TokenKind::String, end.map_or(rest.len(), |(index, next)| if next == '\n' { index } else { index + 1 }))
+        } else if character.is_ascii_digit() || (character == '.' && rest[1..].starts_with(|next: char| next.is_ascii_digit())) {
+            let mut length = 0;
+            for (index, next) in rest.char_indices() {
+                if !(is_word(next) || (next == '.' && rest[index + 1..].starts_with(|after: char| after.is_ascii_digit()))) {
+                    break;
+                }
+                length = index + next.len_utf8();
+            }
+            (TokenKind::Number, length)
+        } else if is_word(character) || (character == '#' && rest[1..].starts_with(char::is_alphabetic)) {
+            let mut length = character.len_utf8() + rest[character.len_utf8()..].find(|next: char| !is_word(next)).unwrap_or(rest.len() - character.len_utf8());
+            let word = &rest[..length];
+            let after = rest[length..].trim_start_matches([' ', '\t']);
+            let kind = if language.keywords.contains(&word) {
+                TokenKind::Keyword
+            } else if language.types.contains(&word) {
+                TokenKind::Type
+            } else if language.lifetimes && rest[length..].starts_with('!') && rest[length + 1..].starts_with(['(', '[', '{']) {
+                length += 1;
+                TokenKind::Function
+            } else if after.starts_with('(') {
+                TokenKind::Function
+            } else if word.starts_with(char::is_uppercase) {
+                TokenKind::Type
+            } else {
+                TokenKind::Identifier
+            };
+            (kind, length)
+        } else if "+-*/%=<>!&|^~?:".contains(character) {
+            (TokenKind::Operator, rest.find(|next: char| !"+-*/%=<>!&|^~?:".contains(next)).unwrap_or(rest.len()))
+        } else {
+            (TokenKind::Punctuation, character.len_utf8())
+        };
+        position += length.max(character.len_utf8());
+        tokens.push((kind, start, position));
+    }
+    tokens
+}
+
+/// Expands the tabs of code into spaces up to the next tab stop.
+fn expand_tabs(code: &str, tab_size: usize) -> String {
+    let mut expanded = String::with_capacity(code.len());
+    let mut column = 0;
+    for character in code.chars() {
+        match character {
+            '\t' => {
+                let spaces = tab_size - column % tab_size.max(1);
+                expanded.push_str(&" ".repeat(spaces));
+                column += spaces;
+            }
+            '\n' => {
+                expanded.push('\n');
+                column = 0;
+            }
+            _ => {
+                expanded.push(character);
+                column += 1;
+            }
+        }
+    }
+    expanded
+}
+
+/// A Code is a block of source code with syntax highlighting, laid out line by line with a monospace FontFace. It can have line numbers and a background box.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct Code {
+    /// The code, with its tabs expanded into spaces.
+    code: Rc<String>,
+    /// The language the code is highlighted as.
+    language: Rc<String>,
+    /// The tokens of the code with their kinds and byte ranges.
+    tokens: Rc<Vec<(TokenKind, usize, usize)>>,
+    /// The font face to lay out the code with, usually a monospace one.
+    font_face: FontFace,
+    /// The size of the font in pixels.
+    font_size: f32,
+    /// The colors of the code.
+    theme: CodeTheme,
+    /// Whether each line is numbered.
+    line_numbers: bool,
+    /// Whether the code is drawn over a background box.
+    background: bool,
+    /// The space between the code and the edges of the background box in pixels.
+    padding: f32,
+}
+
+impl Code {
+    /// Lays out a piece of text on one line with the font face of the code, with its top left corner at a position.
+    fn line_text(&self, text: &str, color: Color, x: f32, y: f32) -> Result<(Vec<VectorObject>, f32), JsError> {
+        let text = Text::new(text.to_string(), self.font_face.clone(), Some(self.font_size), Some(color), None, None, None, Some(false), Some(false), None);
+        let object = text.vector_object_builder()?.shift(x, y, None).build();
+        Ok((object.children(), text.width()?))
+    }
+}
+
+#[wasm_bindgen]
+impl Code {
+    /// Creates a new Code object, failing if the language isn't supported.
+    #[wasm_bindgen(constructor, return_description = "A new Code object.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The source code, whose tabs are expanded into spaces.")]
+        code: String,
+        #[wasm_bindgen(param_description = "The font face to lay out the code with, usually a monospace one.")]
+        font_face: FontFace,
+        #[wasm_bindgen(param_description = "The language to highlight the code as, which is `rust`, `python`, `javascript`, `typescript`, `c`, `cpp`, `java` or `text`, default is `text`.")]
+        language: Option<String>,
+        #[wasm_bindgen(param_description = "The size of the font in pixels, default is 16.")]
+        font_size: Option<f32>,
+        #[wasm_bindgen(param_description = "The colors of the code, default is CodeTheme.dark().")]
+        theme: Option<CodeTheme>,
+        #[wasm_bindgen(param_description = "Whether each line is numbered, default is true.")]
+        line_numbers: Option<bool>,
+        #[wasm_bindgen(param_description = "Whether the code is drawn over a background box, default is true.")]
+        background: Option<bool>,
+        #[wasm_bindgen(param_description = "The space between the code and the edges of the background box in pixels, default is the font size.")]
+        padding: Option<f32>,
+        #[wasm_bindgen(param_description = "The number of columns between tab stops, default is 4.")]
+        tab_size: Option<usize>,
+    ) -> Result<Code, JsError> {
+        let language = language.unwrap_or("text".to_string());
+        let rules = self::language(&language).map_err(|error| JsError::new(&error))?;
+        let code = expand_tabs(&code, tab_size.unwrap_or(4));
+        let tokens = tokenize(&code, rules.as_ref());
+        let font_size = font_size.unwrap_or(16.0);
+        Ok(Code {
+            code: Rc::new(code),
+            language: Rc::new(language),
+            tokens: Rc::new(tokens),
+            font_face,
+            font_size,
+            theme: theme.unwrap_or_else(CodeTheme::dark),
+            line_numbers: line_numbers.unwrap_or(true),
+            background: background.unwrap_or(true),
+            padding: padding.unwrap_or(font_size),
+        })
+    }
+    /// Clones the Code object.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn copy(&self) -> Code {
+        self.clone()
+    }
+    /// Returns the code, with its tabs expanded into spaces.
+    #[wasm_bindgen(getter, return_description = "The code of the Code object.")]
+    pub fn code(&self) -> String {
+        self.code.to_string()
+    }
+    /// Returns the language the code is highlighted as.
+    #[wasm_bindgen(getter, return_description = "The language of the code.")]
+    pub fn language(&self) -> String {
+        self.language.to_string()
+    }
+    /// Returns the colors of the code.
+    #[wasm_bindgen(getter, return_description = "The theme of the code.")]
+    pub fn theme(&self) -> CodeTheme {
+        self.theme.clone()
+    }
+    /// Returns a copy of the Code object with other colors.
+    #[wasm_bindgen(return_description = "The Code object with the theme.")]
+    pub fn with_theme(
+        &self,
+        #[wasm_bindgen(param_description = "The colors of the code.")]
+        theme: CodeTheme,
+    ) -> Code {
+        Code { theme, ..self.clone() }
+    }
+    /// Returns the number of lines of the code.
+    #[wasm_bindgen(getter, return_description = "The number of lines.")]
+    pub fn line_count(&self) -> usize {
+        self.code.split('\n').count()
+    }
+    /// Returns the kinds of the tokens of the code, in order.
+    #[wasm_bindgen(getter, return_description = "The kinds of the tokens.")]
+    pub fn token_kinds(&self) -> Vec<TokenKind> {
+        self.tokens.iter().map(|(kind, _, _)| *kind).collect()
+    }
+    /// Returns the texts of the tokens of the code, in order.
+    #[wasm_bindgen(getter, return_description = "The texts of the tokens.")]
+    pub fn token_texts(&self) -> Vec<String> {
+        self.tokens.iter().map(|(_, start, end)| self.code[*start..*end].to_string()).collect()
+    }
+    /// Lays out the code and returns a VectorObjectBuilder. Its children are the background box named `background`, if any, followed by one child per line named `line_1`, `line_2` and so on. Each line holds its number named `line_number`, if any, followed by its tokens, named after their kind and byte range in the code such as `keyword_0_2`, whose children are their glyphs. Lines can be found with selectors such as `line_3` and tokens with selectors such as `line_*/keyword_*`.
+    #[wasm_bindgen(return_description = "A VectorObjectBuilder representing the code.")]
+    pub fn vector_object_builder(&self) -> Result<VectorObjectBuilder, JsError> {
+        let line_count = self.line_count();
+        let line_advance = self.font_size * LINE_HEIGHT;
+        let digits = line_count.to_string();
+        let gutter = if self.line_numbers {
+            let (_, digits_width) = self.line_text(&"0".repeat(digits.len()), self.theme.line_number, 0.0, 0.0)?;
+            digits_width + self.font_size
+        } else {
+            0.0
+        };
+        let code_x = self.padding + gutter;
+        let mut lines = vec![];
+        let mut code_width: f32 = 0.0;
+        let mut line_start = 0;
+        for (index, line) in self.code.split('\n').enumerate() {
+            // Half of the leading goes above each line, so the box fits the lines evenly.
+            let y = self.padding + index as f32 * line_advance + (line_advance - self.font_size) / 2.0;
+            let mut children = vec![];
+            if self.line_numbers {
+                let number = (index + 1).to_string();
+                let (_, number_width) = self.line_text(&number, self.theme.line_number, 0.0, 0.0)?;
+                let (glyphs, _) = self.line_text(&number, self.theme.line_number, self.padding + gutter - self.font_size - number_width, y)?;
+                children.push(
+                    VectorObjectBuilder::default()
+                        .set_children(glyphs.iter().map(VectorObjectBuilder::new).collect())
+                        .set_name(Some("line_number".to_string()))
+                );
+            }
+            let (glyphs, width) = self.line_text(line, self.theme.foreground, code_x, y)?;
+            code_width = code_width.max(width);
+            let line_end = line_start + line.len();
+            for (kind, start, end) in self.tokens.iter().filter(|(_, start, end)| *start < line_end && *end > line_start) {
+                let (start, end) = (*start.max(&line_start), *end.min(&line_end));
+                // Glyphs are named after their range in the line, so they're renamed after their range in the code.
+                let token_glyphs = glyphs.iter()
+                    .filter_map(|glyph| glyph.name().and_then(|name| span_range(&name)).map(|range| (glyph, range)))
+                    .filter(|(_, (glyph_start, glyph_end))| line_start + glyph_start >= start && line_start + glyph_end <= end)
+                    .map(|(glyph, (glyph_start, glyph_end))| {
+                        VectorObjectBuilder::new(glyph).set_name(Some(format!("span_{}_{}", line_start + glyph_start, line_start + glyph_end)))
+                    })
+                    .collect::<Vec<VectorObjectBuilder>>();
+                if token_glyphs.is_empty() {
+                    continue;
+                }
+                children.push(
+                    VectorObjectBuilder::default()
+                        .set_children(token_glyphs)
+                        .set_fill(Style::from_color(self.theme.color_of(*kind)), None)
+                        .set_name(Some(format!("{}_{}_{}", kind.name(), start, end)))
+                );
+            }
+            lines.push(VectorObjectBuilder::default().set_children(children).set_name(Some(format!("line_{}", index + 1))));
+            line_start = line_end + 1;
+        }
+        let mut children = vec![];
+        if self.background {
+            let width = code_x + code_width + self.padding;
+            let height = 2.0 * self.padding + line_count as f32 * line_advance;
+            let bbox = BoundingBox::new(0.0, 0.0, width, height)?;
+            children.push(
+                Rectangle::new(bbox, None).vector_object_builder()
+                    .set_fill(Style::from_color(self.theme.background), None)
+                    .set_name(Some("background".to_string()))
+            );
+        }
+        children.extend(lines);
+        Ok(VectorObjectBuilder::default().set_children(children))
+    }
+}
@@ -0,0 +1,311 @@
+use std::rc::Rc;
+
+use rustybuzz::{ttf_parser::GlyphId, Face};
+use wasm_bindgen::prelude::*;
+
+use crate::{objects::{typesetting::{text::glyph_path, typst::span_name}, vector_object::VectorObjectBuilder}, scene::value_tracker::ValueTracker, utils::{font_face::FontFace, linear_algebra::TransformationMatrix, point2d::Point2D, style::{Color, Style}}};
+
+/// The point of a DecimalNumber that stays at its position when its value changes.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NumberAnchor {
+    /// The left edge of the number, so it grows to the right.
+    Left,
+    /// The center of the number, so it grows to both sides.
+    Center,
+    /// The right edge of the number, so it grows to the left.
+    Right,
+    /// The start of the decimal separator, or the end of the integer part if there are no decimal places, so digits of the same place stay in the same column.
+    DecimalPoint,
+}
+
+/// A glyph of a DecimalNumber laid out on its line.
+struct NumberGlyph {
+    /// The glyph in the font.
+    id: GlyphId,
+    /// The horizontal position of the glyph origin from the start of the line, in font units.
+    x: f32,
+    /// The byte range of the displayed text the glyph was laid out from.
+    range: (usize, usize),
+}
+
+/// A DecimalNumber is a number laid out with a FontFace, such as a counter, a total or an angle, that can be rebuilt with another value every frame without compiling it with Typst.
+/// Digits are laid out in columns as wide as the widest digit of the font, so the number doesn't jitter while its value changes.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct DecimalNumber {
+    /// The value of the number.
+    value: f64,
+    /// The font face to lay out the number with.
+    font_face: FontFace,
+    /// The number of digits after the decimal separator.
+    decimal_places: usize,
+    /// The separator inserted between groups of three digits of the integer part, if any.
+    thousands_separator: Option<Rc<String>>,
+    /// The separator between the integer and the decimal parts.
+    decimal_separator: Rc<String>,
+    /// The text appended after the number, if any.
+    unit: Option<Rc<String>>,
+    /// Whether a plus sign is shown before positive numbers and zero.
+    show_sign: bool,
+    /// The size of the font in pixels.
+    font_size: f32,
+    /// The color of the glyphs.
+    color: Color,
+    /// The point of the number that stays at its position.
+    anchor: NumberAnchor,
+    /// The position of the anchor on the baseline of the number.
+    position: Point2D,
+}
+
+impl DecimalNumber {
+    /// Formats a value with the settings of the number, returning the text and the byte offset of the anchor column within it.
+    fn format(&self, value: f64) -> (String, usize) {
+        let digits = format!("{:.*}", self.decimal_places, value.abs());
+        let (integer, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+        // A value that rounds to zero is shown without a minus sign.
+        let is_zero = digits.chars().all(|character| matches!(character, '0' | '.'));
+        let mut text = if value < 0.0 && !is_zero {
+            "-".to_string()
+        } else if self.show_sign && !value.is_nan() {
+            "+".to_string()
+        } else {
+            String::new()
+        };
+        for (index, digit) in integer.chars().enumerate() {
+            if index > 0 && (integer.len() - index) % 3 == 0 && integer.chars().all(|character| character.is_ascii_digit()) {
+                if let Some(separator) = &self.thousands_separator {
+                    text.push_str(separator);
+                }
+            }
+            text.push(digit);
+        }
+        let decimal_point = text.len();
+        if !fraction.is_empty() {
+            text.push_str(&self.decimal_separator);
+            text.push_str(fraction);
+        }
+        if let Some(unit) = &self.unit {
+            text.push_str(unit);
+        }
+        (text, decimal_point)
+    }
+    /// Parses the font face of the DecimalNumber object, failing if its data isn't a font.
+    fn face<'a>(&self, data: &'a [u8]) -> Result<Face<'a>, JsError> {
+        Face::from_slice(data, 0).ok_or_else(|| JsError::new("The font face of the DecimalNumber object could not be parsed."))
+    }
+    /// Lays out a text on one line, returning the glyph of each character, the horizontal position of a byte offset and the width of the line, in font units.
+    fn layout(&self, face: &Face, text: &str, anchor_offset: usize) -> (Vec<NumberGlyph>, f32, f32) {
+        let glyph_of = |character: char| face.glyph_index(character).unwrap_or(GlyphId(0));
+        let advance_of = |glyph: GlyphId| face.glyph_hor_advance(glyph).unwrap_or(0) as f32;
+        let column = ('0'..='9').map(|digit| advance_of(glyph_of(digit))).fold(0.0, f32::max);
+        let mut glyphs = vec![];
+        let mut x = 0.0;
+        let mut anchor_x = 0.0;
+        for (start, character) in text.char_indices() {
+            if start == anchor_offset {
+                anchor_x = x;
+            }
+            let glyph = glyph_of(character);
+            let advance = advance_of(glyph);
+            let range = (start, start + character.len_utf8());
+            if character.is_ascii_digit() {
+                glyphs.push(NumberGlyph { id: glyph, x: x + (column - advance) / 2.0, range });
+                x += column;
+            } else {
+                glyphs.push(NumberGlyph { id: glyph, x, range });
+                x += advance;
+            }
+        }
+        if anchor_offset >= text.len() {
+            anchor_x = x;
+        }
+        (glyphs, anchor_x, x)
+    }
+    /// Lays out the number with a value and returns a VectorObjectBuilder with its glyphs.
+    fn build(&self, value: f64) -> Result<VectorObjectBuilder, JsError> {
+        let data = self.font_face.shared_data();
+        let face = self.face(&data)?;
+        let scale = self.font_size / face.units_per_em() as f32;
+        let (text, decimal_point) = self.format(value);
+        let (glyphs, anchor_x, width) = self.layout(&face, &text, decimal_point);
+        let start_x = self.position.x - scale * match self.anchor {
+            NumberAnchor::Left => 0.0,
+            NumberAnchor::Center => width / 2.0,
+            NumberAnchor::Right => width,
+            NumberAnchor::DecimalPoint => anchor_x,
+        };
+        let fill = Style::from_color(self.color);
+        let children = glyphs.into_iter()
+            .filter_map(|glyph| {
+                let path = glyph_path(&face, glyph.id, scale)?;
+                // Glyphs are drawn upwards, so they are mirrored vertically around their origin on the baseline.
+                let transform = TransformationMatrix::translate(start_x + glyph.x * scale, self.position.y) * TransformationMatrix::scale(1.0, -1.0);
+                Some(
+                    VectorObjectBuilder::default()
+                        .set_path(path)
+                        .set_fill(fill.clone(), Some(false))
+                        .set_transform(transform, Some(false))
+                        .set_name(Some(span_name(glyph.range.0, glyph.range.1)))
+                )
+            })
+            .collect();
+        Ok(VectorObjectBuilder::default().set_children(children))
+    }
+}
+
+#[wasm_bindgen]
+impl DecimalNumber {
+    /// Creates a new DecimalNumber object from a value and a font face.
+    #[wasm_bindgen(constructor, return_description = "A new DecimalNumber object.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The value of the number.")]
+        value: f64,
+        #[wasm_bindgen(param_description = "The font face to lay out the number with.")]
+        font_face: FontFace,
+        #[wasm_bindgen(param_description = "The number of digits after the decimal separator, default is 2.")]
+        decimal_places: Option<usize>,
+        #[wasm_bindgen(param_description = "The separator inserted between groups of three digits of the integer part, such as `,`, default is none.")]
+        thousands_separator: Option<String>,
+        #[wasm_bindgen(param_description = "The separator between the integer and the decimal parts, default is `.`.")]
+        decimal_separator: Option<String>,
+        #[wasm_bindgen(param_description = "The text appended after the number, such as ` cm` or `°`, default is none.")]
+        unit: Option<String>,
+        #[wasm_bindgen(param_description = "Whether a plus sign is shown before positive numbers and zero, default is false.")]
+        show_sign: Option<bool>,
+        #[wasm_bindgen(param_description = "The size of the font in pixels, default is 16.")]
+        font_size: Option<f32>,
+        #[wasm_bindgen(param_description = "The color of the glyphs, default is black.")]
+        color: Option<Color>,
+        #[wasm_bindgen(param_description = "The point of the number that stays at its position when its value changes, default is NumberAnchor.Left.")]
+        anchor: Option<NumberAnchor>,
+        #[wasm_bindgen(param_description = "The position of the anchor on the baseline of the number, default is (0, 0).")]
+        position: Option<Point2D>,
+    ) -> DecimalNumber {
+        DecimalNumber {
+            value,
+            font_face,
+            decimal_places: decimal_places.unwrap_or(2),
+            thousands_separator: thousands_separator.map(Rc::new),
+            decimal_separator: Rc::new(decimal_separator.unwrap_or(".".to_string())),
+            unit: unit.map(Rc::new),
+            show_sign: show_sign.unwrap_or(false),
+            font_size: font_size.unwrap_or(16.0),
+            color: color.unwrap_or(Color::new(0, 0, 0, 1.0)),
+            anchor: anchor.unwrap_or(NumberAnchor::Left),
+            position: position.unwrap_or(Point2D::new(0.0, 0.0)),
+        }
+    }
+    /// Clones the DecimalNumber object.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn copy(&self) -> DecimalNumber {
+        self.clone()
+    }
+    /// Returns the value of the number.
+    #[wasm_bindgen(getter, return_description = "The value of the number.")]
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+    /// Returns the number of digits after the decimal separator.
+    #[wasm_bindgen(getter, return_description = "The number of decimal places.")]
+    pub fn decimal_places(&self) -> usize {
+        self.decimal_places
+    }
+    /// Returns the separator between groups of three digits of the integer part, if any.
+    #[wasm_bindgen(getter, return_description = "The thousands separator.")]
+    pub fn thousands_separator(&self) -> Option<String> {
+        self.thousands_separator.as_ref().map(|separator| separator.to_string())
+    }
+    /// Returns the separator between the integer and the decimal parts.
+    #[wasm_bindgen(getter, return_description = "The decimal separator.")]
+    pub fn decimal_separator(&self) -> String {
+        self.decimal_separator.to_string()
+    }
+    /// Returns the text appended after the number, if any.
+    #[wasm_bindgen(getter, return_description = "The unit of the number.")]
+    pub fn unit(&self) -> Option<String> {
+        self.unit.as_ref().map(|unit| unit.to_string())
+    }
+    /// Returns whether a plus sign is shown before positive numbers and zero.
+    #[wasm_bindgen(getter, return_description = "Whether the sign of positive numbers is shown.")]
+    pub fn show_sign(&self) -> bool {
+        self.show_sign
+    }
+    /// Returns the size of the font in pixels.
+    #[wasm_bindgen(getter, return_description = "The size of the font in pixels.")]
+    pub fn font_size(&self) -> f32 {
+        self.font_size
+    }
+    /// Returns the color of the glyphs.
+    #[wasm_bindgen(getter, return_description = "The color of the glyphs.")]
+    pub fn color(&self) -> Color {
+        self.color
+    }
+    /// Returns the point of the number that stays at its position.
+    #[wasm_bindgen(getter, return_description = "The anchor of the number.")]
+    pub fn anchor(&self) -> NumberAnchor {
+        self.anchor
+    }
+    /// Returns the position of the anchor on the baseline of the number.
+    #[wasm_bindgen(getter, return_description = "The position of the anchor.")]
+    pub fn position(&self) -> Point2D {
+        self.position
+    }
+    /// Returns the number formatted as it's displayed, whose byte ranges are the ones glyphs are named after.
+    #[wasm_bindgen(getter, return_description = "The displayed text of the number.")]
+    pub fn text(&self) -> String {
+        self.format(self.value).0
+    }
+    /// Returns a copy of the number with another value and the same settings.
+    #[wasm_bindgen(return_description = "A DecimalNumber object with the new value.")]
+    pub fn with_value(
+        &self,
+        #[wasm_bindgen(param_description = "The new value of the number.")]
+        value: f64,
+    ) -> DecimalNumber {
+        DecimalNumber { value, ..self.clone() }
+    }
+    /// Lays out the number and returns its width in pixels.
+    #[wasm_bindgen(return_description = "The width of the number in pixels.")]
+    pub fn width(&self) -> Result<f32, JsError> {
+        let data = self.font_face.shared_data();
+        let face = self.face(&data)?;
+        let (text, decimal_point) = self.format(self.value);
+        let (_, _, width) = self.layout(&face, &text, decimal_point);
+        Ok(width * self.font_size / face.units_per_em() as f32)
+    }
+    /// Lays out the number and returns a VectorObjectBuilder whose children are its glyphs in reading order, with the anchor at the position of the number on its baseline. Each glyph is named after the range of the displayed text it was laid out from, such as `span_2_3`.
+    #[wasm_bindgen(return_description = "A VectorObjectBuilder representing the number.")]
+    pub fn vector_object_builder(&self) -> Result<VectorObjectBuilder, JsError> {
+        self.build(self.value)
+    }
+    /// Lays out the number with the linear interpolation between two values, which is how the number is animated from one value to another.
+    #[wasm_bindgen(return_description = "A VectorObjectBuilder representing the number with the interpolated value.")]
+    pub fn interpolated_vector_object_builder(
+        &self,
+        #[wasm_bindgen(param_description = "The value at the start of the animation.")]
+        start: f64,
+        #[wasm_bindgen(param_description = "The value at the end of the animation.")]
+        end: f64,
+        #[wasm_bindgen(param_description = "The progress value.")]
+        t: f32,
+    ) -> Result<VectorObjectBuilder, JsError> {
+        self.build(start + (end - start) * t as f64)
+    }
+    /// Creates a VectorObjectBuilder that lays out the number with the value of a tracker every time it's built, so it can be rebuilt every frame with UpdaterRegistry.always_rebuild.
+    #[wasm_bindgen(return_description = "A VectorObjectBuilder representing the number with the current value of the tracker.")]
+    pub fn tracked_vector_object_builder(
+        &self,
+        #[wasm_bindgen(param_description = "The tracker holding the value of the number.")]
+        tracker: &ValueTracker,
+    ) -> Result<VectorObjectBuilder, JsError> {
+        tracker.number_value().map_err(|error| JsError::new(&error))?;
+        self.face(&self.font_face.shared_data())?;
+        let number = self.clone();
+        let tracker = tracker.clone();
+        Ok(VectorObjectBuilder::from_factory(Rc::new(move || {
+            let value = tracker.number_value().unwrap_or(number.value);
+            number.build(value).unwrap_or_default()
+        })))
+    }
+}
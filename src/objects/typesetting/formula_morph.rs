@@ -0,0 +1,273 @@
+use std::{collections::HashMap, rc::Rc};
+
+use typst::syntax::{LinkedNode, Source};
+use wasm_bindgen::prelude::*;
+
+use crate::{objects::{typesetting::typst::Typst, vector_object::{VectorObject, VectorObjectBuilder}}, utils::{font_face::FontFace, interpolation::lerp, linear_algebra::TransformationMatrix, point2d::{Path2D, Point2D}, style::Style}};
+
+/// The precision of the coordinates compared when matching children by shape.
+const SHAPE_PRECISION: f32 = 100.0;
+
+/// The information about a child of a rendered formula used to match it.
+struct ChildKey {
+    /// The source text of the child, if it has a span.
+    text: Option<String>,
+    /// The source text of the smallest syntax node strictly enclosing the child, such as `a^2` for the `2` in it.
+    context: Option<String>,
+    /// The shape of the child, with its path relative to its first point and its transform without translation.
+    shape: String,
+}
+
+impl ChildKey {
+    /// Gets the key of a child of a formula rendered from a Source.
+    fn new(child: &VectorObject, source: &Source) -> ChildKey {
+        let span = Typst::source_span(child);
+        let text = span.and_then(|span| source.text().get(span.start..span.end)).map(|text| text.to_string());
+        let context = span
+            .and_then(|span| enclosing_range(&LinkedNode::new(source.root()), span.start, span.end))
+            .and_then(|(start, end)| source.text().get(start..end))
+            .map(|text| text.to_string());
+        ChildKey { text, context, shape: shape_key(child) }
+    }
+}
+
+/// Gets the range of the smallest syntax node below a node whose range strictly contains a range.
+fn enclosing_range(node: &LinkedNode, start: usize, end: usize) -> Option<(usize, usize)> {
+    node.children().find_map(|child| {
+        let range = child.range();
+        if range.start <= start && end <= range.end && (range.start, range.end) != (start, end) {
+            enclosing_range(&child, start, end).or(Some((range.start, range.end)))
+        } else {
+            None
+        }
+    })
+}
+
+/// Gets a string identifying the shape of a VectorObject regardless of its position, so that copies of the same glyph get the same key.
+fn shape_key(object: &VectorObject) -> String {
+    let points = object.path().points();
+    let first = points.first().copied().unwrap_or_default();
+    let transform = object.transform();
+    let quantize = |value: f32| (value * SHAPE_PRECISION).round() as i64;
+    let mut key = format!("{},{},{},{}", quantize(transform.a), quantize(transform.b), quantize(transform.c), quantize(transform.d));
+    for point in points {
+        key.push_str(&format!(";{},{}", quantize(point.x - first.x), quantize(point.y - first.y)));
+    }
+    key
+}
+
+/// Pairs the unmatched children of both formulas whose keys are equal, in reading order.
+fn match_by<K: Eq + std::hash::Hash>(
+    keys1: &[Option<K>],
+    keys2: &[Option<K>],
+    matched1: &mut [bool],
+    matched2: &mut [bool],
+    pairs: &mut Vec<(usize, usize)>,
+) {
+    let mut candidates = HashMap::<&K, Vec<usize>>::new();
+    for (index, key) in keys2.iter().enumerate().rev() {
+        if let (Some(key), false) = (key, matched2[index]) {
+            candidates.entry(key).or_default().push(index);
+        }
+    }
+    for (index1, key) in keys1.iter().enumerate() {
+        if matched1[index1] {
+            continue;
+        }
+        if let Some(index2) = key.as_ref().and_then(|key| candidates.get_mut(key)).and_then(|indices| indices.pop()) {
+            matched1[index1] = true;
+            matched2[index2] = true;
+            pairs.push((index1, index2));
+        }
+    }
+}
+
+/// Subdivides the curves of a path until it has a given number of cubic bezier curves, splitting the curves as evenly as possible.
+fn subdivide_path(path: &Path2D, curves: usize) -> Path2D {
+    let current = path.len() / 4;
+    if current == 0 {
+        return Path2D::fill(Point2D::default(), curves * 4);
+    }
+    let mut points = Vec::with_capacity(curves * 4);
+    for i in 0..current {
+        let pieces = (i + 1) * curves / current - i * curves / current;
+        let curve = path.slice(i * 4, i * 4 + 4);
+        for j in 0..pieces {
+            let piece = curve.partial_bezier_path(j as f32 / pieces as f32, (j + 1) as f32 / pieces as f32);
+            points.extend(piece.points());
+        }
+    }
+    Path2D::new(points)
+}
+
+/// Interpolates two children of the formulas. Copies of the same glyph keep their path and interpolate their transforms, while different shapes are interpolated point by point in scene coordinates.
+fn lerp_child(child1: &VectorObject, child2: &VectorObject, t: f32) -> VectorObject {
+    let style = |style1: Style, style2: Style| {
+        Style::lerp(&style1, &style2, t, None, None, None, None, None, None, None)
+            .unwrap_or(if t < 0.5 { style1 } else { style2 })
+    };
+    let builder = VectorObjectBuilder::new(child1)
+        .set_fill(style(child1.fill(), child2.fill()), Some(false))
+        .set_stroke(style(child1.stroke(), child2.stroke()), Some(false))
+        .set_stroke_width(lerp(child1.stroke_width(), child2.stroke_width(), t), Some(false));
+    if child1.path().points() == child2.path().points() {
+        return builder
+            .set_transform(TransformationMatrix::lerp(&child1.transform(), &child2.transform(), t), Some(false))
+            .build();
+    }
+    let path1 = child1.actual_path();
+    let path2 = child2.actual_path();
+    let curves = (path1.len() / 4).max(path2.len() / 4).max(1);
+    let points = subdivide_path(&path1, curves).points().iter()
+        .zip(subdivide_path(&path2, curves).points().iter())
+        .map(|(p1, p2)| Point2D::lerp(p1, p2, t))
+        .collect();
+    builder
+        .set_path(Path2D::new(points))
+        .set_transform(TransformationMatrix::identity(), Some(false))
+        .build()
+}
+
+/// A FormulaMorph transforms a formula rendered by Typst into another one, like TransformMatchingTex. Children that appear in both formulas move to their new positions, and the rest fade out or in.
+/// Children are matched by an optional explicit key map first, then by their source text within the same enclosing expression, then by their source text alone and finally by their shape.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct FormulaMorph {
+    /// The rendered formula at the start of the morph.
+    start: Rc<VectorObject>,
+    /// The rendered formula at the end of the morph.
+    end: Rc<VectorObject>,
+    /// The indices of the children of the start formula that become each child of the end formula.
+    pairs: Rc<Vec<(usize, usize)>>,
+    /// The indices of the children of the start formula that fade out.
+    fading_out: Rc<Vec<usize>>,
+    /// The indices of the children of the end formula that fade in.
+    fading_in: Rc<Vec<usize>>,
+}
+
+#[wasm_bindgen]
+impl FormulaMorph {
+    /// Creates a new FormulaMorph by rendering two Typst objects.
+    #[wasm_bindgen(constructor, return_description = "A new formula morph.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The Typst object to morph from.")]
+        from: &Typst,
+        #[wasm_bindgen(param_description = "The Typst object to morph to.")]
+        to: &Typst,
+        #[wasm_bindgen(param_description = "The FontFaces to use when rendering the Typst objects, default is FontFace.defaults().")]
+        font_faces: Option<Vec<FontFace>>,
+        #[wasm_bindgen(param_description = "Labels such as `<name>` or substrings of the start source to match explicitly, default is none.", unchecked_param_type = "string[]")]
+        from_keys: Option<Vec<String>>,
+        #[wasm_bindgen(param_description = "Labels such as `<name>` or substrings of the end source that the keys of the start source become, in the same order.", unchecked_param_type = "string[]")]
+        to_keys: Option<Vec<String>>,
+    ) -> Result<FormulaMorph, JsError> {
+        let start = from.vector_object_builder(font_faces.clone())?.build();
+        let end = to.vector_object_builder(font_faces)?.build();
+        FormulaMorph::between(from, &start, to, &end, from_keys, to_keys)
+    }
+    /// Creates a new FormulaMorph between two vector objects rendered from Typst objects, which may have been moved or restyled since.
+    #[wasm_bindgen(return_description = "A new formula morph.")]
+    pub fn between(
+        #[wasm_bindgen(param_description = "The Typst object to morph from.")]
+        from: &Typst,
+        #[wasm_bindgen(param_description = "The vector object rendered from the Typst object to morph from.")]
+        from_object: &VectorObject,
+        #[wasm_bindgen(param_description = "The Typst object to morph to.")]
+        to: &Typst,
+        #[wasm_bindgen(param_description = "The vector object rendered from the Typst object to morph to.")]
+        to_object: &VectorObject,
+        #[wasm_bindgen(param_description = "Labels such as `<name>` or substrings of the start source to match explicitly, default is none.", unchecked_param_type = "string[]")]
+        from_keys: Option<Vec<String>>,
+        #[wasm_bindgen(param_description = "Labels such as `<name>` or substrings of the end source that the keys of the start source become, in the same order.", unchecked_param_type = "string[]")]
+        to_keys: Option<Vec<String>>,
+    ) -> Result<FormulaMorph, JsError> {
+        let from_keys = from_keys.unwrap_or_default();
+        let to_keys = to_keys.unwrap_or_default();
+        if from_keys.len() != to_keys.len() {
+            return Err(JsError::new("The key maps of both formulas must have the same length."));
+        }
+        // The children of the pages are numbered across them by indices_of if they aren't merged, so they're morphed as the children of a single object.
+        let start = from.merged_pages(from_object);
+        let end = to.merged_pages(to_object);
+        let children1 = start.children();
+        let children2 = end.children();
+        let mut matched1 = vec![false; children1.len()];
+        let mut matched2 = vec![false; children2.len()];
+        let mut pairs = vec![];
+        for (from_key, to_key) in from_keys.iter().zip(to_keys.iter()) {
+            let indices1 = from.indices_of(from_object, from_key).into_iter().filter(|&index| !matched1[index]).collect::<Vec<usize>>();
+            let indices2 = to.indices_of(to_object, to_key).into_iter().filter(|&index| !matched2[index]).collect::<Vec<usize>>();
+            if indices1.is_empty() || indices2.is_empty() {
+                continue;
+            }
+            // Groups of different sizes are paired proportionally, so that no child of either group is left out.
+            let count = indices1.len().max(indices2.len());
+            for k in 0..count {
+                pairs.push((indices1[k * indices1.len() / count], indices2[k * indices2.len() / count]));
+            }
+            indices1.iter().for_each(|&index| matched1[index] = true);
+            indices2.iter().for_each(|&index| matched2[index] = true);
+        }
+        let source1 = Source::detached(from.source());
+        let source2 = Source::detached(to.source());
+        let keys1 = children1.iter().map(|child| ChildKey::new(child, &source1)).collect::<Vec<ChildKey>>();
+        let keys2 = children2.iter().map(|child| ChildKey::new(child, &source2)).collect::<Vec<ChildKey>>();
+        let in_context = |keys: &[ChildKey]| keys.iter().map(|key| key.text.clone().zip(key.context.clone())).collect::<Vec<Option<(String, String)>>>();
+        match_by(&in_context(&keys1), &in_context(&keys2), &mut matched1, &mut matched2, &mut pairs);
+        let texts = |keys: &[ChildKey]| keys.iter().map(|key| key.text.clone()).collect::<Vec<Option<String>>>();
+        match_by(&texts(&keys1), &texts(&keys2), &mut matched1, &mut matched2, &mut pairs);
+        let shapes = |keys: &[ChildKey]| keys.iter().map(|key| Some(key.shape.clone())).collect::<Vec<Option<String>>>();
+        match_by(&shapes(&keys1), &shapes(&keys2), &mut matched1, &mut matched2, &mut pairs);
+        let fading_out = (0..children1.len()).filter(|&index| !matched1[index]).collect();
+        let fading_in = (0..children2.len()).filter(|&index| !matched2[index]).collect();
+        Ok(FormulaMorph {
+            start: Rc::new(start),
+            end: Rc::new(end),
+            pairs: Rc::new(pairs),
+            fading_out: Rc::new(fading_out),
+            fading_in: Rc::new(fading_in),
+        })
+    }
+    /// Clones the formula morph.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn copy(&self) -> FormulaMorph {
+        self.clone()
+    }
+    /// Gets the rendered formula at the start of the morph.
+    #[wasm_bindgen(getter, return_description = "The start formula.")]
+    pub fn start(&self) -> VectorObject {
+        (*self.start).clone()
+    }
+    /// Gets the rendered formula at the end of the morph.
+    #[wasm_bindgen(getter, return_description = "The end formula.")]
+    pub fn end(&self) -> VectorObject {
+        (*self.end).clone()
+    }
+    /// Gets the number of children of the start formula that move to the end formula, counting children that split in several ones once per destination.
+    #[wasm_bindgen(getter, return_description = "The number of matched pairs of children.")]
+    pub fn num_matches(&self) -> usize {
+        self.pairs.len()
+    }
+    /// Gets the formula at a given progress of the morph, with the moving children first, then the fading out ones and then the fading in ones.
+    #[wasm_bindgen(return_description = "The formula at the given progress.")]
+    pub fn at(
+        &self,
+        #[wasm_bindgen(param_description = "The progress value, from 0 to 1.")]
+        t: f32
+    ) -> VectorObject {
+        if t <= 0.0 {
+            return self.start();
+        }
+        if t >= 1.0 {
+            return self.end();
+        }
+        let children1 = self.start.children();
+        let children2 = self.end.children();
+        let moving = self.pairs.iter().map(|&(index1, index2)| VectorObjectBuilder::new(&lerp_child(&children1[index1], &children2[index2], t)));
+        let fading_out = self.fading_out.iter().map(|&index| VectorObjectBuilder::new(&children1[index]).fade_fill(t, None).fade_stroke(t, None));
+        let fading_in = self.fading_in.iter().map(|&index| VectorObjectBuilder::new(&children2[index]).fade_fill(1.0 - t, None).fade_stroke(1.0 - t, None));
+        VectorObjectBuilder::new(&self.end)
+            .set_children(moving.chain(fading_out).chain(fading_in).collect())
+            .build()
+    }
+}
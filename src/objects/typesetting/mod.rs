@@ -1 +1,9 @@
-pub mod typst;
\ No newline at end of file
+pub mod aligned_formulas;
+pub mod code;
+pub mod decimal_number;
+pub mod formula_morph;
+pub mod tex;
+pub mod text;
+pub mod typst;
+pub mod typst_engine;
+pub mod typst_settings;
\ No newline at end of file
@@ -0,0 +1,979 @@
+use std::rc::Rc;
+
+use typst::syntax::Source;
+use wasm_bindgen::prelude::*;
+
+use crate::{objects::{typesetting::{typst::{format_diagnostics, span_names, span_range, DiagnosticSeverity, SourceSpan, Typst, TypstDiagnostic}, typst_engine::TypstEngine, typst_settings::TypstSettings}, vector_object::{VectorObject, VectorObjectBuilder}}, utils::font_face::FontFace};
+
+/// Gets the Typst symbol of a LaTeX command that stands for a single symbol, such as a Greek letter, an operator, a relation or an arrow.
+fn symbol(command: &str) -> Option<&'static str> {
+    Some(match command {
+        "alpha" => "alpha", "beta" => "beta", "gamma" => "gamma", "delta" => "delta", "epsilon" => "epsilon.alt",
+        "varepsilon" => "epsilon", "zeta" => "zeta", "eta" => "eta", "theta" => "theta", "vartheta" => "theta.alt",
+        "iota" => "iota", "kappa" => "kappa", "varkappa" => "kappa.alt", "lambda" => "lambda", "mu" => "mu",
+        "nu" => "nu", "xi" => "xi", "omicron" => "omicron", "pi" => "pi", "varpi" => "pi.alt", "rho" => "rho",
+        "varrho" => "rho.alt", "sigma" => "sigma", "varsigma" => "sigma.alt", "tau" => "tau", "upsilon" => "upsilon",
+        "phi" => "phi.alt", "varphi" => "phi", "chi" => "chi", "psi" => "psi", "omega" => "omega",
+        "Gamma" => "Gamma", "Delta" => "Delta", "Theta" => "Theta", "Lambda" => "Lambda", "Xi" => "Xi", "Pi" => "Pi",
+        "Sigma" => "Sigma", "Upsilon" => "Upsilon", "Phi" => "Phi", "Psi" => "Psi", "Omega" => "Omega",
+        "sum" => "sum", "prod" => "product", "coprod" => "product.co", "int" => "integral", "iint" => "integral.double",
+        "iiint" => "integral.triple", "oint" => "integral.cont", "bigcup" => "union.big", "bigcap" => "sect.big",
+        "bigoplus" => "plus.circle.big", "bigotimes" => "times.circle.big", "bigvee" => "or.big", "bigwedge" => "and.big",
+        "pm" => "plus.minus", "mp" => "minus.plus", "times" => "times", "div" => "div", "cdot" => "dot.op",
+        "ast" => "ast", "star" => "star", "circ" => "compose", "bullet" => "bullet", "cup" => "union", "cap" => "sect",
+        "setminus" => "without", "oplus" => "plus.circle", "otimes" => "times.circle", "wedge" | "land" => "and",
+        "vee" | "lor" => "or", "leq" | "le" => "lt.eq", "geq" | "ge" => "gt.eq", "neq" | "ne" => "eq.not",
+        "approx" => "approx", "equiv" => "equiv", "sim" => "tilde.op", "simeq" => "tilde.eq", "cong" => "tilde.equiv",
+        "propto" => "prop", "in" => "in", "notin" => "in.not", "ni" => "in.rev", "subset" => "subset",
+        "subseteq" => "subset.eq", "supset" => "supset", "supseteq" => "supset.eq", "ll" => "lt.double",
+        "gg" => "gt.double", "perp" => "perp", "parallel" => "parallel", "mid" => "divides", "to" | "rightarrow" => "arrow.r",
+        "leftarrow" | "gets" => "arrow.l", "leftrightarrow" => "arrow.l.r", "Rightarrow" => "arrow.r.double",
+        "Leftarrow" => "arrow.l.double", "Leftrightarrow" | "iff" => "arrow.l.r.double", "implies" => "arrow.r.double.long",
+        "impliedby" => "arrow.l.double.long", "mapsto" => "arrow.r.bar", "longrightarrow" => "arrow.r.long",
+        "longleftarrow" => "arrow.l.long", "uparrow" => "arrow.t", "downarrow" => "arrow.b", "infty" => "infinity",
+        "partial" => "diff", "nabla" => "nabla", "forall" => "forall", "exists" => "exists", "nexists" => "exists.not",
+        "emptyset" | "varnothing" => "nothing", "neg" | "lnot" => "not", "ldots" | "dots" => "dots.h",
+        "cdots" => "dots.h.c", "vdots" => "dots.v", "ddots" => "dots.down", "angle" => "angle", "hbar" => "planck.reduce",
+        "ell" => "ell", "Re" => "Re", "Im" => "Im", "aleph" => "aleph", "prime" => "prime", "degree" => "degree",
+        "langle" => "angle.l", "rangle" => "angle.r", "lfloor" => "floor.l", "rfloor" => "floor.r", "lceil" => "ceil.l",
+        "rceil" => "ceil.r", "vert" | "lvert" | "rvert" => "bar.v", "Vert" | "lVert" | "rVert" | "|" => "bar.v.double",
+        "colon" => "colon", "triangle" => "triangle.t", "square" => "square", "checkmark" => "checkmark",
+        "{" => "\\{", "}" => "\\}", "%" => "\\%", "$" => "\\$", "#" => "\\#", "&" => "\\&", "_" => "\\_",
+        "," => "thin", ":" | ">" => "med", ";" => "thick", "!" => "#h(-0.1667em)", " " => "space", "quad" => "quad",
+        "qquad" => "wide",
+        _ => return None,
+    })
+}
+
+/// Gets the Typst operator of a LaTeX command that stands for a named operator, such as `\sin` or `\lim`.
+fn operator(command: &str) -> Option<&'static str> {
+    Some(match command {
+        "sin" => "sin", "cos" => "cos", "tan" => "tan", "cot" => "cot", "sec" => "sec", "csc" => "csc",
+        "arcsin" => "arcsin", "arccos" => "arccos", "arctan" => "arctan", "sinh" => "sinh", "cosh" => "cosh",
+        "tanh" => "tanh", "coth" => "coth", "exp" => "exp", "log" => "log", "ln" => "ln", "lg" => "lg", "det" => "det",
+        "dim" => "dim", "ker" => "ker", "deg" => "deg", "gcd" => "gcd", "lcm" => "lcm", "lim" => "lim",
+        "liminf" => "liminf", "limsup" => "limsup", "max" => "max", "min" => "min", "sup" => "sup", "inf" => "inf",
+        "arg" => "arg", "hom" => "hom", "Pr" => "Pr", "bmod" | "mod" => "mod",
+        _ => return None,
+    })
+}
+
+/// Gets the nested Typst functions a LaTeX command with one argument applies to it, such as a font or an accent.
+fn unary(command: &str) -> Option<&'static [&'static str]> {
+    Some(match command {
+        "mathbb" => &["bb"], "mathcal" | "mathscr" => &["cal"], "mathfrak" => &["frak"], "mathbf" => &["upright", "bold"],
+        "boldsymbol" | "bm" => &["bold"], "mathit" => &["italic"], "mathrm" => &["upright"], "mathsf" => &["sans"],
+        "mathtt" => &["mono"], "hat" | "widehat" => &["hat"], "tilde" | "widetilde" => &["tilde"], "bar" => &["macron"],
+        "vec" | "overrightarrow" => &["arrow"], "overleftarrow" => &["arrow.l"], "dot" => &["dot"], "ddot" => &["dot.double"],
+        "acute" => &["acute"], "grave" => &["grave"], "breve" => &["breve"], "check" => &["caron"],
+        "overline" => &["overline"], "underline" => &["underline"], "sqrt" => &["sqrt"], "cancel" => &["cancel"],
+        _ => return None,
+    })
+}
+
+/// Gets the Typst function of a LaTeX command with two arguments, such as a fraction.
+fn binary(command: &str) -> Option<&'static str> {
+    Some(match command {
+        "frac" | "cfrac" => "frac", "dfrac" => "display(frac", "tfrac" => "inline(frac", "binom" => "binom",
+        "dbinom" => "display(binom", "tbinom" => "inline(binom",
+        _ => return None,
+    })
+}
+
+/// Gets the Typst function of a LaTeX text command, which typesets its argument as text.
+fn text(command: &str) -> Option<&'static str> {
+    Some(match command {
+        "text" | "textrm" | "textnormal" | "mbox" => "", "textbf" => "bold", "textit" => "italic",
+        "textsf" => "sans", "texttt" => "mono",
+        _ => return None,
+    })
+}
+
+/// Gets the Typst string literal of the raw text of a LaTeX text argument, where the escaped special characters `\{ \} \$ \% \& \_ \#` stand for themselves.
+fn text_literal(text: &str) -> String {
+    let mut literal = String::from("\"");
+    let mut characters = text.chars().peekable();
+    while let Some(character) = characters.next() {
+        match character {
+            '\\' if matches!(characters.peek(), Some('{' | '}' | '$' | '%' | '&' | '_' | '#')) => {}
+            '\\' | '"' => {
+                literal.push('\\');
+                literal.push(character);
+            }
+            _ => literal.push(character),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// Gets the Typst delimiter of a LaTeX delimiter after `\left`, `\right`, `\middle` or `\big`, where `.` is no delimiter.
+fn delimiter(token: &Token) -> Option<&'static str> {
+    Some(match token {
+        Token::Char('.') => "",
+        Token::Char('(') => "\\(", Token::Char(')') => "\\)", Token::Char('[') => "\\[", Token::Char(']') => "\\]",
+        Token::Char('|') => "bar.v", Token::Char('/') => "\\/", Token::Char('<') => "angle.l", Token::Char('>') => "angle.r",
+        Token::Command(command) => match command.as_str() {
+            "{" | "lbrace" => "\\{", "}" | "rbrace" => "\\}", "langle" => "angle.l", "rangle" => "angle.r",
+            "lfloor" => "floor.l", "rfloor" => "floor.r", "lceil" => "ceil.l", "rceil" => "ceil.r",
+            "vert" | "lvert" | "rvert" => "bar.v", "|" | "Vert" | "lVert" | "rVert" => "bar.v.double",
+            "uparrow" => "arrow.t", "downarrow" => "arrow.b", "backslash" => "\\\\",
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
+
+/// Gets the relative size of the delimiter after a LaTeX sizing command such as `\big` or `\Bigl`.
+fn delimiter_size(command: &str) -> Option<&'static str> {
+    let size = command.strip_suffix(['l', 'r', 'm']).unwrap_or(command);
+    Some(match size {
+        "big" => "120%", "Big" => "180%", "bigg" => "240%", "Bigg" => "300%",
+        _ => return None,
+    })
+}
+
+/// Gets the Typst delimiter of the matrix of a LaTeX environment, or none if the environment is not a matrix.
+fn matrix_delimiter(environment: &str) -> Option<&'static str> {
+    Some(match environment {
+        "matrix" | "smallmatrix" | "array" => "#none", "pmatrix" => "\"(\"", "bmatrix" => "\"[\"",
+        "Bmatrix" => "\"{\"", "vmatrix" => "\"|\"", "Vmatrix" => "\"‖\"",
+        _ => return None,
+    })
+}
+
+/// A token of a LaTeX source.
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    /// A command such as `\frac` or `\{`, without its backslash.
+    Command(String),
+    /// The start of a group.
+    Open,
+    /// The end of a group.
+    Close,
+    /// Any other character.
+    Char(char),
+}
+
+/// How alignment tabs and line breaks are translated in the current environment.
+#[derive(Clone, Copy, PartialEq)]
+enum Layout {
+    /// Lines of an equation, where tabs align columns.
+    Lines,
+    /// Cells of a matrix, where tabs separate cells and line breaks separate rows.
+    Matrix,
+    /// Branches of cases, where tabs align columns and line breaks separate branches.
+    Cases,
+}
+
+/// A range of the Typst source translated from a range of the LaTeX source.
+type Segment = ((usize, usize), (usize, usize));
+
+/// A Translator translates LaTeX math into Typst math, keeping track of which range of the LaTeX source each range of the Typst source comes from.
+struct Translator<'a> {
+    /// The LaTeX source.
+    tex: &'a str,
+    /// The LaTeX source, used to locate errors.
+    source: Source,
+    /// The byte position of the next token in the LaTeX source.
+    position: usize,
+    /// The Typst source translated so far.
+    output: String,
+    /// The ranges of the Typst source translated from ranges of the LaTeX source.
+    segments: Vec<Segment>,
+    /// The errors found so far.
+    errors: Vec<TypstDiagnostic>,
+    /// How alignment tabs and line breaks are translated.
+    layout: Layout,
+    /// The byte position in the output of the current matrix cell or case.
+    cell_start: usize,
+    /// The number of `\left` delimiters waiting for their `\right`.
+    lefts: usize,
+    /// The number of environments waiting for their `\end`.
+    environments: usize,
+}
+
+impl<'a> Translator<'a> {
+    /// Creates a Translator for a LaTeX source.
+    fn new(tex: &'a str) -> Translator<'a> {
+        Translator {
+            tex,
+            source: Source::detached(tex),
+            position: 0,
+            output: String::new(),
+            segments: vec![],
+            errors: vec![],
+            layout: Layout::Lines,
+            cell_start: 0,
+            lefts: 0,
+            environments: 0,
+        }
+    }
+    /// Reports an error located at a byte range of the LaTeX source.
+    fn error(&mut self, message: String, hints: Vec<String>, start: usize, end: usize) {
+        self.errors.push(TypstDiagnostic::located(DiagnosticSeverity::Error, message, hints, &self.source, start, end));
+    }
+    /// Skips whitespace and comments.
+    fn skip_space(&mut self) {
+        loop {
+            let rest = &self.tex[self.position..];
+            let trimmed = rest.trim_start();
+            self.position += rest.len() - trimmed.len();
+            if !trimmed.starts_with('%') {
+                return;
+            }
+            self.position += trimmed.find('\n').unwrap_or(trimmed.len());
+        }
+    }
+    /// Reads the next token without consuming it, along with its byte range.
+    fn peek(&mut self) -> Option<(Token, usize, usize)> {
+        self.skip_space();
+        let start = self.position;
+        let mut chars = self.tex[start..].chars();
+        let (token, length) = match chars.next()? {
+            '\\' => {
+                let name: String = chars.clone().take_while(|character| character.is_ascii_alphabetic()).collect();
+                match chars.next() {
+                    _ if !name.is_empty() => (Token::Command(name.clone()), 1 + name.len()),
+                    // Control symbols such as `\{` or `\,` are a single character, and control spaces are spaces.
+                    Some(character) if character.is_whitespace() => (Token::Command(" ".to_string()), 1 + character.len_utf8()),
+                    Some(character) => (Token::Command(character.to_string()), 1 + character.len_utf8()),
+                    None => (Token::Command(" ".to_string()), 1),
+                }
+            }
+            '{' => (Token::Open, 1),
+            '}' => (Token::Close, 1),
+            character => (Token::Char(character), character.len_utf8()),
+        };
+        Some((token, start, start + length))
+    }
+    /// Reads and consumes the next token, along with its byte range.
+    fn next(&mut self) -> Option<(Token, usize, usize)> {
+        let token = self.peek()?;
+        self.position = token.2;
+        Some(token)
+    }
+    /// Whether the next token is a given command.
+    fn next_is(&mut self, command: &str) -> bool {
+        matches!(self.peek(), Some((Token::Command(name), _, _)) if name == command)
+    }
+    /// Appends Typst code to the output without a range in the LaTeX source.
+    fn write(&mut self, code: &str) {
+        self.output.push_str(code);
+    }
+    /// Removes the trailing whitespace of the output, so that Typst code can be appended right after the last atom.
+    fn trim(&mut self) {
+        self.output.truncate(self.output.trim_end().len());
+    }
+    /// Appends Typst code to the output, translated from a byte range of the LaTeX source.
+    fn emit(&mut self, code: &str, start: usize, end: usize) {
+        let typst_start = self.output.len();
+        self.output.push_str(code);
+        self.output.push(' ');
+        self.segments.push(((typst_start, typst_start + code.len()), (start, end)));
+    }
+    /// Inserts Typst code into the output at a byte position, moving the ranges after it.
+    fn insert(&mut self, position: usize, code: &str) {
+        self.output.insert_str(position, code);
+        for ((start, end), _) in self.segments.iter_mut() {
+            if *start >= position {
+                *start += code.len();
+            }
+            if *end > position {
+                *end += code.len();
+            }
+        }
+    }
+    /// Records that the Typst output from a byte position on was translated from the LaTeX source from a byte position on.
+    fn mark(&mut self, typst_start: usize, start: usize) {
+        let typst_end = self.output.trim_end().len();
+        let end = start + self.tex[start..self.position].trim_end().len();
+        if typst_end > typst_start {
+            self.segments.push(((typst_start, typst_end), (start, end)));
+        }
+    }
+    /// Ends the current matrix cell or case, which can't be empty in Typst.
+    fn end_cell(&mut self) {
+        if self.output[self.cell_start..].trim().is_empty() {
+            self.write("\"\" ");
+        }
+        self.trim();
+    }
+    /// Translates a sequence of atoms until the end of the source or a closing brace, or the `\right` or `\end` of an enclosing delimiter or environment, none of which are consumed. A closing character such as `]` also ends the sequence when given.
+    fn sequence(&mut self, closing: Option<char>) {
+        while let Some((token, start, end)) = self.peek() {
+            match &token {
+                Token::Close => return,
+                Token::Char(character) if Some(*character) == closing => return,
+                Token::Command(command) if command == "right" && self.lefts > 0 => return,
+                Token::Command(command) if command == "end" && self.environments > 0 => return,
+                Token::Char('&') => {
+                    self.next();
+                    match self.layout {
+                        Layout::Matrix => {
+                            self.end_cell();
+                            self.write(", ");
+                            self.cell_start = self.output.len();
+                        }
+                        Layout::Lines | Layout::Cases => self.emit("&", start, end),
+                    }
+                }
+                Token::Command(command) if command == "\\" || command == "cr" || command == "newline" => {
+                    self.next();
+                    self.skip_line_spacing();
+                    if self.next_is("end") && self.layout != Layout::Lines {
+                        continue;
+                    }
+                    match self.layout {
+                        Layout::Matrix | Layout::Cases => {
+                            self.end_cell();
+                            self.write(if self.layout == Layout::Matrix { "; " } else { ", " });
+                            self.cell_start = self.output.len();
+                        }
+                        Layout::Lines => self.emit("\\", start, end),
+                    }
+                }
+                Token::Command(command) if matches!(command.as_str(), "displaystyle" | "textstyle" | "scriptstyle") => {
+                    // Style switches apply to the rest of the group.
+                    self.next();
+                    let typst_start = self.output.len();
+                    self.write(match command.as_str() {
+                        "displaystyle" => "display(",
+                        "textstyle" => "inline(",
+                        _ => "script(",
+                    });
+                    let content_start = self.output.len();
+                    self.sequence(closing);
+                    if self.output[content_start..].trim().is_empty() {
+                        self.write("\"\" ");
+                    }
+                    self.trim();
+                    self.write(") ");
+                    self.mark(typst_start, start);
+                }
+                _ => self.atom(),
+            }
+        }
+    }
+    /// Skips the spacing after a line break, such as `[2pt]` in `\\[2pt]`.
+    fn skip_line_spacing(&mut self) {
+        if self.tex[self.position..].starts_with('[') {
+            if let Some(length) = self.tex[self.position..].find(']') {
+                self.position += length + 1;
+            }
+        }
+    }
+    /// Translates an atom along with its scripts, primes and limits.
+    fn atom(&mut self) {
+        let Some((token, start, _)) = self.peek() else {
+            return;
+        };
+        let typst_start = self.output.len();
+        let grouped = token == Token::Open;
+        self.base(false);
+        let mut base_end = None;
+        let mut limits = None;
+        let mut scripts = vec![];
+        while let Some((token, token_start, token_end)) = self.peek() {
+            match token {
+                Token::Command(command) if command == "limits" || command == "nolimits" => {
+                    self.next();
+                    limits = Some(if command == "limits" { "limits(" } else { "scripts(" });
+                }
+                Token::Char('\'') => {
+                    self.next();
+                    self.trim();
+                    self.emit("'", token_start, token_end);
+                }
+                Token::Char(script @ ('^' | '_')) => {
+                    self.next();
+                    if scripts.contains(&script) {
+                        let name = if script == '^' { "superscript" } else { "subscript" };
+                        self.error(format!("Double {}.", name), vec!["use braces to group the scripts, such as `{x^a}^b`".to_string()], token_start, token_end);
+                    }
+                    scripts.push(script);
+                    self.trim();
+                    base_end.get_or_insert(self.output.len());
+                    if grouped {
+                        self.write(if script == '^' { ", t: " } else { ", b: " });
+                        self.argument(token_start, token_end);
+                    } else {
+                        self.write(&format!("{}(", script));
+                        self.group_or_token(token_start, token_end);
+                        self.trim();
+                        self.write(") ");
+                    }
+                }
+                _ => break,
+            }
+        }
+        let decorated = limits.is_some() || !scripts.is_empty();
+        if let Some(function) = limits {
+            let base_end = base_end.unwrap_or(self.output.trim_end().len());
+            self.insert(base_end, ")");
+            self.insert(typst_start, function);
+        }
+        if grouped && !scripts.is_empty() {
+            self.trim();
+            self.write(") ");
+            self.insert(typst_start, "attach(");
+        }
+        if decorated {
+            self.mark(typst_start, start);
+        }
+    }
+    /// Translates the base of an atom, which is a single character rather than a whole number when it is the argument of a command or a script, like `\frac12`.
+    fn base(&mut self, single: bool) {
+        let Some((token, start, end)) = self.next() else {
+            return;
+        };
+        match token {
+            Token::Open => {
+                let typst_start = self.output.len();
+                self.sequence(None);
+                self.close(start, end);
+                if self.output[typst_start..].trim().is_empty() {
+                    self.write("zws ");
+                }
+            }
+            Token::Close => self.error("Unmatched closing brace.".to_string(), vec![], start, end),
+            Token::Char('^' | '_') => {
+                // Scripts without a base are attached to an empty one.
+                self.position = start;
+                self.write("zws ");
+            }
+            Token::Char(character) if character.is_ascii_digit() && !single => {
+                let rest = &self.tex[start..];
+                let digits = |text: &str| text.find(|character: char| !character.is_ascii_digit()).unwrap_or(text.len());
+                let mut length = digits(rest);
+                if rest[length..].starts_with('.') && rest[length + 1..].starts_with(|character: char| character.is_ascii_digit()) {
+                    length += 1 + digits(&rest[length + 1..]);
+                }
+                self.position = start + length;
+                self.emit(&rest[..length], start, start + length);
+            }
+            Token::Char('~') => self.emit("space.nobreak", start, end),
+            Token::Char(character) if "()[],;/|\"$#@<>`".contains(character) => self.emit(&format!("\\{}", character), start, end),
+            Token::Char(character) => self.emit(&character.to_string(), start, end),
+            Token::Command(command) => self.command(&command, start, end),
+        }
+    }
+    /// Consumes the closing brace of a group opened at a byte range, or reports the group as unclosed.
+    fn close(&mut self, start: usize, end: usize) {
+        match self.peek() {
+            Some((Token::Close, _, _)) => {
+                self.next();
+            }
+            _ => self.error("Unclosed brace.".to_string(), vec!["add a `}` to close the group".to_string()], start, end),
+        }
+    }
+    /// Translates a group or a single token, such as the argument of a command or a script.
+    fn group_or_token(&mut self, start: usize, end: usize) {
+        match self.peek() {
+            Some((Token::Open, open_start, open_end)) => {
+                self.next();
+                self.sequence(None);
+                self.close(open_start, open_end);
+            }
+            Some((Token::Close, _, _)) | None => self.error("Missing argument.".to_string(), vec![], start, end),
+            Some(_) => self.base(true),
+        }
+    }
+    /// Translates the argument of a command as the argument of a Typst function, which can't be empty, and removes the whitespace after it.
+    fn argument(&mut self, start: usize, end: usize) {
+        let typst_start = self.output.len();
+        let layout = self.layout;
+        self.layout = Layout::Lines;
+        self.group_or_token(start, end);
+        self.layout = layout;
+        if self.output[typst_start..].trim().is_empty() {
+            self.write("\"\"");
+        }
+        self.trim();
+    }
+    /// Reads the raw text of a group argument, such as the argument of `\text` or the name of an environment, along with its byte position.
+    fn raw_argument(&mut self, start: usize, end: usize) -> Option<(String, usize)> {
+        let Some((Token::Open, open_start, _)) = self.peek() else {
+            self.error("Missing argument.".to_string(), vec!["wrap the argument in braces".to_string()], start, end);
+            return None;
+        };
+        let mut depth = 0;
+        let mut escaped = false;
+        for (index, character) in self.tex[open_start..].char_indices() {
+            if escaped {
+                // The character after a backslash, such as an escaped brace, doesn't open or close a group.
+                escaped = false;
+                continue;
+            }
+            match character {
+                '\\' => escaped = true,
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.position = open_start + index + 1;
+                        return Some((self.tex[open_start + 1..open_start + index].to_string(), open_start + 1));
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.error("Unclosed brace.".to_string(), vec!["add a `}` to close the group".to_string()], open_start, open_start + 1);
+        self.position = self.tex.len();
+        None
+    }
+    /// Translates a command.
+    fn command(&mut self, command: &str, start: usize, end: usize) {
+        let typst_start = self.output.len();
+        if let Some(symbol) = symbol(command) {
+            self.emit(symbol, start, end);
+            return;
+        } else if let Some(operator) = operator(command) {
+            self.emit(operator, start, end);
+            return;
+        } else if command == "sqrt" && self.tex[self.position..].trim_start().starts_with('[') {
+            self.skip_space();
+            self.position += 1;
+            self.write("root(");
+            let index_start = self.output.len();
+            self.sequence(Some(']'));
+            match self.peek() {
+                Some((Token::Char(']'), _, _)) => {
+                    self.next();
+                }
+                _ => self.error("Unclosed root index.".to_string(), vec!["add a `]` to close the index".to_string()], start, end),
+            }
+            if self.output[index_start..].trim().is_empty() {
+                self.write("\"\"");
+            }
+            self.trim();
+            self.write(", ");
+            self.argument(start, end);
+            self.write(") ");
+        } else if let Some(functions) = unary(command) {
+            for function in functions {
+                self.write(&format!("{}(", function));
+            }
+            self.argument(start, end);
+            self.write(&format!("{} ", ")".repeat(functions.len())));
+        } else if let Some(function) = binary(command) {
+            self.write(&format!("{}(", function));
+            self.argument(start, end);
+            self.write(", ");
+            self.argument(start, end);
+            self.write(&format!("{} ", ")".repeat(function.matches('(').count() + 1)));
+        } else if let Some(function) = text(command) {
+            let Some((text, text_start)) = self.raw_argument(start, end) else {
+                return;
+            };
+            let literal = text_literal(&text);
+            let code = if function.is_empty() { literal } else { format!("{}({})", function, literal) };
+            self.emit(&code, text_start, text_start + text.len());
+        } else if command == "operatorname" {
+            let limits = self.tex[self.position..].starts_with('*');
+            if limits {
+                self.position += 1;
+            }
+            let Some((name, _)) = self.raw_argument(start, end) else {
+                return;
+            };
+            let name = text_literal(name.trim());
+            let code = if limits { format!("op({}, limits: #true)", name) } else { format!("op({})", name) };
+            self.emit(&code, start, self.position);
+            return;
+        } else if command == "pmod" {
+            self.write("(mod ");
+            self.argument(start, end);
+            self.write(") ");
+        } else if command == "overbrace" || command == "underbrace" {
+            let script = if command == "overbrace" { '^' } else { '_' };
+            self.write(&format!("{}(", command));
+            self.argument(start, end);
+            if let Some((Token::Char(character), script_start, script_end)) = self.peek() {
+                if character == script {
+                    self.next();
+                    self.write(", ");
+                    self.argument(script_start, script_end);
+                }
+            }
+            self.write(") ");
+        } else if command == "overset" || command == "underset" || command == "stackrel" {
+            // Named arguments can come first in Typst, so the script is translated before the base like in LaTeX.
+            self.write(if command == "underset" { "attach(b: " } else { "attach(t: " });
+            self.argument(start, end);
+            self.write(", limits(");
+            self.argument(start, end);
+            self.write(")) ");
+        } else if command == "substack" {
+            self.argument(start, end);
+            self.write(" ");
+        } else if command == "not" {
+            let negated = match self.next() {
+                Some((Token::Char('='), _, _)) => Some("eq.not"),
+                Some((Token::Char('<'), _, _)) => Some("lt.not"),
+                Some((Token::Char('>'), _, _)) => Some("gt.not"),
+                Some((Token::Command(command), _, _)) => match command.as_str() {
+                    "in" => Some("in.not"), "subset" => Some("subset.not"), "supset" => Some("supset.not"),
+                    "subseteq" => Some("subset.eq.not"), "supseteq" => Some("supset.eq.not"), "equiv" => Some("equiv.not"),
+                    "exists" => Some("exists.not"), "leq" | "le" => Some("lt.eq.not"), "geq" | "ge" => Some("gt.eq.not"),
+                    "sim" => Some("tilde.not"), "mid" => Some("divides.not"), "parallel" => Some("parallel.not"),
+                    _ => None,
+                },
+                _ => None,
+            };
+            match negated {
+                Some(negated) => self.emit(negated, start, self.position),
+                None => self.error(
+                    "Unsupported negation.".to_string(),
+                    vec!["`\\not` is supported before `=`, `<`, `>` and common relations such as `\\in` or `\\subset`".to_string()],
+                    start,
+                    self.position,
+                ),
+            }
+            return;
+        } else if command == "left" {
+            self.left(start, end);
+        } else if command == "middle" && self.lefts > 0 {
+            let delimiter = self.delimiter_after(command, start, end);
+            self.emit(&format!("mid({})", if delimiter.is_empty() { "zws" } else { &delimiter }), start, self.position);
+            return;
+        } else if let Some(size) = delimiter_size(command) {
+            match self.delimiter_after(command, start, end) {
+                delimiter if delimiter.is_empty() => {}
+                delimiter => self.emit(&format!("lr({}, size: #{})", delimiter, size), start, self.position),
+            }
+            return;
+        } else if command == "begin" {
+            self.environment(start, end);
+        } else if command == "right" || command == "middle" {
+            self.error(format!("`\\{}` without a matching `\\left`.", command), vec![], start, end);
+            self.delimiter_after(command, start, end);
+            return;
+        } else if command == "end" {
+            self.error("`\\end` without a matching `\\begin`.".to_string(), vec![], start, end);
+            self.raw_argument(start, end);
+            return;
+        } else {
+            self.error(
+                format!("Unsupported LaTeX command `\\{}`.", command),
+                vec!["supported commands include fractions, roots, Greek letters, operators, matrices, `\\left` and `\\right` delimiters and font commands".to_string()],
+                start,
+                end,
+            );
+            return;
+        }
+        self.mark(typst_start, start);
+    }
+    /// Translates `\left`, the sequence after it and its matching `\right`, with any `\middle` delimiters in between.
+    fn left(&mut self, start: usize, end: usize) {
+        let opening = self.delimiter_after("left", start, end);
+        self.write("lr(");
+        if !opening.is_empty() {
+            self.emit(&opening, start, self.position);
+        }
+        let layout = self.layout;
+        self.layout = Layout::Lines;
+        self.lefts += 1;
+        self.sequence(None);
+        self.lefts -= 1;
+        self.layout = layout;
+        match self.peek() {
+            Some((Token::Command(command), right_start, right_end)) if command == "right" => {
+                self.next();
+                let closing = self.delimiter_after("right", right_start, right_end);
+                if !closing.is_empty() {
+                    self.emit(&closing, right_start, self.position);
+                }
+            }
+            _ => self.error("`\\left` without a matching `\\right`.".to_string(), vec!["add a `\\right` delimiter, or `\\right.` for none".to_string()], start, end),
+        }
+        self.trim();
+        self.write(") ");
+    }
+    /// Reads the delimiter after a command such as `\left`, reporting it if it is missing or unsupported. No delimiter is empty.
+    fn delimiter_after(&mut self, command: &str, start: usize, end: usize) -> String {
+        let Some((token, token_start, token_end)) = self.next() else {
+            self.error(format!("Missing delimiter after `\\{}`.", command), vec![], start, end);
+            return String::new();
+        };
+        match delimiter(&token) {
+            Some(delimiter) => delimiter.to_string(),
+            None => {
+                self.error(
+                    format!("Unsupported delimiter after `\\{}`.", command),
+                    vec!["use a delimiter such as `(`, `[`, `\\{`, `|`, `\\langle` or `.` for none".to_string()],
+                    token_start,
+                    token_end,
+                );
+                String::new()
+            }
+        }
+    }
+    /// Translates an environment from its `\begin` to its matching `\end`.
+    fn environment(&mut self, start: usize, end: usize) {
+        let Some((name, _)) = self.raw_argument(start, end) else {
+            return;
+        };
+        let name = name.trim().to_string();
+        if name == "array" || name.starts_with("alignat") {
+            // Column specifications don't change how the cells are laid out.
+            self.raw_argument(start, end);
+        }
+        let (layout, cell_start) = (self.layout, self.cell_start);
+        let closing = if let Some(delimiter) = matrix_delimiter(&name) {
+            self.write(&format!("mat(delim: {}, ", delimiter));
+            self.layout = Layout::Matrix;
+            true
+        } else if name == "cases" {
+            self.write("cases(");
+            self.layout = Layout::Cases;
+            true
+        } else {
+            let lines = ["aligned", "align", "align*", "alignat", "alignat*", "gathered", "gather", "gather*", "split", "equation", "equation*", "multline", "multline*"];
+            if !lines.contains(&name.as_str()) {
+                self.error(
+                    format!("Unsupported LaTeX environment `{}`.", name),
+                    vec!["supported environments are `matrix`, `pmatrix`, `bmatrix`, `Bmatrix`, `vmatrix`, `Vmatrix`, `array`, `cases` and aligned equations".to_string()],
+                    start,
+                    self.position,
+                );
+            }
+            self.layout = Layout::Lines;
+            false
+        };
+        self.cell_start = self.output.len();
+        self.environments += 1;
+        self.sequence(None);
+        self.environments -= 1;
+        match self.peek() {
+            Some((Token::Command(command), end_start, end_end)) if command == "end" => {
+                self.next();
+                if let Some((end_name, _)) = self.raw_argument(end_start, end_end) {
+                    if end_name.trim() != name {
+                        self.error(format!("`\\end{{{}}}` doesn't match `\\begin{{{}}}`.", end_name.trim(), name), vec![], end_start, self.position);
+                    }
+                }
+            }
+            _ => self.error(format!("`\\begin{{{}}}` without a matching `\\end`.", name), vec![format!("add `\\end{{{}}}` to close the environment", name)], start, end),
+        }
+        if closing {
+            self.end_cell();
+            self.write(") ");
+        }
+        self.layout = layout;
+        self.cell_start = cell_start;
+    }
+}
+
+/// The result of translating LaTeX math into Typst math, with the ranges of the Typst source translated from ranges of the LaTeX source.
+type Translation = (String, Vec<Segment>);
+
+/// Translates LaTeX math into Typst math, returning the errors found if any.
+fn translate(tex: &str) -> Result<Translation, Vec<TypstDiagnostic>> {
+    let mut translator = Translator::new(tex);
+    translator.sequence(None);
+    // At the top level, the sequence only ends early at a closing brace.
+    while let Some((_, start, end)) = translator.next() {
+        translator.error("Unmatched closing brace.".to_string(), vec![], start, end);
+        translator.sequence(None);
+    }
+    if translator.errors.is_empty() {
+        Ok((translator.output.trim_end().to_string(), translator.segments))
+    } else {
+        Err(translator.errors)
+    }
+}
+
+/// A Tex is a typesetting object that renders LaTeX math by translating it into Typst math. It supports fractions, roots, scripts, Greek letters, operators, matrices, `\left` and `\right` delimiters and font commands.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct Tex {
+    /// The LaTeX source of the Tex object.
+    source: Rc<String>,
+    /// The Typst object the LaTeX source is translated into.
+    typst: Typst,
+    /// The byte position of the translated math in the source of the Typst object.
+    offset: usize,
+    /// The ranges of the source of the Typst object translated from ranges of the LaTeX source.
+    segments: Rc<Vec<Segment>>,
+}
+
+impl Tex {
+    /// Gets the range of the LaTeX source that a range of the source of the Typst object was translated from, which is the smallest translated range containing it.
+    fn tex_range(&self, start: usize, end: usize) -> Option<(usize, usize)> {
+        let (start, end) = (start.checked_sub(self.offset)?, end.checked_sub(self.offset)?);
+        self.segments.iter()
+            .filter(|((segment_start, segment_end), _)| *segment_start <= start && end <= *segment_end)
+            .min_by_key(|((segment_start, segment_end), _)| segment_end - segment_start)
+            .map(|(_, range)| *range)
+    }
+    /// Renames the descendants of a VectorObject rendered from the Typst object after the ranges of the LaTeX source they were translated from, keeping the names of siblings unique.
+    fn renamed(&self, object: &VectorObject) -> VectorObject {
+        let children = object.children();
+        let ranges = children.iter()
+            .map(|child| {
+                child.name()
+                    .filter(|_| child.children().is_empty())
+                    .and_then(|name| span_range(&name))
+                    .and_then(|(start, end)| self.tex_range(start, end))
+            })
+            .collect::<Vec<Option<(usize, usize)>>>();
+        let renamed = children.iter()
+            .zip(span_names(&ranges))
+            .map(|(child, name)| match child.children().is_empty() {
+                true => VectorObjectBuilder::new(child).set_name(name),
+                false => VectorObjectBuilder::new(&self.renamed(child)),
+            })
+            .collect();
+        VectorObjectBuilder::new(object).set_children(renamed).build()
+    }
+    /// Relocates a diagnostic of the Typst object into the LaTeX source.
+    fn relocated(&self, diagnostic: TypstDiagnostic) -> TypstDiagnostic {
+        let source = Source::detached(self.source());
+        match diagnostic.start().zip(diagnostic.end()).and_then(|(start, end)| self.tex_range(start, end)) {
+            Some((start, end)) => TypstDiagnostic::located(diagnostic.severity(), diagnostic.message(), diagnostic.hints(), &source, start, end),
+            None => TypstDiagnostic::unlocated(diagnostic.severity(), diagnostic.message(), diagnostic.hints()),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Tex {
+    /// Creates a new Tex object by translating LaTeX math into Typst math. If the source uses unsupported commands or is malformed, the error message lists the problems with their positions in the source.
+    #[wasm_bindgen(constructor, return_description = "A new Tex object.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The LaTeX math source, without surrounding dollar signs.")]
+        source: String,
+        #[wasm_bindgen(param_description = "Whether the math is typeset in display style, like `\\[...\\]`, or inline, like `$...$`, default is true.")]
+        display: Option<bool>,
+        #[wasm_bindgen(param_description = "The settings and inputs to compile the translated source with, default is to compile it as it is.")]
+        settings: Option<TypstSettings>,
+    ) -> Result<Tex, JsError> {
+        let (math, segments) = translate(&source)
+            .map_err(|errors| JsError::new(&format!("Failed to translate LaTeX to Typst.\n{}", format_diagnostics(&errors))))?;
+        let (opening, closing) = if display.unwrap_or(true) { ("$ ", " $") } else { ("$", "$") };
+        let source_code = format!("{}{}{}", opening, math, closing);
+        let typst = match settings {
+            Some(settings) => Typst::new_with_settings(source_code, settings),
+            None => Typst::new(source_code),
+        };
+        Ok(Tex { source: Rc::new(source), typst, offset: opening.len(), segments: Rc::new(segments) })
+    }
+    /// Translates LaTeX math into Typst math and returns the problems found, such as unsupported commands or unmatched braces.
+    #[wasm_bindgen(return_description = "The errors of the LaTeX source, empty if it can be translated.")]
+    pub fn check(
+        #[wasm_bindgen(param_description = "The LaTeX math source, without surrounding dollar signs.")]
+        source: &str,
+    ) -> Vec<TypstDiagnostic> {
+        translate(source).err().unwrap_or_default()
+    }
+    /// Returns the LaTeX source of the Tex object.
+    #[wasm_bindgen(getter, return_description = "The LaTeX source of the Tex object.")]
+    pub fn source(&self) -> String {
+        self.source.to_string()
+    }
+    /// Returns the Typst source the LaTeX source is translated into.
+    #[wasm_bindgen(getter, return_description = "The translated Typst source.")]
+    pub fn typst_source(&self) -> String {
+        self.typst.source()
+    }
+    /// Returns the Typst object the LaTeX source is translated into. Its rendered children are named after ranges of the Typst source rather than the LaTeX source.
+    #[wasm_bindgen(getter, return_description = "The translated Typst object.")]
+    pub fn typst(&self) -> Typst {
+        self.typst.clone()
+    }
+    /// Returns a copy of the Tex object compiled with other settings and inputs.
+    #[wasm_bindgen(return_description = "The Tex object with the settings.")]
+    pub fn with_settings(
+        &self,
+        #[wasm_bindgen(param_description = "The settings and inputs to compile the translated source with.")]
+        settings: TypstSettings,
+    ) -> Tex {
+        Tex { typst: self.typst.with_settings(settings), ..self.clone() }
+    }
+    /// Compiles the translated Tex object and returns its errors and warnings, located in the LaTeX source when possible.
+    #[wasm_bindgen(return_description = "The diagnostics of the Tex object, errors first.")]
+    pub fn diagnostics(
+        &self,
+        #[wasm_bindgen(param_description = "The font faces to use when compiling the Tex object, default is FontFace.defaults().")]
+        font_faces: Option<Vec<FontFace>>
+    ) -> Result<Vec<TypstDiagnostic>, JsError> {
+        Ok(self.diagnostics_with_engine(&TypstEngine::for_font_faces(font_faces)?))
+    }
+    /// Compiles the translated Tex object with a TypstEngine and returns its errors and warnings, located in the LaTeX source when possible.
+    #[wasm_bindgen(return_description = "The diagnostics of the Tex object, errors first.")]
+    pub fn diagnostics_with_engine(
+        &self,
+        #[wasm_bindgen(param_description = "The engine to compile the Tex object with.")]
+        engine: &TypstEngine
+    ) -> Vec<TypstDiagnostic> {
+        self.typst.diagnostics_with_engine(engine).into_iter().map(|diagnostic| self.relocated(diagnostic)).collect()
+    }
+    /// Renders the Tex object to an SVG string.
+    #[wasm_bindgen(return_description = "The SVG string.")]
+    pub fn to_svg(
+        &self,
+        #[wasm_bindgen(param_description = "The font faces to use when rendering the Tex object, default is FontFace.defaults().")]
+        font_faces: Option<Vec<FontFace>>
+    ) -> Result<String, JsError> {
+        self.typst.to_svg(font_faces)
+    }
+    /// Renders the Tex object to an SVG string with a TypstEngine.
+    #[wasm_bindgen(return_description = "The SVG string.")]
+    pub fn to_svg_with_engine(
+        &self,
+        #[wasm_bindgen(param_description = "The engine to render the Tex object with.")]
+        engine: &TypstEngine
+    ) -> Result<String, JsError> {
+        self.typst.to_svg_with_engine(engine)
+    }
+    /// Renders the Tex object to a VectorObjectBuilder whose children are its glyphs and shapes in rendering order, like Typst's vector_object_builder. Each glyph and shape is named after the range of the LaTeX source it was translated from, such as `span_0_6` for `\alpha`, so it can be found with a Selector or with the indices_of method.
+    #[wasm_bindgen(return_description = "A VectorObjectBuilder representing the typesetting object.")]
+    pub fn vector_object_builder(
+        &self,
+        #[wasm_bindgen(param_description = "The FontFaces to use when rendering the Tex object, default is FontFace.defaults().")]
+        font_faces: Option<Vec<FontFace>>,
+    ) -> Result<VectorObjectBuilder, JsError> {
+        self.vector_object_builder_with_engine(&TypstEngine::for_font_faces(font_faces)?)
+    }
+    /// Renders the Tex object to a VectorObjectBuilder with a TypstEngine, like vector_object_builder.
+    #[wasm_bindgen(return_description = "A VectorObjectBuilder representing the typesetting object.")]
+    pub fn vector_object_builder_with_engine(
+        &self,
+        #[wasm_bindgen(param_description = "The engine to render the Tex object with.")]
+        engine: &TypstEngine,
+    ) -> Result<VectorObjectBuilder, JsError> {
+        let object = self.typst.vector_object_builder_with_engine(engine)?.build();
+        Ok(VectorObjectBuilder::new(&self.renamed(&object)))
+    }
+    /// Gets the ranges in the LaTeX source matched by a substring, one for each of its occurrences.
+    #[wasm_bindgen(return_description = "The spans in the LaTeX source matched by the substring.")]
+    pub fn spans_of(
+        &self,
+        #[wasm_bindgen(param_description = "A substring of the LaTeX source.")]
+        query: &str,
+    ) -> Vec<SourceSpan> {
+        if query.is_empty() {
+            return vec![];
+        }
+        self.source.match_indices(query).map(|(start, matched)| SourceSpan::new(start, start + matched.len())).collect()
+    }
+    /// Gets the indices of the children of a rendered Tex object that lie within the ranges of the LaTeX source matched by a substring. If the settings don't merge the pages, the children of the pages are searched and numbered in order across the pages, as if they were merged.
+    #[wasm_bindgen(return_description = "The indices of the matching children.")]
+    pub fn indices_of(
+        &self,
+        #[wasm_bindgen(param_description = "The vector object rendered from this Tex object.")]
+        object: &VectorObject,
+        #[wasm_bindgen(param_description = "A substring of the LaTeX source.")]
+        query: &str,
+    ) -> Vec<usize> {
+        let spans = self.spans_of(query);
+        self.typst.merged_pages(object).children()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, child)| Typst::source_span(child).map(|span| (index, span)))
+            .filter(|(_, span)| spans.iter().any(|other| span.is_within(other)))
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
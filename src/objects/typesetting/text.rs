@@ -0,0 +1,304 @@
+use std::rc::Rc;
+
+use rustybuzz::{ttf_parser::{GlyphId, OutlineBuilder, Tag}, Face, Feature, UnicodeBuffer};
+use usvg::tiny_skia_path::PathBuilder;
+use wasm_bindgen::prelude::*;
+
+use crate::{objects::{typesetting::typst::span_names, vector_object::VectorObjectBuilder}, utils::{font_face::FontFace, linear_algebra::TransformationMatrix, point2d::Path2D, style::{Color, Style}}};
+
+/// The horizontal alignment of the lines of a Text object.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextAlignment {
+    /// Lines start at the left edge of the text.
+    Left,
+    /// Lines are centered within the text.
+    Center,
+    /// Lines end at the right edge of the text.
+    Right,
+}
+
+/// Collects the outline of a glyph into a path.
+struct GlyphOutline {
+    /// The path being built.
+    builder: PathBuilder,
+}
+
+impl OutlineBuilder for GlyphOutline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.builder.move_to(x, y);
+    }
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.builder.line_to(x, y);
+    }
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.builder.quad_to(x1, y1, x, y);
+    }
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.builder.cubic_to(x1, y1, x2, y2, x, y);
+    }
+    fn close(&mut self) {
+        self.builder.close();
+    }
+}
+
+/// Returns the outline of a glyph scaled from font units, drawn upwards from its origin, or none if the glyph has no outline.
+pub(crate) fn glyph_path(face: &Face, id: GlyphId, scale: f32) -> Option<Path2D> {
+    let mut outline = GlyphOutline { builder: PathBuilder::new() };
+    let path = face.outline_glyph(id, &mut outline).and(outline.builder.finish())?;
+    Some(TransformationMatrix::scale(scale, scale) * Path2D::from_svg_path_data(&path))
+}
+
+/// A glyph laid out on a line, with the byte range of the text it was shaped from.
+struct PlacedGlyph {
+    /// The id of the glyph in the font.
+    id: u16,
+    /// The horizontal position of the glyph origin from the start of the line, in font units.
+    x: f32,
+    /// The vertical offset of the glyph origin from the baseline, in font units, upwards.
+    y: f32,
+    /// The byte range of the text the glyph was shaped from.
+    range: (usize, usize),
+}
+
+/// A Text is a text object laid out with a FontFace, without compiling it with Typst. It supports kerning, ligatures, line breaks, alignment and letter spacing.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct Text {
+    /// The text to lay out.
+    text: Rc<String>,
+    /// The font face to lay out the text with.
+    font_face: FontFace,
+    /// The size of the font in pixels.
+    font_size: f32,
+    /// The color of the glyphs.
+    color: Color,
+    /// The horizontal alignment of the lines.
+    alignment: TextAlignment,
+    /// The distance between consecutive baselines, relative to the font size.
+    line_height: f32,
+    /// The extra space after each character in pixels.
+    letter_spacing: f32,
+    /// Whether the kerning of the font is applied.
+    kerning: bool,
+    /// Whether the standard ligatures of the font are applied.
+    ligatures: bool,
+    /// The width in pixels the lines are wrapped at between words, or none to only break lines at line feeds.
+    max_width: Option<f32>,
+}
+
+impl Text {
+    /// Shapes a line of the text, returning its glyphs and its width in font units. The range of each glyph is relative to the text, given the start of the line in it.
+    fn shape(&self, face: &Face, line: &str, line_start: usize, scale: f32) -> (Vec<PlacedGlyph>, f32) {
+        let mut buffer = UnicodeBuffer::new();
+        buffer.push_str(line);
+        let features = [
+            Feature::new(Tag::from_bytes(b"kern"), self.kerning as u32, ..),
+            Feature::new(Tag::from_bytes(b"liga"), self.ligatures as u32, ..),
+            Feature::new(Tag::from_bytes(b"clig"), self.ligatures as u32, ..),
+        ];
+        let output = rustybuzz::shape(face, &features, buffer);
+        let infos = output.glyph_infos();
+        let mut clusters = infos.iter().map(|info| info.cluster as usize).collect::<Vec<usize>>();
+        clusters.sort_unstable();
+        clusters.dedup();
+        let spacing = self.letter_spacing / scale;
+        let mut x = 0.0;
+        let glyphs = infos.iter().zip(output.glyph_positions()).map(|(info, position)| {
+            let start = info.cluster as usize;
+            let end = clusters.iter().find(|cluster| **cluster > start).copied().unwrap_or(line.len());
+            let glyph = PlacedGlyph {
+                id: info.glyph_id as u16,
+                x: x + position.x_offset as f32,
+                y: position.y_offset as f32,
+                range: (line_start + start, line_start + end),
+            };
+            x += position.x_advance as f32 + spacing;
+            glyph
+        }).collect::<Vec<PlacedGlyph>>();
+        // Letter spacing only goes between characters, so the last one doesn't widen the line.
+        let width = if glyphs.is_empty() { 0.0 } else { x - spacing };
+        (glyphs, width)
+    }
+    /// Splits the text into lines at line feeds and, if there is a maximum width, between words that would overflow it. Each line is given by its byte range in the text.
+    fn lines(&self, face: &Face, scale: f32) -> Vec<(usize, usize)> {
+        let mut lines = vec![];
+        let mut paragraph_start = 0;
+        for paragraph in self.text.split('\n') {
+            let content = paragraph.strip_suffix('\r').unwrap_or(paragraph);
+            let mut line_start = 0;
+            if let Some(max_width) = self.max_width {
+                let mut line_end = 0;
+                for (word_end, _) in content.match_indices(' ').chain([(content.len(), "")]) {
+                    let (_, width) = self.shape(face, &content[line_start..word_end], 0, scale);
+                    if width * scale > max_width && line_end > line_start {
+                        lines.push((paragraph_start + line_start, paragraph_start + line_end));
+                        line_start = line_end + 1;
+                    }
+                    line_end = word_end;
+                }
+            }
+            lines.push((paragraph_start + line_start, paragraph_start + content.len()));
+            paragraph_start += paragraph.len() + 1;
+        }
+        lines
+    }
+    /// Parses the font face of the Text object, failing if its data isn't a font.
+    fn face<'a>(&self, data: &'a [u8]) -> Result<Face<'a>, JsError> {
+        Face::from_slice(data, 0).ok_or_else(|| JsError::new("The font face of the Text object could not be parsed."))
+    }
+    /// Lays out the lines of the text, returning the glyphs and the width in pixels of each one.
+    fn layout(&self, face: &Face, scale: f32) -> Vec<(Vec<PlacedGlyph>, f32)> {
+        self.lines(face, scale).into_iter()
+            .map(|(start, end)| {
+                let (glyphs, width) = self.shape(face, &self.text[start..end], start, scale);
+                (glyphs, width * scale)
+            })
+            .collect()
+    }
+}
+
+#[wasm_bindgen]
+impl Text {
+    /// Creates a new Text object from a string and a font face.
+    #[wasm_bindgen(constructor, return_description = "A new Text object.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The text to lay out, where line feeds start new lines.")]
+        text: String,
+        #[wasm_bindgen(param_description = "The font face to lay out the text with.")]
+        font_face: FontFace,
+        #[wasm_bindgen(param_description = "The size of the font in pixels, default is 16.")]
+        font_size: Option<f32>,
+        #[wasm_bindgen(param_description = "The color of the glyphs, default is black.")]
+        color: Option<Color>,
+        #[wasm_bindgen(param_description = "The horizontal alignment of the lines, default is TextAlignment.Left.")]
+        alignment: Option<TextAlignment>,
+        #[wasm_bindgen(param_description = "The distance between consecutive baselines relative to the font size, default is 1.2.")]
+        line_height: Option<f32>,
+        #[wasm_bindgen(param_description = "The extra space after each character in pixels, default is 0.")]
+        letter_spacing: Option<f32>,
+        #[wasm_bindgen(param_description = "Whether the kerning of the font is applied, default is true.")]
+        kerning: Option<bool>,
+        #[wasm_bindgen(param_description = "Whether the standard ligatures of the font are applied, default is true.")]
+        ligatures: Option<bool>,
+        #[wasm_bindgen(param_description = "The width in pixels the lines are wrapped at between words, default is to only break lines at line feeds.")]
+        max_width: Option<f32>,
+    ) -> Text {
+        Text {
+            text: Rc::new(text),
+            font_face,
+            font_size: font_size.unwrap_or(16.0),
+            color: color.unwrap_or(Color::new(0, 0, 0, 1.0)),
+            alignment: alignment.unwrap_or(TextAlignment::Left),
+            line_height: line_height.unwrap_or(1.2),
+            letter_spacing: letter_spacing.unwrap_or(0.0),
+            kerning: kerning.unwrap_or(true),
+            ligatures: ligatures.unwrap_or(true),
+            max_width,
+        }
+    }
+    /// Clones the Text object.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn copy(&self) -> Text {
+        self.clone()
+    }
+    /// Returns the text of the Text object.
+    #[wasm_bindgen(getter, return_description = "The text of the Text object.")]
+    pub fn text(&self) -> String {
+        self.text.to_string()
+    }
+    /// Returns the font face the text is laid out with.
+    #[wasm_bindgen(getter, return_description = "The font face of the Text object.")]
+    pub fn font_face(&self) -> FontFace {
+        self.font_face.clone()
+    }
+    /// Returns the size of the font in pixels.
+    #[wasm_bindgen(getter, return_description = "The size of the font in pixels.")]
+    pub fn font_size(&self) -> f32 {
+        self.font_size
+    }
+    /// Returns the color of the glyphs.
+    #[wasm_bindgen(getter, return_description = "The color of the glyphs.")]
+    pub fn color(&self) -> Color {
+        self.color
+    }
+    /// Returns the horizontal alignment of the lines.
+    #[wasm_bindgen(getter, return_description = "The alignment of the lines.")]
+    pub fn alignment(&self) -> TextAlignment {
+        self.alignment
+    }
+    /// Returns the distance between consecutive baselines relative to the font size.
+    #[wasm_bindgen(getter, return_description = "The line height relative to the font size.")]
+    pub fn line_height(&self) -> f32 {
+        self.line_height
+    }
+    /// Returns the extra space after each character in pixels.
+    #[wasm_bindgen(getter, return_description = "The letter spacing in pixels.")]
+    pub fn letter_spacing(&self) -> f32 {
+        self.letter_spacing
+    }
+    /// Returns whether the kerning of the font is applied.
+    #[wasm_bindgen(getter, return_description = "Whether kerning is applied.")]
+    pub fn kerning(&self) -> bool {
+        self.kerning
+    }
+    /// Returns whether the standard ligatures of the font are applied.
+    #[wasm_bindgen(getter, return_description = "Whether ligatures are applied.")]
+    pub fn ligatures(&self) -> bool {
+        self.ligatures
+    }
+    /// Returns the width in pixels the lines are wrapped at, if any.
+    #[wasm_bindgen(getter, return_description = "The maximum width of the lines in pixels.")]
+    pub fn max_width(&self) -> Option<f32> {
+        self.max_width
+    }
+    /// Lays out the text and returns the width of its widest line in pixels, including spaces.
+    #[wasm_bindgen(return_description = "The width of the text in pixels.")]
+    pub fn width(&self) -> Result<f32, JsError> {
+        let data = self.font_face.shared_data();
+        let face = self.face(&data)?;
+        let scale = self.font_size / face.units_per_em() as f32;
+        Ok(self.layout(&face, scale).iter().map(|(_, width)| *width).fold(0.0, f32::max))
+    }
+    /// Lays out the text and returns a VectorObjectBuilder whose children are its glyphs in reading order. The top of the first line is at y = 0 and lines are aligned within the widest one, or within the maximum width if there is one. Each glyph is named after the range of the text it was shaped from, such as `span_4_5`, with the number of previous glyphs from the same range appended to the name when a range is shaped into several glyphs, such as `span_4_5_1`, so it can be found with a Selector or with Typst.source_span. Glyphs without an outline, like spaces, have no child.
+    #[wasm_bindgen(return_description = "A VectorObjectBuilder representing the text.")]
+    pub fn vector_object_builder(&self) -> Result<VectorObjectBuilder, JsError> {
+        let data = self.font_face.shared_data();
+        let face = self.face(&data)?;
+        let scale = self.font_size / face.units_per_em() as f32;
+        let lines = self.layout(&face, scale);
+        let block_width = self.max_width
+            .unwrap_or_else(|| lines.iter().map(|(_, width)| *width).fold(0.0, f32::max));
+        let fill = Style::from_color(self.color);
+        let mut glyph_objects = vec![];
+        let mut ranges = vec![];
+        let mut baseline = face.ascender() as f32 * scale;
+        for (glyphs, width) in lines {
+            let x = match self.alignment {
+                TextAlignment::Left => 0.0,
+                TextAlignment::Center => (block_width - width) / 2.0,
+                TextAlignment::Right => block_width - width,
+            };
+            for glyph in glyphs {
+                let Some(path) = glyph_path(&face, GlyphId(glyph.id), scale) else {
+                    continue;
+                };
+                // Glyphs are drawn upwards, so they are mirrored vertically around their origin on the baseline.
+                let transform = TransformationMatrix::translate(x + glyph.x * scale, baseline - glyph.y * scale) * TransformationMatrix::scale(1.0, -1.0);
+                glyph_objects.push(
+                    VectorObjectBuilder::default()
+                        .set_path(path)
+                        .set_fill(fill.clone(), Some(false))
+                        .set_transform(transform, Some(false))
+                );
+                ranges.push(Some(glyph.range));
+            }
+            baseline += self.font_size * self.line_height;
+        }
+        let children = glyph_objects.into_iter()
+            .zip(span_names(&ranges))
+            .map(|(glyph_object, name)| glyph_object.set_name(name))
+            .collect();
+        Ok(VectorObjectBuilder::default().set_children(children))
+    }
+}
@@ -1,68 +1,733 @@
-use std::rc::Rc;
+use std::{collections::HashMap, fmt, rc::Rc, sync::OnceLock};
 
-use typst::{foundations::Bytes, layout::Abs, text::Font};
-use typst_as_lib::TypstTemplate;
-use typst_svg::svg_merged;
+use typst::{diag::{Severity, SourceDiagnostic}, foundations::{Dict, Value}, layout::{Abs, Frame, FrameItem, Page, PagedDocument}, syntax::{LinkedNode, Source, Span, SyntaxKind}, text::Font, Library};
+use typst_as_lib::TypstAsLibError;
+use typst_svg::{svg, svg_merged};
+use unicode_normalization::UnicodeNormalization;
 use wasm_bindgen::prelude::*;
 
-use crate::{objects::vector_object::VectorObjectBuilder, utils::font_face::FontFace};
+use crate::{objects::{typesetting::{typst_engine::TypstEngine, typst_settings::TypstSettings}, vector_object::{VectorObject, VectorObjectBuilder}}, utils::{font_face::FontFace, linear_algebra::TransformationMatrix, point2d::Point2D}};
+
+/// The severity of a TypstDiagnostic.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    /// A fatal error that prevents the Typst object from being rendered.
+    Error,
+    /// A non-fatal warning, such as a missing font or glyph.
+    Warning,
+}
+
+/// A TypstDiagnostic is an error or a warning reported when compiling a Typst object, located in its source when possible.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct TypstDiagnostic {
+    /// Whether the diagnostic is an error or a warning.
+    severity: DiagnosticSeverity,
+    /// The message describing the problem.
+    message: String,
+    /// The byte range of the problem in the source, if it can be located.
+    span: Option<(usize, usize)>,
+    /// The one-based line and column of the start of the problem in the source, if it can be located.
+    position: Option<(usize, usize)>,
+    /// Hints on how the problem could be avoided or worked around.
+    hints: Vec<String>,
+}
+
+#[wasm_bindgen]
+impl TypstDiagnostic {
+    /// Gets whether the diagnostic is an error or a warning.
+    #[wasm_bindgen(getter, return_description = "The severity of the diagnostic.")]
+    pub fn severity(&self) -> DiagnosticSeverity {
+        self.severity
+    }
+    /// Gets the message describing the problem.
+    #[wasm_bindgen(getter, return_description = "The message of the diagnostic.")]
+    pub fn message(&self) -> String {
+        self.message.clone()
+    }
+    /// Gets the byte offset in the source where the problem starts, if it can be located.
+    #[wasm_bindgen(getter, return_description = "The start of the span of the diagnostic.")]
+    pub fn start(&self) -> Option<usize> {
+        self.span.map(|(start, _)| start)
+    }
+    /// Gets the byte offset in the source where the problem ends, if it can be located.
+    #[wasm_bindgen(getter, return_description = "The end of the span of the diagnostic.")]
+    pub fn end(&self) -> Option<usize> {
+        self.span.map(|(_, end)| end)
+    }
+    /// Gets the one-based line of the source where the problem starts, if it can be located.
+    #[wasm_bindgen(getter, return_description = "The line of the diagnostic.")]
+    pub fn line(&self) -> Option<usize> {
+        self.position.map(|(line, _)| line)
+    }
+    /// Gets the one-based column, in characters, of the source where the problem starts, if it can be located.
+    #[wasm_bindgen(getter, return_description = "The column of the diagnostic.")]
+    pub fn column(&self) -> Option<usize> {
+        self.position.map(|(_, column)| column)
+    }
+    /// Gets the hints on how the problem could be avoided or worked around.
+    #[wasm_bindgen(getter, return_description = "The hints of the diagnostic.")]
+    pub fn hints(&self) -> Vec<String> {
+        self.hints.clone()
+    }
+    /// Formats the diagnostic as a readable string, such as "error at 1:5: unclosed delimiter" followed by its hints.
+    #[wasm_bindgen(js_name = toString, return_description = "The formatted diagnostic.")]
+    pub fn format(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl TypstDiagnostic {
+    /// Creates a TypstDiagnostic that can't be located in the source.
+    pub fn unlocated(severity: DiagnosticSeverity, message: String, hints: Vec<String>) -> TypstDiagnostic {
+        TypstDiagnostic { severity, message, span: None, position: None, hints }
+    }
+    /// Creates a TypstDiagnostic located at a byte range of a Source.
+    pub fn located(severity: DiagnosticSeverity, message: String, hints: Vec<String>, source: &Source, start: usize, end: usize) -> TypstDiagnostic {
+        let position = source.byte_to_line(start).zip(source.byte_to_column(start)).map(|(line, column)| (line + 1, column + 1));
+        TypstDiagnostic { severity, message, span: Some((start, end)), position, hints }
+    }
+    /// Relocates a TypstDiagnostic of a source compiled after a prefix of some length. Diagnostics within the prefix can't be located in the source.
+    fn relocated(self, prefix_length: usize, source: &Source) -> TypstDiagnostic {
+        match self.span {
+            Some((start, end)) if start >= prefix_length => TypstDiagnostic::located(self.severity, self.message, self.hints, source, start - prefix_length, end - prefix_length),
+            _ => TypstDiagnostic::unlocated(self.severity, self.message, self.hints),
+        }
+    }
+    /// Converts a SourceDiagnostic of Typst, locating its span in a Source.
+    fn from_source_diagnostic(diagnostic: &SourceDiagnostic, source: &Source) -> TypstDiagnostic {
+        let severity = match diagnostic.severity {
+            Severity::Error => DiagnosticSeverity::Error,
+            Severity::Warning => DiagnosticSeverity::Warning,
+        };
+        let message = diagnostic.message.to_string();
+        let hints = diagnostic.hints.iter().map(|hint| hint.to_string()).collect();
+        match source.range(diagnostic.span) {
+            Some(range) => TypstDiagnostic::located(severity, message, hints, source, range.start, range.end),
+            None => TypstDiagnostic::unlocated(severity, message, hints),
+        }
+    }
+    /// Converts a compilation error of typst-as-lib into TypstDiagnostics.
+    fn from_error(error: TypstAsLibError, source: &Source) -> Vec<TypstDiagnostic> {
+        let unlocated = |message: String, hints: Vec<String>| vec![TypstDiagnostic::unlocated(DiagnosticSeverity::Error, message, hints)];
+        match error {
+            TypstAsLibError::TypstSource(diagnostics) => diagnostics.iter()
+                .map(|diagnostic| TypstDiagnostic::from_source_diagnostic(diagnostic, source))
+                .collect(),
+            TypstAsLibError::TypstFile(error) => unlocated(error.to_string(), vec![]),
+            TypstAsLibError::HintedString(hinted) => unlocated(
+                hinted.message().to_string(),
+                hinted.hints().iter().map(|hint| hint.to_string()).collect(),
+            ),
+            TypstAsLibError::MainSourceFileDoesNotExist(_) => unlocated("The source of the Typst object could not be found.".to_string(), vec![]),
+            // Other variants differ between releases of typst-as-lib, so they're reported through their messages.
+            error => unlocated(error.to_string(), vec![]),
+        }
+    }
+}
+
+impl fmt::Display for TypstDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            DiagnosticSeverity::Error => "error",
+            DiagnosticSeverity::Warning => "warning",
+        };
+        match self.position {
+            Some((line, column)) => write!(f, "{} at {}:{}: {}", severity, line, column, self.message)?,
+            None => write!(f, "{}: {}", severity, self.message)?,
+        }
+        for hint in &self.hints {
+            write!(f, "\n  hint: {}", hint)?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats TypstDiagnostics as a readable string, one diagnostic after another.
+pub fn format_diagnostics(diagnostics: &[TypstDiagnostic]) -> String {
+    diagnostics.iter().map(|diagnostic| diagnostic.to_string()).collect::<Vec<String>>().join("\n")
+}
+
+/// Adds a warning for a character that no font contains, unless the same character at the same range was already reported.
+fn push_missing_glyph_warning(character: &str, range: Option<(usize, usize)>, source: &Source, warnings: &mut Vec<TypstDiagnostic>) {
+    let message = format!("no font contains a glyph for \"{}\"", character);
+    let hints = vec!["provide a font face that contains this character".to_string()];
+    let warning = match range {
+        Some((start, end)) => TypstDiagnostic::located(DiagnosticSeverity::Warning, message, hints, source, start, end),
+        None => TypstDiagnostic::unlocated(DiagnosticSeverity::Warning, message, hints),
+    };
+    if !warnings.iter().any(|other| other.message == warning.message && other.span == warning.span) {
+        warnings.push(warning);
+    }
+}
+
+/// Collects warnings for the characters written in the markup and math of a source that none of the fonts contain. Typst drops such characters silently when no font at all was selected for them.
+fn uncovered_character_warnings(node: &LinkedNode, fonts: &[Font], source: &Source, warnings: &mut Vec<TypstDiagnostic>) {
+    let is_text = match node.kind() {
+        SyntaxKind::Text | SyntaxKind::MathText => true,
+        SyntaxKind::MathIdent => node.text().chars().count() == 1,
+        _ => false,
+    };
+    if is_text {
+        for (index, character) in node.text().char_indices() {
+            if character.is_whitespace() || character.is_control() || fonts.iter().any(|font| font.info().coverage.contains(character as u32)) {
+                continue;
+            }
+            let start = node.offset() + index;
+            let character = &node.text()[index..index + character.len_utf8()];
+            push_missing_glyph_warning(character, Some((start, start + character.len())), source, warnings);
+        }
+    }
+    for child in node.children() {
+        uncovered_character_warnings(&child, fonts, source, warnings);
+    }
+}
+
+/// Collects warnings for the glyphs of a Frame that no font could provide, which Typst renders as empty boxes without warning.
+fn missing_glyph_warnings(frame: &Frame, source: &Source, warnings: &mut Vec<TypstDiagnostic>) {
+    for (_, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => missing_glyph_warnings(&group.frame, source, warnings),
+            FrameItem::Text(text) => {
+                for glyph in text.glyphs.iter().filter(|glyph| glyph.id == 0) {
+                    let character = text.text.get(glyph.range()).unwrap_or_default();
+                    let (span, offset) = glyph.span;
+                    push_missing_glyph_warning(character, located_range(span, offset as usize, character, source), source, warnings);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Gets the byte range of some text at an offset from the start of a Span in a Source. If the source doesn't contain the text there, as with math symbols written by name, the range of the whole span is used.
+fn located_range(span: Span, offset: usize, text: &str, source: &Source) -> Option<(usize, usize)> {
+    let range = source.range(span)?;
+    let start = range.start + offset;
+    if !text.is_empty() && source.text().get(start..start + text.len()) == Some(text) {
+        Some((start, start + text.len()))
+    } else {
+        Some((range.start, range.end))
+    }
+}
+
+/// A glyph or shape of a rendered document.
+struct RenderedItem {
+    /// The origin of the glyph or shape in the parsed SVG.
+    origin: Point2D,
+    /// The range of the glyph or shape in the source, if it has one.
+    range: Option<(usize, usize)>,
+    /// The text of the glyph, if it's a glyph.
+    text: Option<String>,
+}
+
+/// A piece of math written in a source that renders as text, such as a symbol name like `alpha`, an operator like `lim` or a string literal.
+struct MathAtom {
+    /// The range of the atom in the source.
+    range: (usize, usize),
+    /// The normalized text the atom renders as.
+    text: String,
+    /// Whether the atom renders as its own source text, so each character has its own range.
+    literal: bool,
+    /// The number of bytes of the text already matched to glyphs.
+    matched: usize,
+}
+
+/// The standard library, whose math scope resolves the names of symbols and operators.
+static LIBRARY: OnceLock<Library> = OnceLock::new();
+
+/// Normalizes the text of a glyph or a symbol for comparison, so that styled math letters like `𝛼` compare equal to `α`.
+fn normalized(text: &str) -> String {
+    text.nfkc().collect()
+}
+
+/// Gets the text a math identifier or a field access on one renders as, such as `α` for `alpha` or `→` for `arrow.r`, if it's a symbol or an operator of the standard library.
+fn resolved_text(node: &LinkedNode) -> Option<String> {
+    let scope = LIBRARY.get_or_init(Library::default).math.scope();
+    let value = match node.kind() {
+        SyntaxKind::MathIdent => scope.get(node.text())?.read().clone(),
+        SyntaxKind::FieldAccess => {
+            let mut parts = node.get().clone().into_text().split('.').map(|part| part.trim().to_string()).collect::<Vec<String>>().into_iter();
+            let Value::Symbol(mut symbol) = scope.get(&parts.next()?)?.read().clone() else {
+                return None;
+            };
+            for modifier in parts {
+                symbol = symbol.modified(&modifier).ok()?;
+            }
+            Value::Symbol(symbol)
+        }
+        _ => return None,
+    };
+    match value {
+        Value::Symbol(symbol) => Some(symbol.get().to_string()),
+        Value::Content(content) => Some(content.plain_text().to_string()),
+        _ => None,
+    }
+}
+
+/// Collects the math atoms of a source in source order. Their glyphs may have no span, because Typst drops the spans of symbols and strings passed alone as arguments, like `alpha` in `frac(alpha, beta)`, and of operators defined by the library, like `lim`.
+fn collect_math_atoms(node: &LinkedNode, math: bool, atoms: &mut Vec<MathAtom>) {
+    let range = (node.range().start, node.range().end);
+    match node.kind() {
+        SyntaxKind::MathIdent | SyntaxKind::FieldAccess if math => {
+            if let Some(text) = resolved_text(node) {
+                let literal = text == node.text().as_str();
+                atoms.push(MathAtom { range, text: normalized(&text), literal, matched: 0 });
+                return;
+            }
+        }
+        SyntaxKind::Str if math => {
+            let text = node.text().trim_matches('"');
+            atoms.push(MathAtom { range: (range.0 + 1, range.1 - 1), text: normalized(text), literal: true, matched: 0 });
+            return;
+        }
+        _ => {}
+    }
+    let math = math || node.kind() == SyntaxKind::Equation;
+    for child in node.children() {
+        collect_math_atoms(&child, math, atoms);
+    }
+}
+
+/// Gives the glyphs without a range the range of the math atom they were rendered from. Each glyph is matched, in rendering order, to the first atom in source order whose next unmatched text it renders, leaving out atoms with glyphs that already have a range in them.
+fn recover_ranges(items: &mut [RenderedItem], source: &str) {
+    let source = Source::detached(source);
+    let mut atoms = vec![];
+    collect_math_atoms(&LinkedNode::new(source.root()), false, &mut atoms);
+    atoms.retain(|atom| !items.iter().any(|item| item.range.is_some_and(|(start, end)| atom.range.0 <= start && end <= atom.range.1)));
+    for item in items.iter_mut().filter(|item| item.range.is_none()) {
+        let Some(text) = item.text.as_deref().map(normalized).filter(|text| !text.trim().is_empty()) else {
+            continue;
+        };
+        let Some(atom) = atoms.iter_mut().find(|atom| atom.text[atom.matched..].starts_with(&text)) else {
+            continue;
+        };
+        item.range = Some(if atom.literal {
+            (atom.range.0 + atom.matched, atom.range.0 + atom.matched + text.len())
+        } else {
+            atom.range
+        });
+        atom.matched += text.len();
+    }
+}
+
+/// Collects the origins and source ranges of the glyphs and shapes of a Frame in rendering order, where the transform maps the frame to the parsed SVG.
+fn collect_items(frame: &Frame, transform: TransformationMatrix, source: &Source, glyphs: &mut Vec<RenderedItem>) {
+    for (position, item) in frame.items() {
+        let transform = transform * TransformationMatrix::translate(position.x.to_pt() as f32, position.y.to_pt() as f32);
+        match item {
+            FrameItem::Group(group) => {
+                let group_transform = group.transform;
+                let group_transform = TransformationMatrix::new(
+                    group_transform.sx.get() as f32,
+                    group_transform.ky.get() as f32,
+                    group_transform.kx.get() as f32,
+                    group_transform.sy.get() as f32,
+                    group_transform.tx.to_pt() as f32,
+                    group_transform.ty.to_pt() as f32,
+                );
+                collect_items(&group.frame, transform * group_transform, source, glyphs);
+            }
+            FrameItem::Shape(_, span) => {
+                glyphs.push(RenderedItem {
+                    origin: transform * Point2D::new(0.0, 0.0),
+                    range: source.range(*span).map(|range| (range.start, range.end)),
+                    text: None,
+                });
+            }
+            FrameItem::Text(text) => {
+                let mut x = 0.0;
+                for glyph in &text.glyphs {
+                    let offset = x + glyph.x_offset.at(text.size).to_pt();
+                    let (span, span_offset) = glyph.span;
+                    let glyph_text = text.text.get(glyph.range()).unwrap_or_default();
+                    glyphs.push(RenderedItem {
+                        origin: transform * Point2D::new(offset as f32, 0.0),
+                        range: located_range(span, span_offset as usize, glyph_text, source),
+                        text: Some(glyph_text.to_string()),
+                    });
+                    x += glyph.x_advance.at(text.size).to_pt();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Collects the descendants of a VectorObject that have a path, dropping the groups around them.
+fn collect_leaves(object: &VectorObject, leaves: &mut Vec<VectorObject>) {
+    if object.num_points() > 0 {
+        leaves.push(object.clone());
+    }
+    for child in object.children() {
+        collect_leaves(&child, leaves);
+    }
+}
+
+/// Collects the ranges of the elements labelled with a name, which are the elements right before their `<name>` labels.
+fn label_ranges(node: &LinkedNode, name: &str, ranges: &mut Vec<(usize, usize)>) {
+    if node.kind() == SyntaxKind::Label && node.text().trim_start_matches('<').trim_end_matches('>') == name {
+        if let Some(element) = node.prev_sibling() {
+            ranges.push((element.range().start, element.range().end));
+        }
+    }
+    for child in node.children() {
+        label_ranges(&child, name, ranges);
+    }
+}
+
+/// Gets the name of a child from its range in the source.
+pub fn span_name(start: usize, end: usize) -> String {
+    format!("span_{}_{}", start, end)
+}
+
+/// Gets the names of children from their ranges in the source, appending to each repeated range the number of previous children with it, such as `span_4_5_1`, so that every name is unique.
+pub fn span_names(ranges: &[Option<(usize, usize)>]) -> Vec<Option<String>> {
+    let mut counts = HashMap::new();
+    ranges.iter().map(|range| {
+        let (start, end) = (*range)?;
+        let count = counts.entry((start, end)).or_insert(0);
+        *count += 1;
+        Some(match *count {
+            1 => span_name(start, end),
+            count => format!("{}_{}", span_name(start, end), count - 1),
+        })
+    }).collect()
+}
+
+/// Gets the range in the source of a child from its name, ignoring the index that makes it unique.
+pub fn span_range(name: &str) -> Option<(usize, usize)> {
+    let mut parts = name.strip_prefix("span_")?.split('_');
+    let (start, end) = (parts.next()?.parse().ok()?, parts.next()?.parse().ok()?);
+    match parts.next() {
+        Some(index) if index.parse::<usize>().is_err() || parts.next().is_some() => None,
+        _ => Some((start, end)),
+    }
+}
+
+/// A SourceSpan is a range of bytes in the source of a typesetting object.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SourceSpan {
+    /// The byte offset where the span starts.
+    pub start: usize,
+    /// The byte offset where the span ends, exclusive.
+    pub end: usize,
+}
+
+#[wasm_bindgen]
+impl SourceSpan {
+    /// Creates a new SourceSpan from a start and an end byte offset.
+    #[wasm_bindgen(constructor, return_description = "A new source span.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The byte offset where the span starts.")]
+        start: usize,
+        #[wasm_bindgen(param_description = "The byte offset where the span ends, exclusive.")]
+        end: usize,
+    ) -> SourceSpan {
+        SourceSpan { start, end }
+    }
+    /// Checks whether the span lies within another span.
+    #[wasm_bindgen(return_description = "Whether the span lies within the other span.")]
+    pub fn is_within(
+        &self,
+        #[wasm_bindgen(param_description = "The span that may contain this one.")]
+        other: &SourceSpan,
+    ) -> bool {
+        other.start <= self.start && self.end <= other.end
+    }
+}
+
+/// The scale from the points of Typst's SVG output to the pixels of the parsed SVG.
+const PT_TO_PX: f32 = 4.0 / 3.0;
+
+/// The result of compiling a Typst object, with either the document or the errors, along with the warnings. The spans of the document refer to the compiled Source, in which the source of the Typst object starts after a prefix of the given length.
+type Compilation = (Result<PagedDocument, Vec<TypstDiagnostic>>, Vec<TypstDiagnostic>, Source, usize);
+
+/// Collects the origins and source ranges of the glyphs and shapes of the pages of a document in rendering order, where the transform maps the first page to the parsed SVG and each page is placed below the previous one if they are stacked. Ranges are relative to the end of the prefix, and items within it have none. The ranges of glyphs whose spans Typst dropped are recovered from the own source of the Typst object.
+fn collect_page_items(pages: &[Page], transform: TransformationMatrix, stacked: bool, source: &Source, prefix_length: usize) -> Vec<RenderedItem> {
+    let mut items = vec![];
+    let mut y = 0.0;
+    for page in pages {
+        collect_items(&page.frame, transform * TransformationMatrix::translate(0.0, y), source, &mut items);
+        if stacked {
+            y += page.frame.height().to_pt() as f32;
+        }
+    }
+    for item in items.iter_mut() {
+        item.range = item.range.filter(|(start, _)| *start >= prefix_length).map(|(start, end)| (start - prefix_length, end - prefix_length));
+    }
+    recover_ranges(&mut items, &source.text()[prefix_length..]);
+    items
+}
+
+/// Builds a flat VectorObject from the SVG of a rendered Typst object, whose children are uniquely named after the ranges of the glyphs and shapes they were rendered from.
+fn build_from_svg(svg: String, items: &[RenderedItem], font_faces: Vec<FontFace>) -> VectorObject {
+    let object = VectorObjectBuilder::from_svg(svg, Some(font_faces), None).build();
+    let mut leaves = vec![];
+    collect_leaves(&object, &mut leaves);
+    // Glyphs without an outline, like spaces, aren't rendered, so each child is matched to the next glyph or shape at its origin.
+    let mut next_item = 0;
+    let ranges = leaves.iter().map(|leaf| {
+        let origin = leaf.glyph_origin().unwrap_or(leaf.transform() * Point2D::new(0.0, 0.0));
+        let index = items[next_item..].iter().position(|item| item.origin.distance(&origin) < 1e-2)?;
+        next_item += index + 1;
+        items[next_item - 1].range
+    }).collect::<Vec<Option<(usize, usize)>>>();
+    let children = leaves.iter()
+        .zip(span_names(&ranges))
+        .map(|(leaf, name)| VectorObjectBuilder::new(leaf).set_name(name))
+        .collect();
+    VectorObjectBuilder::default().set_children(children).build()
+}
 
 /// A Typst is a typesetting object that can be used to render math text with Typst.
 #[wasm_bindgen]
+#[derive(Clone)]
 pub struct Typst {
     /// The source of the Typst object.
     source: Rc<String>,
+    /// The settings and inputs the source is compiled with, if any.
+    settings: Option<Rc<TypstSettings>>,
+}
+
+impl Typst {
+    /// Gets a vector object rendered from the Typst object with its glyphs and shapes as children, moving the children of its pages into it in order if the settings don't merge the pages.
+    pub fn merged_pages(&self, object: &VectorObject) -> VectorObject {
+        if self.settings.as_ref().is_none_or(|settings| settings.merge_pages()) {
+            return object.clone();
+        }
+        let children = object.children().iter().flat_map(VectorObject::children).map(|child| VectorObjectBuilder::new(&child)).collect();
+        VectorObjectBuilder::new(object).set_children(children).build()
+    }
+    /// Compiles the Typst object into a document with a TypstEngine, returning either the document or the errors, along with the warnings. The settings are compiled as a prefix of the source.
+    fn compile(&self, engine: &TypstEngine) -> Compilation {
+        let (prefix, inputs) = match &self.settings {
+            Some(settings) => (settings.prefix(), settings.inputs()),
+            None => (String::new(), Dict::new()),
+        };
+        let (compiled, source) = engine.compile(&format!("{}{}", prefix, self.source), inputs);
+        let mut missing_glyphs = vec![];
+        uncovered_character_warnings(&LinkedNode::new(source.root()), engine.fonts(), &source, &mut missing_glyphs);
+        let mut warnings = compiled.warnings.iter()
+            .map(|warning| TypstDiagnostic::from_source_diagnostic(warning, &source))
+            .collect::<Vec<TypstDiagnostic>>();
+        let mut doc = compiled.output.map_err(|error| TypstDiagnostic::from_error(error, &source));
+        if let Ok(doc) = &doc {
+            for page in &doc.pages {
+                missing_glyph_warnings(&page.frame, &source, &mut missing_glyphs);
+            }
+        }
+        warnings.extend(missing_glyphs);
+        if !prefix.is_empty() {
+            let own_source = Source::detached(self.source());
+            warnings = warnings.into_iter()
+                .map(|diagnostic| diagnostic.relocated(prefix.len(), &own_source))
+                .collect();
+            doc = doc.map_err(|errors| errors.into_iter()
+                .map(|diagnostic| diagnostic.relocated(prefix.len(), &own_source))
+                .collect());
+        }
+        (doc, warnings, source, prefix.len())
+    }
+    /// Compiles the Typst object with a TypstEngine, failing with its formatted diagnostics if it has errors.
+    fn document(&self, engine: &TypstEngine) -> Result<(PagedDocument, Source, usize), JsError> {
+        let (doc, warnings, source, prefix_length) = self.compile(engine);
+        let doc = doc.map_err(|mut errors| {
+            errors.extend(warnings);
+            JsError::new(&format!("Failed to render Typst object.\n{}", format_diagnostics(&errors)))
+        })?;
+        Ok((doc, source, prefix_length))
+    }
+    /// Renders the Typst object to a VectorObject with a TypstEngine, reusing the object cached by the engine for the same source and settings.
+    fn build(&self, engine: &TypstEngine) -> Result<VectorObject, JsError> {
+        let key = match &self.settings {
+            Some(settings) => settings.cache_key(&self.source),
+            None => self.source.to_string(),
+        };
+        if let Some(object) = engine.cached(&key) {
+            return Ok(object);
+        }
+        let (doc, source, prefix_length) = self.document(engine)?;
+        let scale = TransformationMatrix::scale(PT_TO_PX, PT_TO_PX);
+        let object = if self.settings.as_ref().is_none_or(|settings| settings.merge_pages()) {
+            let items = collect_page_items(&doc.pages, scale, true, &source, prefix_length);
+            build_from_svg(svg_merged(&doc, Abs::zero()), &items, engine.font_faces())
+        } else {
+            let pages = doc.pages.iter().map(|page| {
+                let items = collect_page_items(std::slice::from_ref(page), scale, false, &source, prefix_length);
+                VectorObjectBuilder::new(&build_from_svg(svg(page), &items, engine.font_faces()))
+            }).collect();
+            VectorObjectBuilder::default().set_children(pages).build()
+        };
+        engine.cache(&key, &object);
+        Ok(object)
+    }
 }
 
 #[wasm_bindgen]
 impl Typst {
-    /// Creates a new Typst object from a source string.
+    /// Creates a new Typst object from a source string, which is compiled as it is.
     #[wasm_bindgen(constructor, return_description = "A new Typst object.")]
     pub fn new(
         #[wasm_bindgen(param_description = "The source of the Typst object.")]
         source: String,
     ) -> Typst {
-        Typst { source: Rc::new(source) }
+        Typst { source: Rc::new(source), settings: None }
+    }
+    /// Creates a new Typst object from a source string compiled with settings and inputs.
+    #[wasm_bindgen(return_description = "A new Typst object.")]
+    pub fn new_with_settings(
+        #[wasm_bindgen(param_description = "The source of the Typst object.")]
+        source: String,
+        #[wasm_bindgen(param_description = "The settings and inputs to compile the source with.")]
+        settings: TypstSettings,
+    ) -> Typst {
+        Typst { source: Rc::new(source), settings: Some(Rc::new(settings)) }
     }
     /// Returns the source of the Typst object.
     #[wasm_bindgen(getter, return_description = "The source of the Typst object.")]
     pub fn source(&self) -> String {
         self.source.to_string()
     }
-    /// Renders the Typst object to an SVG string.
+    /// Returns the settings and inputs the source is compiled with, if any.
+    #[wasm_bindgen(getter, return_description = "The settings of the Typst object.")]
+    pub fn settings(&self) -> Option<TypstSettings> {
+        self.settings.as_deref().cloned()
+    }
+    /// Returns a copy of the Typst object compiled with other settings and inputs, such as the values of a template.
+    #[wasm_bindgen(return_description = "The Typst object with the settings.")]
+    pub fn with_settings(
+        &self,
+        #[wasm_bindgen(param_description = "The settings and inputs to compile the source with.")]
+        settings: TypstSettings,
+    ) -> Typst {
+        Typst { source: Rc::clone(&self.source), settings: Some(Rc::new(settings)) }
+    }
+    /// Compiles the Typst object and returns its errors and warnings, including fonts and glyphs that could not be found.
+    #[wasm_bindgen(return_description = "The diagnostics of the Typst object, errors first.")]
+    pub fn diagnostics(
+        &self,
+        #[wasm_bindgen(param_description = "The font faces to use when compiling the Typst object, default is FontFace.defaults().")]
+        font_faces: Option<Vec<FontFace>>
+    ) -> Result<Vec<TypstDiagnostic>, JsError> {
+        Ok(self.diagnostics_with_engine(&TypstEngine::for_font_faces(font_faces)?))
+    }
+    /// Compiles the Typst object with a TypstEngine and returns its errors and warnings, including fonts and glyphs that could not be found.
+    #[wasm_bindgen(return_description = "The diagnostics of the Typst object, errors first.")]
+    pub fn diagnostics_with_engine(
+        &self,
+        #[wasm_bindgen(param_description = "The engine to compile the Typst object with.")]
+        engine: &TypstEngine
+    ) -> Vec<TypstDiagnostic> {
+        let (doc, warnings, _, _) = self.compile(engine);
+        let mut diagnostics = doc.err().unwrap_or_default();
+        diagnostics.extend(warnings);
+        diagnostics
+    }
+    /// Renders the Typst object to an SVG string. If it fails to compile, the error message lists its diagnostics with their positions in the source.
     #[wasm_bindgen(return_description = "The SVG string.")]
     pub fn to_svg(
         &self,
-        #[wasm_bindgen(param_description = "The font faces to use when rendering the Typst object.")]
+        #[wasm_bindgen(param_description = "The font faces to use when rendering the Typst object, default is FontFace.defaults().")]
         font_faces: Option<Vec<FontFace>>
     ) -> Result<String, JsError> {
-        let fonts = font_faces.unwrap_or(Vec::new())
-            .iter()
-            .map(|font_face| font_face.data())
-            .map(|font_data| Font::new(Bytes::new(font_data), 0))
-            .collect::<Vec<Option<Font>>>();
-        if fonts.iter().any(|font| font.is_none()) {
-            return Err(JsError::new("Failed to load font."));
-        }
-        let fonts = fonts.into_iter().map(|font| font.unwrap()).collect::<Vec<Font>>();
-        let template = TypstTemplate::new(self.source()).add_fonts(fonts);
-        let doc = template
-            .compile()
-            .output
-            .map_err(|_| JsError::new("Failed to render Typst object."))?;
-        let padding = Abs::zero();
-        let svg = svg_merged(&doc, padding);
-        Ok(svg)
-    }
-    /// Renders the Typst object to a VectorObjectBuilder.
+        self.to_svg_with_engine(&TypstEngine::for_font_faces(font_faces)?)
+    }
+    /// Renders the Typst object to an SVG string with a TypstEngine. If it fails to compile, the error message lists its diagnostics with their positions in the source.
+    #[wasm_bindgen(return_description = "The SVG string.")]
+    pub fn to_svg_with_engine(
+        &self,
+        #[wasm_bindgen(param_description = "The engine to render the Typst object with.")]
+        engine: &TypstEngine
+    ) -> Result<String, JsError> {
+        let (doc, _, _) = self.document(engine)?;
+        Ok(svg_merged(&doc, Abs::zero()))
+    }
+    /// Renders each page of the Typst object to its own SVG string. If it fails to compile, the error message lists its diagnostics with their positions in the source.
+    #[wasm_bindgen(return_description = "The SVG strings of the pages.")]
+    pub fn to_svg_pages(
+        &self,
+        #[wasm_bindgen(param_description = "The font faces to use when rendering the Typst object, default is FontFace.defaults().")]
+        font_faces: Option<Vec<FontFace>>
+    ) -> Result<Vec<String>, JsError> {
+        self.to_svg_pages_with_engine(&TypstEngine::for_font_faces(font_faces)?)
+    }
+    /// Renders each page of the Typst object to its own SVG string with a TypstEngine. If it fails to compile, the error message lists its diagnostics with their positions in the source.
+    #[wasm_bindgen(return_description = "The SVG strings of the pages.")]
+    pub fn to_svg_pages_with_engine(
+        &self,
+        #[wasm_bindgen(param_description = "The engine to render the Typst object with.")]
+        engine: &TypstEngine
+    ) -> Result<Vec<String>, JsError> {
+        let (doc, _, _) = self.document(engine)?;
+        Ok(doc.pages.iter().map(svg).collect())
+    }
+    /// Renders the Typst object to a VectorObjectBuilder whose children are its glyphs and shapes in rendering order, or one child per page holding them if its settings don't merge the pages. Each glyph and shape is named after its range in the source, such as `span_4_5`, followed by an index when several share the range, such as `span_4_5_1`, so it can be found with a Selector or with the indices_of method.
     #[wasm_bindgen(return_description = "A VectorObjectBuilder representing the typesetting object.")]
     pub fn vector_object_builder(
         &self,
-        #[wasm_bindgen(param_description = "The FontFaces to use when rendering the Typst object, if any.")]
+        #[wasm_bindgen(param_description = "The FontFaces to use when rendering the Typst object, default is FontFace.defaults().")]
         font_faces: Option<Vec<FontFace>>,
     ) -> Result<VectorObjectBuilder, JsError> {
-        let svg = self.to_svg(font_faces.clone())?;
-        let builder = VectorObjectBuilder::from_svg(svg, font_faces, None);
-        Ok(builder)
+        self.vector_object_builder_with_engine(&TypstEngine::for_font_faces(font_faces)?)
+    }
+    /// Renders the Typst object to a VectorObjectBuilder with a TypstEngine, like vector_object_builder. Sources rendered recently by the same engine are not rendered again.
+    #[wasm_bindgen(return_description = "A VectorObjectBuilder representing the typesetting object.")]
+    pub fn vector_object_builder_with_engine(
+        &self,
+        #[wasm_bindgen(param_description = "The engine to render the Typst object with.")]
+        engine: &TypstEngine,
+    ) -> Result<VectorObjectBuilder, JsError> {
+        Ok(VectorObjectBuilder::new(&self.build(engine)?))
+    }
+    /// Gets the range in the source of a child of a rendered Typst object, from its name.
+    #[wasm_bindgen(return_description = "The span of the child in the source, if it has one.")]
+    pub fn source_span(
+        #[wasm_bindgen(param_description = "The child of a rendered Typst object.")]
+        child: &VectorObject,
+    ) -> Option<SourceSpan> {
+        child.name()
+            .and_then(|name| span_range(&name))
+            .map(|(start, end)| SourceSpan::new(start, end))
+    }
+    /// Gets the ranges in the source matched by a query, which is either a label such as `<name>`, matching the labelled element, or a substring, matching each of its occurrences.
+    #[wasm_bindgen(return_description = "The spans in the source matched by the query.")]
+    pub fn spans_of(
+        &self,
+        #[wasm_bindgen(param_description = "A label such as `<name>` or a substring of the source.")]
+        query: &str,
+    ) -> Vec<SourceSpan> {
+        let mut ranges = vec![];
+        match query.strip_prefix('<').and_then(|query| query.strip_suffix('>')) {
+            Some(name) => {
+                let source = Source::detached(self.source());
+                label_ranges(&LinkedNode::new(source.root()), name, &mut ranges);
+            }
+            None if !query.is_empty() => {
+                ranges.extend(self.source.match_indices(query).map(|(start, matched)| (start, start + matched.len())));
+            }
+            None => {}
+        }
+        ranges.into_iter().map(|(start, end)| SourceSpan::new(start, end)).collect()
+    }
+    /// Gets the indices of the children of a rendered Typst object that lie within the ranges of the source matched by a query. If the settings don't merge the pages, the children of the pages are searched and numbered in order across the pages, as if they were merged.
+    #[wasm_bindgen(return_description = "The indices of the matching children.")]
+    pub fn indices_of(
+        &self,
+        #[wasm_bindgen(param_description = "The vector object rendered from this Typst object.")]
+        object: &VectorObject,
+        #[wasm_bindgen(param_description = "A label such as `<name>` or a substring of the source.")]
+        query: &str,
+    ) -> Vec<usize> {
+        let spans = self.spans_of(query);
+        self.merged_pages(object).children()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, child)| Typst::source_span(child).map(|span| (index, span)))
+            .filter(|(_, span)| spans.iter().any(|other| span.is_within(other)))
+            .map(|(index, _)| index)
+            .collect()
     }
 }
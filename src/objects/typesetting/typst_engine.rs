@@ -0,0 +1,287 @@
+use std::{cell::{OnceCell, RefCell}, collections::{BTreeMap, HashMap}, hash::Hash, rc::Rc};
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+#[cfg(not(target_arch = "wasm32"))]
+use chrono::Local;
+use typst::{comemo, diag::{FileError, FileResult, Warned}, foundations::{Bytes, Datetime, Dict}, layout::PagedDocument, syntax::{FileId, Source, VirtualPath}, text::{Font, FontBook}, utils::{hash128, LazyHash}, Library, World};
+use typst_as_lib::TypstAsLibError;
+use wasm_bindgen::prelude::*;
+
+use crate::{objects::vector_object::VectorObject, utils::font_face::FontFace};
+
+/// The number of compilations after which the unused results memoized by Typst are evicted.
+const MEMOIZATION_MAX_AGE: usize = 16;
+
+/// The default number of rendered sources kept by a TypstEngine.
+const DEFAULT_CACHE_CAPACITY: usize = 256;
+
+/// The number of standard libraries, one for each set of inputs, kept by a TypstEngine.
+const LIBRARY_CACHE_CAPACITY: usize = 16;
+
+thread_local! {
+    /// The TypstEngine used when no font faces are given, created the first time it's needed.
+    static DEFAULT_ENGINE: OnceCell<TypstEngine> = const { OnceCell::new() };
+}
+
+/// The world a TypstEngine compiles its main source in, which has no other files.
+struct EngineWorld<'a> {
+    /// The standard library with the inputs of the compilation.
+    library: &'a LazyHash<Library>,
+    /// The metadata of the fonts.
+    book: &'a LazyHash<FontBook>,
+    /// The fonts available to the source.
+    fonts: &'a [Font],
+    /// The main source.
+    main_source: Source,
+    /// The time of the compilation.
+    now: DateTime<Utc>,
+}
+
+impl World for EngineWorld<'_> {
+    fn library(&self) -> &LazyHash<Library> {
+        self.library
+    }
+    fn book(&self) -> &LazyHash<FontBook> {
+        self.book
+    }
+    fn main(&self) -> FileId {
+        self.main_source.id()
+    }
+    fn source(&self, id: FileId) -> FileResult<Source> {
+        if self.main_source.id() == id {
+            Ok(self.main_source.clone())
+        } else {
+            Err(FileError::NotFound(id.vpath().as_rootless_path().into()))
+        }
+    }
+    fn file(&self, id: FileId) -> FileResult<Bytes> {
+        Err(FileError::NotFound(id.vpath().as_rootless_path().into()))
+    }
+    fn font(&self, index: usize) -> Option<Font> {
+        self.fonts.get(index).cloned()
+    }
+    fn today(&self, offset: Option<i64>) -> Option<Datetime> {
+        let date = match offset {
+            Some(offset) => (self.now + Duration::hours(offset)).date_naive(),
+            None => local_date(self.now),
+        };
+        Datetime::from_ymd(date.year(), date.month() as u8, date.day() as u8)
+    }
+}
+
+/// Gets the date in the local time zone at a time, which is the time zone of the browser in WebAssembly.
+#[cfg(target_arch = "wasm32")]
+fn local_date(now: DateTime<Utc>) -> NaiveDate {
+    // JavaScript gives the minutes from local time to UTC, the opposite of an offset from UTC.
+    let minutes = js_sys::Date::new(&JsValue::from_f64(now.timestamp_millis() as f64)).get_timezone_offset();
+    (now - Duration::minutes(minutes as i64)).date_naive()
+}
+
+/// Gets the date in the local time zone at a time.
+#[cfg(not(target_arch = "wasm32"))]
+fn local_date(now: DateTime<Utc>) -> NaiveDate {
+    now.with_timezone(&Local).date_naive()
+}
+
+/// A least recently used cache, backed by a map from the keys to the values and a list of the keys ordered by their last use.
+#[derive(Debug)]
+struct LruCache<K, V> {
+    /// The maximum number of entries.
+    capacity: usize,
+    /// The values and the times they were last used.
+    entries: HashMap<K, (V, u64)>,
+    /// The keys by the time they were last used, from the least to the most recently used.
+    recency: BTreeMap<u64, K>,
+    /// The time of the last use of an entry, which increases with every use.
+    clock: u64,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> LruCache<K, V> {
+    /// Creates an empty cache.
+    fn new(capacity: usize) -> LruCache<K, V> {
+        LruCache { capacity, entries: HashMap::new(), recency: BTreeMap::new(), clock: 0 }
+    }
+    /// Gets the value of a key, marking it as the most recently used.
+    fn get<Q: Eq + Hash + ?Sized>(&mut self, key: &Q) -> Option<V> where K: std::borrow::Borrow<Q> {
+        self.clock += 1;
+        let (value, time) = self.entries.get_mut(key)?;
+        let key = self.recency.remove(time)?;
+        *time = self.clock;
+        let value = value.clone();
+        self.recency.insert(self.clock, key);
+        Some(value)
+    }
+    /// Inserts the value of a key, evicting the least recently used entries beyond the capacity.
+    fn insert(&mut self, key: K, value: V) {
+        self.clock += 1;
+        if let Some((_, time)) = self.entries.insert(key.clone(), (value, self.clock)) {
+            self.recency.remove(&time);
+        }
+        self.recency.insert(self.clock, key);
+        self.evict();
+    }
+    /// Evicts the least recently used entries beyond the capacity.
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            let Some((_, key)) = self.recency.pop_first() else { break };
+            self.entries.remove(&key);
+        }
+    }
+    /// Returns the number of entries.
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+    /// Removes all the entries.
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+}
+
+/// A TypstEngine compiles Typst objects with a set of fonts that are loaded once. The results of the compilations are memoized across them, so similar sources are recompiled incrementally, and the objects rendered from the most recently used sources are cached.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct TypstEngine {
+    /// The fonts available to the Typst objects.
+    fonts: Rc<Vec<Font>>,
+    /// The font faces the fonts were loaded from.
+    font_faces: Rc<Vec<FontFace>>,
+    /// The metadata of the fonts.
+    book: Rc<LazyHash<FontBook>>,
+    /// The standard libraries by the hash of the inputs they were built with, reused so that Typst's memoized results stay valid across compilations with the same inputs.
+    libraries: Rc<RefCell<LruCache<u128, Rc<LazyHash<Library>>>>>,
+    /// The main source, replaced before each compilation so that Typst can reuse the previous one.
+    main_source: Rc<RefCell<Source>>,
+    /// The cache of rendered sources.
+    cache: Rc<RefCell<LruCache<String, VectorObject>>>,
+}
+
+#[wasm_bindgen]
+impl TypstEngine {
+    /// Creates a new TypstEngine by loading FontFaces.
+    #[wasm_bindgen(constructor, return_description = "A new Typst engine.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The FontFaces available to the Typst objects, default is FontFace.defaults().")]
+        font_faces: Option<Vec<FontFace>>,
+        #[wasm_bindgen(param_description = "The maximum number of rendered sources to keep, default is 256.")]
+        cache_capacity: Option<usize>,
+    ) -> Result<TypstEngine, JsError> {
+        let font_faces = font_faces.unwrap_or_else(FontFace::defaults);
+        let fonts = font_faces.iter()
+            .map(|font_face| Font::new(Bytes::new(font_face.shared_data()), 0))
+            .collect::<Option<Vec<Font>>>()
+            .ok_or_else(|| JsError::new("Failed to load font."))?;
+        let main_source = Source::new(FileId::new(None, VirtualPath::new("/main.typ")), String::new());
+        let cache = LruCache::new(cache_capacity.unwrap_or(DEFAULT_CACHE_CAPACITY));
+        Ok(TypstEngine {
+            book: Rc::new(LazyHash::new(FontBook::from_fonts(&fonts))),
+            fonts: Rc::new(fonts),
+            font_faces: Rc::new(font_faces),
+            libraries: Rc::new(RefCell::new(LruCache::new(LIBRARY_CACHE_CAPACITY))),
+            main_source: Rc::new(RefCell::new(main_source)),
+            cache: Rc::new(RefCell::new(cache)),
+        })
+    }
+    /// Clones the Typst engine, sharing its fonts and caches.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn copy(&self) -> TypstEngine {
+        self.clone()
+    }
+    /// Gets the FontFaces available to the Typst objects.
+    #[wasm_bindgen(getter, return_description = "The font faces of the engine.")]
+    pub fn font_faces(&self) -> Vec<FontFace> {
+        self.font_faces.to_vec()
+    }
+    /// Gets the maximum number of rendered sources kept by the engine.
+    #[wasm_bindgen(getter, return_description = "The capacity of the cache.")]
+    pub fn cache_capacity(&self) -> usize {
+        self.cache.borrow().capacity
+    }
+    /// Sets the maximum number of rendered sources kept by the engine, evicting the least recently used ones beyond it.
+    #[wasm_bindgen(setter)]
+    pub fn set_cache_capacity(
+        &mut self,
+        #[wasm_bindgen(param_description = "The capacity of the cache.")]
+        capacity: usize
+    ) {
+        let mut cache = self.cache.borrow_mut();
+        cache.capacity = capacity;
+        cache.evict();
+    }
+    /// Gets the number of rendered sources kept by the engine.
+    #[wasm_bindgen(getter, return_description = "The number of cached sources.")]
+    pub fn cache_size(&self) -> usize {
+        self.cache.borrow().len()
+    }
+    /// Removes all the rendered sources kept by the engine.
+    pub fn clear_cache(&self) {
+        self.cache.borrow_mut().clear();
+    }
+}
+
+#[cfg(all(feature = "system-fonts", not(target_arch = "wasm32")))]
+impl TypstEngine {
+    /// Creates a new TypstEngine by loading FontFaces followed by the fonts installed on the system.
+    pub fn with_system_fonts(font_faces: Option<Vec<FontFace>>, cache_capacity: Option<usize>) -> Result<TypstEngine, JsError> {
+        let mut font_faces = font_faces.unwrap_or_else(FontFace::defaults);
+        font_faces.extend(FontFace::system());
+        TypstEngine::new(Some(font_faces), cache_capacity)
+    }
+}
+
+impl TypstEngine {
+    /// Gets the engine to compile with when no engine is given. Without font faces, it's the default engine of the thread, which keeps its fonts, libraries and rendered sources between calls. With font faces, it's a new engine that doesn't keep rendered sources.
+    pub fn for_font_faces(font_faces: Option<Vec<FontFace>>) -> Result<TypstEngine, JsError> {
+        if font_faces.is_some() {
+            return TypstEngine::new(font_faces, Some(0));
+        }
+        DEFAULT_ENGINE.with(|default_engine| {
+            if let Some(engine) = default_engine.get() {
+                return Ok(engine.clone());
+            }
+            let engine = TypstEngine::new(None, None)?;
+            Ok(default_engine.get_or_init(|| engine).clone())
+        })
+    }
+    /// Gets the fonts available to the Typst objects.
+    pub fn fonts(&self) -> &[Font] {
+        &self.fonts
+    }
+    /// Compiles a source as the main source of the engine with inputs available as `sys.inputs`, returning the result and the Source its spans refer to.
+    pub fn compile(&self, text: &str, inputs: Dict) -> (Warned<Result<PagedDocument, TypstAsLibError>>, Source) {
+        let source = {
+            let mut main_source = self.main_source.borrow_mut();
+            main_source.replace(text);
+            main_source.clone()
+        };
+        let library = self.library(inputs);
+        let world = EngineWorld {
+            library: &library,
+            book: &self.book,
+            fonts: &self.fonts,
+            main_source: source.clone(),
+            now: Utc::now(),
+        };
+        let Warned { output, warnings } = typst::compile(&world);
+        comemo::evict(MEMOIZATION_MAX_AGE);
+        (Warned { output: output.map_err(TypstAsLibError::from), warnings }, source)
+    }
+    /// Gets the standard library with inputs available as `sys.inputs`, building it only the first time the inputs are used.
+    fn library(&self, inputs: Dict) -> Rc<LazyHash<Library>> {
+        let key = hash128(&inputs);
+        let mut libraries = self.libraries.borrow_mut();
+        libraries.get(&key).unwrap_or_else(|| {
+            let library = Rc::new(LazyHash::new(Library::builder().with_inputs(inputs).build()));
+            libraries.insert(key, Rc::clone(&library));
+            library
+        })
+    }
+    /// Gets the object rendered from a source, if it is cached.
+    pub fn cached(&self, source: &str) -> Option<VectorObject> {
+        self.cache.borrow_mut().get(source)
+    }
+    /// Caches the object rendered from a source.
+    pub fn cache(&self, source: &str, object: &VectorObject) {
+        self.cache.borrow_mut().insert(source.to_string(), object.clone());
+    }
+}
@@ -0,0 +1,204 @@
+use typst::foundations::{Dict, IntoValue, Str};
+use wasm_bindgen::prelude::*;
+
+use crate::utils::style::Color;
+
+/// Escapes a string so it can be written as a Typst string literal.
+fn string_literal(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Formats an optional length in points as a Typst length, where no length means `auto`.
+fn length_or_auto(length: Option<f32>) -> String {
+    length.map_or("auto".to_string(), |length| format!("{}pt", length))
+}
+
+/// TypstSettings are the document settings and inputs a Typst object is compiled with. The settings are applied as `#set` rules before the source, and the inputs are available to it as `sys.inputs`.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct TypstSettings {
+    /// The width of the page in points, or none to fit the content.
+    page_width: Option<f32>,
+    /// The height of the page in points, or none to fit the content.
+    page_height: Option<f32>,
+    /// The margin around the content of the page in points.
+    margin: f32,
+    /// The size of the text in points, or none for Typst's default.
+    text_size: Option<f32>,
+    /// The color of the text, or none for Typst's default.
+    text_color: Option<Color>,
+    /// The font family of the equations, or none for Typst's default.
+    math_font: Option<String>,
+    /// The Typst code placed after the settings and before the source, such as `#let` definitions and `#show` rules.
+    preamble: String,
+    /// The string inputs available to the source as `sys.inputs`, in insertion order.
+    inputs: Vec<(String, String)>,
+    /// Whether the pages are rendered one below the other into a single object, or into one child per page.
+    merge_pages: bool,
+}
+
+#[wasm_bindgen]
+impl TypstSettings {
+    /// Creates new TypstSettings. By default, pages fit their content without margins or background, the text keeps Typst's defaults and the pages are merged.
+    #[wasm_bindgen(constructor, return_description = "New Typst settings.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The width of the page in points, default is to fit the content.")]
+        page_width: Option<f32>,
+        #[wasm_bindgen(param_description = "The height of the page in points, default is to fit the content.")]
+        page_height: Option<f32>,
+        #[wasm_bindgen(param_description = "The margin around the content of the page in points, default is 0.")]
+        margin: Option<f32>,
+        #[wasm_bindgen(param_description = "The size of the text in points, default is Typst's default of 11.")]
+        text_size: Option<f32>,
+        #[wasm_bindgen(param_description = "The color of the text, default is Typst's default of black.")]
+        text_color: Option<Color>,
+        #[wasm_bindgen(param_description = "The font family of the equations, default is Typst's default.")]
+        math_font: Option<String>,
+        #[wasm_bindgen(param_description = "The Typst code placed after the settings and before the source, default is empty.")]
+        preamble: Option<String>,
+    ) -> TypstSettings {
+        TypstSettings {
+            page_width,
+            page_height,
+            margin: margin.unwrap_or(0.0),
+            text_size,
+            text_color,
+            math_font,
+            preamble: preamble.unwrap_or_default(),
+            inputs: Vec::new(),
+            merge_pages: true,
+        }
+    }
+    /// Clones the Typst settings.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn copy(&self) -> TypstSettings {
+        self.clone()
+    }
+    /// Gets the width of the page in points, if it doesn't fit the content.
+    #[wasm_bindgen(getter, return_description = "The width of the page.")]
+    pub fn page_width(&self) -> Option<f32> {
+        self.page_width
+    }
+    /// Gets the height of the page in points, if it doesn't fit the content.
+    #[wasm_bindgen(getter, return_description = "The height of the page.")]
+    pub fn page_height(&self) -> Option<f32> {
+        self.page_height
+    }
+    /// Gets the margin around the content of the page in points.
+    #[wasm_bindgen(getter, return_description = "The margin of the page.")]
+    pub fn margin(&self) -> f32 {
+        self.margin
+    }
+    /// Gets the size of the text in points, if it is set.
+    #[wasm_bindgen(getter, return_description = "The size of the text.")]
+    pub fn text_size(&self) -> Option<f32> {
+        self.text_size
+    }
+    /// Gets the color of the text, if it is set.
+    #[wasm_bindgen(getter, return_description = "The color of the text.")]
+    pub fn text_color(&self) -> Option<Color> {
+        self.text_color
+    }
+    /// Gets the font family of the equations, if it is set.
+    #[wasm_bindgen(getter, return_description = "The font family of the equations.")]
+    pub fn math_font(&self) -> Option<String> {
+        self.math_font.clone()
+    }
+    /// Gets the Typst code placed after the settings and before the source.
+    #[wasm_bindgen(getter, return_description = "The preamble.")]
+    pub fn preamble(&self) -> String {
+        self.preamble.clone()
+    }
+    /// Gets whether the pages are rendered one below the other into a single object rather than into one child per page.
+    #[wasm_bindgen(getter, return_description = "Whether the pages are merged.")]
+    pub fn merge_pages(&self) -> bool {
+        self.merge_pages
+    }
+    /// Gets the names of the inputs available to the source as `sys.inputs`.
+    #[wasm_bindgen(getter, return_description = "The names of the inputs.")]
+    pub fn input_names(&self) -> Vec<String> {
+        self.inputs.iter().map(|(name, _)| name.clone()).collect()
+    }
+    /// Gets the value of an input available to the source as `sys.inputs`.
+    #[wasm_bindgen(return_description = "The value of the input, if it is set.")]
+    pub fn input(
+        &self,
+        #[wasm_bindgen(param_description = "The name of the input.")]
+        name: &str,
+    ) -> Option<String> {
+        self.inputs.iter().find(|(other, _)| other == name).map(|(_, value)| value.clone())
+    }
+    /// Returns a copy of the settings with an input available to the source as `sys.inputs.at(name)`, replacing the input with the same name.
+    #[wasm_bindgen(return_description = "The settings with the input.")]
+    pub fn with_input(
+        &self,
+        #[wasm_bindgen(param_description = "The name of the input.")]
+        name: String,
+        #[wasm_bindgen(param_description = "The value of the input.")]
+        value: String,
+    ) -> TypstSettings {
+        let mut settings = self.clone();
+        match settings.inputs.iter_mut().find(|(other, _)| *other == name) {
+            Some(input) => input.1 = value,
+            None => settings.inputs.push((name, value)),
+        }
+        settings
+    }
+    /// Returns a copy of the settings with another preamble.
+    #[wasm_bindgen(return_description = "The settings with the preamble.")]
+    pub fn with_preamble(
+        &self,
+        #[wasm_bindgen(param_description = "The Typst code placed after the settings and before the source.")]
+        preamble: String,
+    ) -> TypstSettings {
+        TypstSettings { preamble, ..self.clone() }
+    }
+    /// Returns a copy of the settings that renders the pages one below the other into a single object, or into one child per page.
+    #[wasm_bindgen(return_description = "The settings with the way pages are rendered.")]
+    pub fn with_merged_pages(
+        &self,
+        #[wasm_bindgen(param_description = "Whether the pages are rendered into a single object rather than into one child per page.")]
+        merge_pages: bool,
+    ) -> TypstSettings {
+        TypstSettings { merge_pages, ..self.clone() }
+    }
+}
+
+impl TypstSettings {
+    /// Gets the Typst code placed before the source, with the `#set` rules of the settings followed by the preamble. It ends with a new line, so the source starts on a line of its own.
+    pub fn prefix(&self) -> String {
+        let mut prefix = format!(
+            "#set page(width: {}, height: {}, margin: {}pt, fill: none)\n",
+            length_or_auto(self.page_width),
+            length_or_auto(self.page_height),
+            self.margin,
+        );
+        let mut text_settings = vec![];
+        if let Some(size) = self.text_size {
+            text_settings.push(format!("size: {}pt", size));
+        }
+        if let Some(color) = self.text_color {
+            text_settings.push(format!("fill: rgb({})", string_literal(&color.to_hex())));
+        }
+        if !text_settings.is_empty() {
+            prefix.push_str(&format!("#set text({})\n", text_settings.join(", ")));
+        }
+        if let Some(font) = &self.math_font {
+            prefix.push_str(&format!("#show math.equation: set text(font: {})\n", string_literal(font)));
+        }
+        if !self.preamble.is_empty() {
+            prefix.push_str(&self.preamble);
+            prefix.push('\n');
+        }
+        prefix
+    }
+    /// Gets the inputs as a Typst dictionary of strings.
+    pub fn inputs(&self) -> Dict {
+        self.inputs.iter().map(|(name, value)| (Str::from(name.as_str()), value.as_str().into_value())).collect()
+    }
+    /// Gets a key identifying the result of compiling a source with the settings, including the inputs.
+    pub fn cache_key(&self, source: &str) -> String {
+        let inputs = self.inputs.iter().map(|(name, value)| format!("{}={}", string_literal(name), string_literal(value))).collect::<Vec<String>>();
+        format!("{}\n{}\n{}{}", self.merge_pages, inputs.join(","), self.prefix(), source)
+    }
+}
@@ -3,7 +3,7 @@ use std::{rc::Rc, sync::Arc};
 use base64::{prelude::BASE64_STANDARD, Engine};
 use usvg::{ImageHrefResolver, ImageKind};
 use wasm_bindgen::{prelude::*, throw_str};
-use crate::{objects::geometry::triangle::EquilateralTriangle, utils::{bezier::CubicBezierTuple, bounding_box::BoundingBox, console::log, font_face::FontFace, image_library::ImageLibrary, interpolation::IntegerLerp, linear_algebra::TransformationMatrix, point2d::{Path2D, Point2D}, style::{Color, ImageBitmap, Style}}};
+use crate::{objects::geometry::triangle::EquilateralTriangle, utils::{bezier::CubicBezierTuple, bounding_box::BoundingBox, color_space::ColorSpace, console::log, font_face::FontFace, image_library::ImageLibrary, interpolation::IntegerLerp, linear_algebra::TransformationMatrix, point2d::{Path2D, Point2D}, selector::Selector, style::{Color, ImageBitmap, Style}, tracing::{trace, BinaryBitmap, TraceChannel}}};
 
 use super::geometry::rectangle::Rectangle;
 
@@ -304,6 +304,41 @@ impl VectorOperation for RemoveChildByName {
     }
 }
 
+pub struct RemoveSelection {
+    pub selector: Selector,
+}
+
+impl VectorOperation for RemoveSelection {
+    fn apply(&self, object: &mut VectorObject) {
+        let mut paths = object.select_paths(&self.selector);
+        // Removing in reverse lexicographic order removes descendants before their ancestors and later siblings before earlier ones, so the remaining paths stay valid.
+        paths.sort_by(|a, b| b.cmp(a));
+        for path in paths {
+            let (index, parent_path) = path.split_last().unwrap();
+            if let Some(parent) = object.descendant_mut(parent_path) {
+                if *index < parent.children.len() {
+                    parent.children.remove(*index);
+                }
+            }
+        }
+    }
+}
+
+pub struct ApplyToSelection {
+    pub selector: Selector,
+    pub operation: &'static dyn VectorOperation,
+}
+
+impl VectorOperation for ApplyToSelection {
+    fn apply(&self, object: &mut VectorObject) {
+        for path in object.select_paths(&self.selector) {
+            if let Some(descendant) = object.descendant_mut(&path) {
+                self.operation.apply(descendant);
+            }
+        }
+    }
+}
+
 pub struct MatchStyleProperties {
     pub vector_object_builder: VectorObjectBuilder,
 }
@@ -1000,6 +1035,240 @@ impl VectorOperation for ArrangeChildren {
     }
 }
 
+pub struct ArrangeInGrid {
+    pub rows: Option<usize>,
+    pub columns: Option<usize>,
+    pub row_spacing: Option<f32>,
+    pub column_spacing: Option<f32>,
+    pub cell_alignment: Option<Point2D>,
+    pub center: Option<Point2D>,
+    pub recursive: Option<bool>,
+}
+
+impl VectorOperation for ArrangeInGrid {
+    fn apply(&self, object: &mut VectorObject) {
+        let count = object.children.len();
+        if count == 0 {
+            return;
+        }
+        let (rows, columns) = match (self.rows, self.columns) {
+            (Some(rows), Some(columns)) => (rows.max(count.div_ceil(columns.max(1))), columns.max(1)),
+            (Some(rows), None) => (rows.max(1), count.div_ceil(rows.max(1))),
+            (None, Some(columns)) => (count.div_ceil(columns.max(1)), columns.max(1)),
+            (None, None) => {
+                let columns = (count as f32).sqrt().ceil() as usize;
+                (count.div_ceil(columns), columns)
+            }
+        };
+        let row_spacing = self.row_spacing.unwrap_or(0.0);
+        let column_spacing = self.column_spacing.unwrap_or(0.0);
+        let cell_alignment = self.cell_alignment.unwrap_or(Point2D::new(0.0, 0.0));
+        let bounding_boxes = object.children.iter().map(|child| child.bounding_box(self.recursive)).collect::<Vec<Option<BoundingBox>>>();
+        let origin = bounding_boxes.iter().cloned().fold(None, BoundingBox::union);
+        if origin.is_none() {
+            return;
+        }
+        let origin = origin.unwrap().get_critical_point(-1.0, -1.0);
+        let mut widths = vec![0.0_f32; columns];
+        let mut heights = vec![0.0_f32; rows];
+        for (i, bounding_box) in bounding_boxes.iter().enumerate() {
+            if let Some(bounding_box) = bounding_box {
+                widths[i % columns] = widths[i % columns].max(bounding_box.width());
+                heights[i / columns] = heights[i / columns].max(bounding_box.height());
+            }
+        }
+        for (i, bounding_box) in bounding_boxes.iter().enumerate() {
+            if bounding_box.is_none() {
+                continue;
+            }
+            let (row, column) = (i / columns, i % columns);
+            let cell = BoundingBox::new(
+                origin.x + widths[..column].iter().sum::<f32>() + column as f32 * column_spacing,
+                origin.y + heights[..row].iter().sum::<f32>() + row as f32 * row_spacing,
+                widths[column],
+                heights[row],
+            ).unwrap();
+            let target = cell.get_critical_point(cell_alignment.x, cell_alignment.y);
+            let point_to_align = bounding_box.as_ref().unwrap().get_critical_point(cell_alignment.x, cell_alignment.y);
+            let shift = target - point_to_align;
+            object.children[i].apply_operation(&Shift {
+                dx: shift.x,
+                dy: shift.y,
+                recursive: self.recursive,
+            });
+        }
+        if let Some(center) = self.center {
+            let move_to = MoveTo {
+                point: center,
+                recursive: self.recursive,
+            };
+            move_to.apply(object);
+        }
+    }
+}
+
+pub struct AlignChildren {
+    pub edge: Point2D,
+    pub align_x: Option<bool>,
+    pub align_y: Option<bool>,
+    pub target: Option<Point2D>,
+    pub recursive: Option<bool>,
+}
+
+impl VectorOperation for AlignChildren {
+    fn apply(&self, object: &mut VectorObject) {
+        let align_x = self.align_x.unwrap_or(true);
+        let align_y = self.align_y.unwrap_or(true);
+        let target = if let Some(target) = self.target {
+            target
+        } else {
+            let bounding_box = object.children.iter().map(|child| child.bounding_box(self.recursive)).fold(None, BoundingBox::union);
+            if bounding_box.is_none() {
+                return;
+            }
+            bounding_box.unwrap().get_critical_point(self.edge.x, self.edge.y)
+        };
+        for child in object.children.iter_mut() {
+            let point_to_align = child.get_critical_point(self.edge.x, self.edge.y, self.recursive);
+            if point_to_align.is_none() {
+                continue;
+            }
+            let shift = target - point_to_align.unwrap();
+            child.apply_operation(&Shift {
+                dx: if align_x { shift.x } else { 0.0 },
+                dy: if align_y { shift.y } else { 0.0 },
+                recursive: self.recursive,
+            });
+        }
+    }
+}
+
+pub struct DistributeChildren {
+    pub direction: Option<Point2D>,
+    pub by_gaps: Option<bool>,
+    pub spacing: Option<f32>,
+    pub recursive: Option<bool>,
+}
+
+impl VectorOperation for DistributeChildren {
+    fn apply(&self, object: &mut VectorObject) {
+        let direction = self.direction.unwrap_or(Point2D::new(1.0, 0.0)).normalized();
+        let indices = (0..object.children.len())
+            .filter(|&i| object.children[i].bounding_box(self.recursive).is_some())
+            .collect::<Vec<usize>>();
+        if indices.len() < 2 {
+            return;
+        }
+        // Each child is reduced to its center and half extent along the direction.
+        let extents = indices.iter().map(|&i| {
+            let bounding_box = object.children[i].bounding_box(self.recursive).unwrap();
+            let center = bounding_box.center().dot(&direction);
+            let half_extent = (bounding_box.width() * direction.x.abs() + bounding_box.height() * direction.y.abs()) / 2.0;
+            (center, half_extent)
+        }).collect::<Vec<(f32, f32)>>();
+        let (first_center, first_half_extent) = extents[0];
+        let (last_center, last_half_extent) = extents[extents.len() - 1];
+        let steps = (extents.len() - 1) as f32;
+        let mut targets = Vec::with_capacity(extents.len());
+        if self.by_gaps.unwrap_or(false) {
+            let total_extent = extents.iter().map(|(_, half_extent)| 2.0 * half_extent).sum::<f32>();
+            let span = (last_center + last_half_extent) - (first_center - first_half_extent);
+            let gap = self.spacing.unwrap_or((span - total_extent) / steps);
+            let mut position = first_center - first_half_extent;
+            for (_, half_extent) in extents.iter() {
+                targets.push(position + half_extent);
+                position += 2.0 * half_extent + gap;
+            }
+        } else {
+            let step = self.spacing.unwrap_or((last_center - first_center) / steps);
+            for i in 0..extents.len() {
+                targets.push(first_center + i as f32 * step);
+            }
+        }
+        for (k, &i) in indices.iter().enumerate() {
+            let shift = (targets[k] - extents[k].0) * direction;
+            object.children[i].apply_operation(&Shift {
+                dx: shift.x,
+                dy: shift.y,
+                recursive: self.recursive,
+            });
+        }
+    }
+}
+
+pub struct StackByBaseline {
+    pub line_spacing: Option<f32>,
+    pub buff: Option<f32>,
+    pub aligned_edge: Option<f32>,
+    pub recursive: Option<bool>,
+}
+
+impl VectorOperation for StackByBaseline {
+    fn apply(&self, object: &mut VectorObject) {
+        let buff = self.buff.unwrap_or(0.0);
+        let aligned_edge = self.aligned_edge.unwrap_or(0.0);
+        let mut previous: Option<(f32, f32, f32)> = None;
+        for child in object.children.iter_mut() {
+            let bounding_box = child.bounding_box(self.recursive);
+            if bounding_box.is_none() {
+                continue;
+            }
+            let bounding_box = bounding_box.unwrap();
+            let baseline = child.baseline().unwrap();
+            let ascent = baseline - bounding_box.min_y();
+            let descent = bounding_box.min_y() + bounding_box.height() - baseline;
+            let x = bounding_box.get_critical_point(aligned_edge, 0.0).x;
+            match previous {
+                None => previous = Some((baseline, descent, x)),
+                Some((previous_baseline, previous_descent, previous_x)) => {
+                    // Baselines are kept line_spacing apart, unless the children would get closer than buff.
+                    let target_baseline = (previous_baseline + self.line_spacing.unwrap_or(0.0)).max(previous_baseline + previous_descent + buff + ascent);
+                    child.apply_operation(&Shift {
+                        dx: previous_x - x,
+                        dy: target_baseline - baseline,
+                        recursive: self.recursive,
+                    });
+                    previous = Some((target_baseline, descent, previous_x));
+                }
+            }
+        }
+    }
+}
+
+pub struct AlignBaselines {
+    pub baseline: Option<f32>,
+    pub recursive: Option<bool>,
+}
+
+impl VectorOperation for AlignBaselines {
+    fn apply(&self, object: &mut VectorObject) {
+        let target = self.baseline.or_else(|| object.children.iter().find_map(|child| child.baseline()));
+        if target.is_none() {
+            return;
+        }
+        let target = target.unwrap();
+        for child in object.children.iter_mut() {
+            if let Some(baseline) = child.baseline() {
+                child.apply_operation(&Shift {
+                    dx: 0.0,
+                    dy: target - baseline,
+                    recursive: self.recursive,
+                });
+            }
+        }
+    }
+}
+
+pub struct Redraw {
+    pub factory: Rc<dyn Fn() -> VectorObjectBuilder>,
+}
+
+impl VectorOperation for Redraw {
+    fn apply(&self, object: &mut VectorObject) {
+        *object = (self.factory)().build();
+    }
+}
+
 pub struct ReversePath {}
 
 impl VectorOperation for ReversePath {
@@ -1037,12 +1306,13 @@ pub struct LerpFill {
     pub height: Option<f32>,
     pub data_width: Option<usize>,
     pub data_height: Option<usize>,
+    pub color_space: Option<ColorSpace>,
     pub recursive: Option<bool>,
 }
 
 impl VectorOperation for LerpFill {
     fn apply(&self, object: &mut VectorObject) {
-        let fill = Style::lerp(&object.fill, &self.fill, self.t, self.x, self.y, self.width, self.height, self.data_width, self.data_height);
+        let fill = Style::lerp(&object.fill, &self.fill, self.t, self.x, self.y, self.width, self.height, self.data_width, self.data_height, self.color_space);
         if fill.is_err() {
             throw_str(&fill.unwrap_err());
         }
@@ -1058,6 +1328,7 @@ impl VectorOperation for LerpFill {
                     height: self.height,
                     data_width: self.data_width,
                     data_height: self.data_height,
+                    color_space: self.color_space,
                     recursive: Some(true),
                 };
                 lerp_fill.apply(child);
@@ -1075,12 +1346,13 @@ pub struct LerpStroke {
     pub height: Option<f32>,
     pub data_width: Option<usize>,
     pub data_height: Option<usize>,
+    pub color_space: Option<ColorSpace>,
     pub recursive: Option<bool>,
 }
 
 impl VectorOperation for LerpStroke {
     fn apply(&self, object: &mut VectorObject) {
-        let stroke = Style::lerp(&object.stroke, &self.stroke, self.t, self.x, self.y, self.width, self.height, self.data_width, self.data_height);
+        let stroke = Style::lerp(&object.stroke, &self.stroke, self.t, self.x, self.y, self.width, self.height, self.data_width, self.data_height, self.color_space);
         if stroke.is_err() {
             throw_str(&stroke.unwrap_err());
         }
@@ -1096,6 +1368,7 @@ impl VectorOperation for LerpStroke {
                     height: self.height,
                     data_width: self.data_width,
                     data_height: self.data_height,
+                    color_space: self.color_space,
                     recursive: Some(true),
                 };
                 lerp_stroke.apply(child);
@@ -1170,12 +1443,91 @@ impl VectorObjectBuilder {
         ).vector_object_builder().set_fill(Style::from_color(Color::new(0, 0, 0, 1.0)), None)
     }
 
+    /// Creates a new VectorObjectBuilder by tracing an ImageBitmap into closed cubic paths, following potrace. With a single level, the pixels of the channel below the threshold are traced. With several levels, the channel is quantized and every level is traced into a child filled with the average color of its pixels, stacked so that darker levels are drawn over lighter ones.
+    #[wasm_bindgen(return_description = "A new vector object builder with the traced paths.")]
+    pub fn from_bitmap(
+        #[wasm_bindgen(param_description = "The bitmap to trace.")]
+        bitmap: &ImageBitmap,
+        #[wasm_bindgen(param_description = "The intensity from 0 to 1 below which pixels are traced when there's a single level, default is 0.5.")]
+        threshold: Option<f32>,
+        #[wasm_bindgen(param_description = "The channel that is thresholded or quantized, default is the luminance.")]
+        channel: Option<TraceChannel>,
+        #[wasm_bindgen(param_description = "The area in pixels up to which traced regions are discarded as noise, default is 2.")]
+        turd_size: Option<f32>,
+        #[wasm_bindgen(param_description = "How smooth a vertex must be to be traced as a curve instead of a corner, from 0 where every vertex is a corner to 4 / 3 where there are no corners, default is 1.")]
+        corner_threshold: Option<f32>,
+        #[wasm_bindgen(param_description = "The number of levels the channel is quantized into, default is 1.")]
+        levels: Option<usize>
+    ) -> VectorObjectBuilder {
+        let threshold = threshold.unwrap_or(0.5);
+        let channel = channel.unwrap_or_default();
+        let turd_size = turd_size.unwrap_or(2.0);
+        let corner_threshold = corner_threshold.unwrap_or(1.0);
+        let levels = levels.unwrap_or(1).max(1);
+        let rgba_image = bitmap.rgba_image();
+        let (pixels_x, pixels_y) = (rgba_image.width() as usize, rgba_image.height() as usize);
+        if pixels_x == 0 || pixels_y == 0 {
+            return VectorObjectBuilder::default();
+        }
+        let pixel = |x: usize, y: usize| rgba_image.get_pixel(x as u32, y as u32).0;
+        // The level of every visible pixel, where level 0 has the lowest intensity.
+        let level_of = |x: usize, y: usize| {
+            let pixel = pixel(x, y);
+            if !channel.is_visible(pixel) {
+                return None;
+            }
+            let intensity = channel.intensity(pixel);
+            if levels == 1 {
+                return (intensity < threshold).then_some(0);
+            }
+            Some(((intensity * levels as f32) as usize).min(levels - 1))
+        };
+        let pixel_to_scene = TransformationMatrix::new(
+            bitmap.width / pixels_x as f32, 0.0,
+            0.0, bitmap.height / pixels_y as f32,
+            bitmap.x, bitmap.y,
+        );
+        let mut layers = vec![];
+        for level in (0..levels).rev() {
+            let mut sum = [0.0; 3];
+            let mut count = 0;
+            for y in 0..pixels_y {
+                for x in 0..pixels_x {
+                    if level_of(x, y) == Some(level) {
+                        let pixel = pixel(x, y);
+                        for (total, channel) in sum.iter_mut().zip(pixel.iter()) {
+                            *total += *channel as f32;
+                        }
+                        count += 1;
+                    }
+                }
+            }
+            if count == 0 {
+                continue;
+            }
+            let mask = BinaryBitmap::from_fn(pixels_x, pixels_y, |x, y| level_of(x, y).is_some_and(|pixel_level| pixel_level <= level));
+            let path = trace(&mask, turd_size, corner_threshold).transform(&pixel_to_scene);
+            if path.is_empty() {
+                continue;
+            }
+            let [red, green, blue] = sum.map(|total| (total / count as f32).round() as u8);
+            layers.push(VectorObjectBuilder::default()
+                .set_path(path)
+                .set_fill(Style::from_color(Color::new(red, green, blue, 1.0)), None)
+                .set_fill_rule("evenodd".to_string(), None)
+                .set_stroke_width(0.0, None));
+        }
+        if levels == 1 {
+            return layers.pop().unwrap_or_default();
+        }
+        VectorObjectBuilder::default().add_children(layers)
+    }
     /// Creates a new VectorObjectBuilder from an SVG string.
     #[wasm_bindgen(return_description = "A new vector object builder.")]
     pub fn from_svg(
         #[wasm_bindgen(param_description = "The SVG string to create the vector object builder from.")]
         svg: String,
-        #[wasm_bindgen(param_description = "Data from font faces to use for text rendering.")]
+        #[wasm_bindgen(param_description = "Data from font faces to use for text rendering, default is FontFace.defaults().")]
         font_faces: Option<Vec<FontFace>>,
         #[wasm_bindgen(param_description = "Image library to use for image rendering.")]
         image_library: Option<ImageLibrary>,
@@ -1183,8 +1535,8 @@ impl VectorObjectBuilder {
         let mut vector_object_builder = VectorObjectBuilder::default();
         let mut options = usvg::Options::default();
         let mut fontdatabase = options.fontdb.clone();
-        for font_face in font_faces.unwrap_or_default() {
-            Arc::make_mut(&mut fontdatabase).load_font_data(font_face.data());
+        for font_face in font_faces.unwrap_or_else(FontFace::defaults) {
+            Arc::make_mut(&mut fontdatabase).load_font_source(usvg::fontdb::Source::Binary(Arc::new(font_face.shared_data())));
         }
         let image_library = Box::new(image_library.unwrap_or(ImageLibrary::new()));
         let image_library = Box::leak(image_library);
@@ -1430,6 +1782,31 @@ impl VectorObjectBuilder {
         self.ops.add_operation(Box::leak(remove_child_by_name));
         self
     }
+    /// Removes every descendant of the VectorObjectBuilder matched by the given Selector.
+    #[wasm_bindgen(return_description = "The vector object being built with the remove selection operation.")]
+    pub fn remove_selection(
+        mut self,
+        #[wasm_bindgen(param_description = "The selector of the descendants to remove from the vector object.")]
+        selector: &Selector
+    ) -> VectorObjectBuilder {
+        let remove_selection = Box::new(RemoveSelection { selector: selector.clone() });
+        self.ops.add_operation(Box::leak(remove_selection));
+        self
+    }
+    /// Applies the operations of another VectorObjectBuilder to every descendant of the VectorObjectBuilder matched by the given Selector.
+    #[wasm_bindgen(return_description = "The vector object being built with the apply to selection operation.")]
+    pub fn apply_to_selection(
+        mut self,
+        #[wasm_bindgen(param_description = "The selector of the descendants to apply the operations to.")]
+        selector: &Selector,
+        #[wasm_bindgen(param_description = "The vector object builder whose operations are applied to each match. The object it was started from is ignored.")]
+        operations: VectorObjectBuilder
+    ) -> VectorObjectBuilder {
+        let operation = Box::leak(Box::new(operations.ops));
+        let apply_to_selection = Box::new(ApplyToSelection { selector: selector.clone(), operation });
+        self.ops.add_operation(Box::leak(apply_to_selection));
+        self
+    }
     /// Matches the style properties of the VectorObjectBuilder with another VectorObject.
     #[wasm_bindgen(return_description = "The vector object being built with the match style properties operation.")]
     pub fn match_style_properties(
@@ -1836,6 +2213,95 @@ impl VectorObjectBuilder {
         self.ops.add_operation(Box::leak(arrange_subobjects));
         self
     }
+    /// Arranges the children of the VectorObjectBuilder in a grid, where every column is as wide as its widest child and every row is as tall as its tallest child.
+    #[wasm_bindgen(return_description = "The vector object being built with the arrange in grid operation.")]
+    pub fn arrange_in_grid(
+        mut self,
+        #[wasm_bindgen(param_description = "The number of rows of the grid. If not given, it's computed from the number of columns, and if both are missing the grid is as square as possible. Rows are added if there are not enough cells for the children.")]
+        rows: Option<usize>,
+        #[wasm_bindgen(param_description = "The number of columns of the grid. If not given, it's computed from the number of rows.")]
+        columns: Option<usize>,
+        #[wasm_bindgen(param_description = "The space between consecutive rows, default is 0.")]
+        row_spacing: Option<f32>,
+        #[wasm_bindgen(param_description = "The space between consecutive columns, default is 0.")]
+        column_spacing: Option<f32>,
+        #[wasm_bindgen(param_description = "The edge of each cell to align its child with, with the same keys as a critical point. If not given, the children will be centered in their cells.")]
+        cell_alignment: Option<Point2D>,
+        #[wasm_bindgen(param_description = "The center of the grid. If not given, the grid starts at the top left corner of the children.")]
+        center: Option<Point2D>,
+        #[wasm_bindgen(param_description = "Whether to apply the arrange in grid operation to the children of the children, default is true.")]
+        recursive: Option<bool>
+    ) -> VectorObjectBuilder {
+        let arrange_in_grid = Box::new(ArrangeInGrid { rows, columns, row_spacing, column_spacing, cell_alignment, center, recursive });
+        self.ops.add_operation(Box::leak(arrange_in_grid));
+        self
+    }
+    /// Aligns the children of the VectorObjectBuilder to a common edge or center.
+    #[wasm_bindgen(return_description = "The vector object being built with the align children operation.")]
+    pub fn align_children(
+        mut self,
+        #[wasm_bindgen(param_description = "The edge to align, with the same keys as a critical point. A key of zero aligns the centers along that axis, so (-1, 0) aligns the left edges and the vertical centers, and (0, 0) the centers.")]
+        edge: Point2D,
+        #[wasm_bindgen(param_description = "Whether to align the x-coordinates, default is true.")]
+        align_x: Option<bool>,
+        #[wasm_bindgen(param_description = "Whether to align the y-coordinates, default is true.")]
+        align_y: Option<bool>,
+        #[wasm_bindgen(param_description = "The point to align the edges with. If not given, the same edge of all the children together is used.")]
+        target: Option<Point2D>,
+        #[wasm_bindgen(param_description = "Whether to apply the align children operation to the children of the children, default is true.")]
+        recursive: Option<bool>
+    ) -> VectorObjectBuilder {
+        let align_children = Box::new(AlignChildren { edge, align_x, align_y, target, recursive });
+        self.ops.add_operation(Box::leak(align_children));
+        self
+    }
+    /// Distributes the children of the VectorObjectBuilder evenly along a direction, keeping the first and last children in place.
+    #[wasm_bindgen(return_description = "The vector object being built with the distribute children operation.")]
+    pub fn distribute_children(
+        mut self,
+        #[wasm_bindgen(param_description = "The direction to distribute the children along, default is right.")]
+        direction: Option<Point2D>,
+        #[wasm_bindgen(param_description = "Whether to make the gaps between the children equal instead of the distances between their centers, default is false.")]
+        by_gaps: Option<bool>,
+        #[wasm_bindgen(param_description = "The gap or distance between centers to use. If given, the children are placed from the first one and the last one may move.")]
+        spacing: Option<f32>,
+        #[wasm_bindgen(param_description = "Whether to apply the distribute children operation to the children of the children, default is true.")]
+        recursive: Option<bool>
+    ) -> VectorObjectBuilder {
+        let distribute_children = Box::new(DistributeChildren { direction, by_gaps, spacing, recursive });
+        self.ops.add_operation(Box::leak(distribute_children));
+        self
+    }
+    /// Stacks the children of the VectorObjectBuilder downwards by their baselines, such as the lines of a derivation typeset with Typst.
+    #[wasm_bindgen(return_description = "The vector object being built with the stack by baseline operation.")]
+    pub fn stack_by_baseline(
+        mut self,
+        #[wasm_bindgen(param_description = "The distance between consecutive baselines, default is 0, so the children only keep the buff between them.")]
+        line_spacing: Option<f32>,
+        #[wasm_bindgen(param_description = "The minimum space between consecutive children, default is 0.")]
+        buff: Option<f32>,
+        #[wasm_bindgen(param_description = "The horizontal edge to align the children with the first child. If negative, the left edges are aligned. If positive, the right edges are aligned. Default is 0, which aligns the centers.")]
+        aligned_edge: Option<f32>,
+        #[wasm_bindgen(param_description = "Whether to apply the stack by baseline operation to the children of the children, default is true.")]
+        recursive: Option<bool>
+    ) -> VectorObjectBuilder {
+        let stack_by_baseline = Box::new(StackByBaseline { line_spacing, buff, aligned_edge, recursive });
+        self.ops.add_operation(Box::leak(stack_by_baseline));
+        self
+    }
+    /// Moves the children of the VectorObjectBuilder vertically so their baselines are aligned.
+    #[wasm_bindgen(return_description = "The vector object being built with the align baselines operation.")]
+    pub fn align_baselines(
+        mut self,
+        #[wasm_bindgen(param_description = "The y-coordinate of the baseline. If not given, the baseline of the first child is used.")]
+        baseline: Option<f32>,
+        #[wasm_bindgen(param_description = "Whether to apply the align baselines operation to the children of the children, default is true.")]
+        recursive: Option<bool>
+    ) -> VectorObjectBuilder {
+        let align_baselines = Box::new(AlignBaselines { baseline, recursive });
+        self.ops.add_operation(Box::leak(align_baselines));
+        self
+    }
     /// Reverses the path of the VectorObjectBuilder.
     #[wasm_bindgen(return_description = "The vector object being built with the reverse path operation.")]
     pub fn reverse_path(mut self) -> VectorObjectBuilder {
@@ -1863,10 +2329,12 @@ impl VectorObjectBuilder {
         data_width: Option<usize>,
         #[wasm_bindgen(param_description = "The image's number of pixels in a column. It must be provided if the fill style contains an image or different kinds of gradients.")]
         data_height: Option<usize>,
+        #[wasm_bindgen(param_description = "The color space in which colors are interpolated, default is sRGB.")]
+        color_space: Option<ColorSpace>,
         #[wasm_bindgen(param_description = "Whether to apply the interpolate fill operation to the children of the vector object, default is true.")]
         recursive: Option<bool>,
     ) -> VectorObjectBuilder {
-        let interpolate_fill = Box::new(LerpFill { fill, t, x, y, width, height, data_width, data_height, recursive });
+        let interpolate_fill = Box::new(LerpFill { fill, t, x, y, width, height, data_width, data_height, color_space, recursive });
         self.ops.add_operation(Box::leak(interpolate_fill));
         self
     }
@@ -1890,10 +2358,12 @@ impl VectorObjectBuilder {
         data_width: Option<usize>,
         #[wasm_bindgen(param_description = "The image's number of pixels in a column. It must be provided if the stroke style contains an image or different kinds of gradients.")]
         data_height: Option<usize>,
+        #[wasm_bindgen(param_description = "The color space in which colors are interpolated, default is sRGB.")]
+        color_space: Option<ColorSpace>,
         #[wasm_bindgen(param_description = "Whether to apply the interpolate stroke operation to the children of the vector object, default is true.")]
         recursive: Option<bool>,
     ) -> VectorObjectBuilder {
-        let interpolate_stroke = Box::new(LerpStroke { stroke, t, x, y, width, height, data_width, data_height, recursive });
+        let interpolate_stroke = Box::new(LerpStroke { stroke, t, x, y, width, height, data_width, data_height, color_space, recursive });
         self.ops.add_operation(Box::leak(interpolate_stroke));
         self
     }
@@ -1907,6 +2377,12 @@ impl VectorObjectBuilder {
 }
 
 impl VectorObjectBuilder {
+    /// Creates a VectorObjectBuilder that calls a factory every time it's built, so the object reflects values read by the factory, such as those of a ValueTracker.
+    pub fn from_factory(factory: Rc<dyn Fn() -> VectorObjectBuilder>) -> VectorObjectBuilder {
+        let mut builder = VectorObjectBuilder::default();
+        builder.ops.add_operation(Box::leak(Box::new(Redraw { factory })));
+        builder
+    }
     pub fn from_node(node: &usvg::Node) -> VectorObjectBuilder {
         let mut builder = VectorObjectBuilder::default();
         match node {
@@ -2207,31 +2683,14 @@ impl VectorObject {
         #[wasm_bindgen(param_description = "Whether to include the children of the vector object.")]
         recursive: Option<bool>
     ) -> Option<Point2D> {
-        let bounding_box = self.bounding_box(recursive);
-        if bounding_box.is_none() {
-            return None;
-        }
-        let bounding_box = bounding_box.unwrap();
-        let center = bounding_box.center();
-        let min_x = bounding_box.min_x();
-        let min_y = bounding_box.min_y();
-        let width = bounding_box.width();
-        let height = bounding_box.height();
-        let x = if key_x < 0.0 {
-            min_x
-        } else if key_x > 0.0 {
-            min_x + width
-        } else {
-            center.x
-        };
-        let y = if key_y < 0.0 {
-            min_y
-        } else if key_y > 0.0 {
-            min_y + height
-        } else {
-            center.y
-        };
-        Some(Point2D { x, y })
+        self.bounding_box(recursive).map(|bounding_box| bounding_box.get_critical_point(key_x, key_y))
+    }
+    /// Gets the y-coordinate of the baseline of the VectorObject. Glyphs typeset by Typst are mirrored vertically by their transform and have their origin on the baseline, so the origin of the first one is used. Otherwise, the bottom of the bounding box is used.
+    #[wasm_bindgen(return_description = "The y-coordinate of the baseline of the vector object.")]
+    pub fn baseline(&self) -> Option<f32> {
+        self.glyph_origin()
+            .map(|origin| origin.y)
+            .or_else(|| self.bounding_box(None).map(|bounding_box| bounding_box.min_y() + bounding_box.height()))
     }
     /// Gets the children of the VectorObject recursively.
     #[wasm_bindgen(return_description = "The children of the vector object.")]
@@ -2316,10 +2775,54 @@ impl VectorObject {
         }
         children
     }
+    /// Gets the descendants of the VectorObject matched by the given Selector, in document order.
+    #[wasm_bindgen(return_description = "The descendants matched by the selector.")]
+    pub fn select(
+        &self,
+        #[wasm_bindgen(param_description = "The selector of the descendants to get.")]
+        selector: &Selector
+    ) -> Vec<VectorObject> {
+        self.select_paths(selector)
+            .iter()
+            .filter_map(|path| self.descendant(path).cloned())
+            .collect()
+    }
 }
 
 impl VectorObject {
     pub fn apply_operation(&mut self, operation: &dyn VectorOperation) {
         operation.apply(self);
     }
+    /// Gets the transformed origin of the first descendant that looks like a typeset glyph, a non-empty path mirrored vertically by its transform.
+    pub fn glyph_origin(&self) -> Option<Point2D> {
+        let transform = self.transform;
+        if !self.path.is_empty() && transform.a * transform.d - transform.b * transform.c < 0.0 {
+            return Some(transform * Point2D::new(0.0, 0.0));
+        }
+        self.children.iter().find_map(|child| child.glyph_origin())
+    }
+    /// Gets the index paths of the descendants matched by the given Selector, each one starting from the children of the VectorObject.
+    pub fn select_paths(&self, selector: &Selector) -> Vec<Vec<usize>> {
+        selector.match_paths(&|path| {
+            self.descendant(path)
+                .map(|descendant| descendant.children.iter().map(|child| child.name()).collect())
+                .unwrap_or_default()
+        })
+    }
+    /// Gets the descendant at the given index path, where an empty path is the VectorObject itself.
+    pub fn descendant(&self, path: &[usize]) -> Option<&VectorObject> {
+        let mut object = self;
+        for index in path {
+            object = object.children.get(*index)?;
+        }
+        Some(object)
+    }
+    /// Gets a mutable reference to the descendant at the given index path, where an empty path is the VectorObject itself.
+    pub fn descendant_mut(&mut self, path: &[usize]) -> Option<&mut VectorObject> {
+        let mut object = self;
+        for index in path {
+            object = object.children.get_mut(*index)?;
+        }
+        Some(object)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,257 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{objects::vector_object::{VectorObject, VectorObjectBuilder}, utils::{bounding_box::BoundingBox, interpolation::lerp, linear_algebra::TransformationMatrix, point2d::Point2D}};
+
+/// A Camera is a rectangular frame in scene coordinates, defined by its center, size and rotation, that is mapped onto the output pixels.
+/// With y-up enabled, the y-axis of the scene points upwards, as in a mathematical coordinate system.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera {
+    /// The center of the frame in scene coordinates.
+    center: Point2D,
+    /// The width of the frame in scene coordinates.
+    width: f32,
+    /// The height of the frame in scene coordinates.
+    height: f32,
+    /// The rotation of the frame in radians.
+    rotation: f32,
+    /// The width of the output in pixels.
+    output_width: f32,
+    /// The height of the output in pixels.
+    output_height: f32,
+    /// Whether the y-axis of the scene points upwards.
+    y_up: bool,
+}
+
+#[wasm_bindgen]
+impl Camera {
+    /// Creates a new Camera for an output of the given size. By default, the frame matches the output pixels.
+    #[wasm_bindgen(constructor, return_description = "A new camera.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The width of the output in pixels.")]
+        output_width: f32,
+        #[wasm_bindgen(param_description = "The height of the output in pixels.")]
+        output_height: f32,
+        #[wasm_bindgen(param_description = "The center of the frame in scene coordinates, default is the center of the output.")]
+        center: Option<Point2D>,
+        #[wasm_bindgen(param_description = "The width of the frame in scene coordinates, default is the output width.")]
+        width: Option<f32>,
+        #[wasm_bindgen(param_description = "The height of the frame in scene coordinates, default is the width times the aspect ratio of the output.")]
+        height: Option<f32>,
+        #[wasm_bindgen(param_description = "The rotation of the frame in radians, default is 0.")]
+        rotation: Option<f32>,
+        #[wasm_bindgen(param_description = "Whether the y-axis of the scene points upwards, default is false.")]
+        y_up: Option<bool>,
+    ) -> Result<Camera, JsError> {
+        if output_width <= 0.0 || output_height <= 0.0 {
+            return Err(JsError::new("The output width and height must be positive."));
+        }
+        let width = width.unwrap_or(output_width);
+        let height = height.unwrap_or(width * output_height / output_width);
+        if width <= 0.0 || height <= 0.0 {
+            return Err(JsError::new("The frame width and height must be positive."));
+        }
+        Ok(Camera {
+            center: center.unwrap_or(Point2D::new(output_width / 2.0, output_height / 2.0)),
+            width,
+            height,
+            rotation: rotation.unwrap_or(0.0),
+            output_width,
+            output_height,
+            y_up: y_up.unwrap_or(false),
+        })
+    }
+    /// Creates a Camera with a mathematical coordinate system, centered at the origin with the y-axis pointing upwards.
+    #[wasm_bindgen(return_description = "A new camera with a mathematical coordinate system.")]
+    pub fn mathematical(
+        #[wasm_bindgen(param_description = "The width of the output in pixels.")]
+        output_width: f32,
+        #[wasm_bindgen(param_description = "The height of the output in pixels.")]
+        output_height: f32,
+        #[wasm_bindgen(param_description = "The width of the frame in scene units, default is 14.")]
+        width: Option<f32>,
+    ) -> Result<Camera, JsError> {
+        Camera::new(output_width, output_height, Some(Point2D::new(0.0, 0.0)), Some(width.unwrap_or(14.0)), None, None, Some(true))
+    }
+    /// Clones the camera.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn copy(&self) -> Camera {
+        *self
+    }
+    /// Returns the center of the frame in scene coordinates.
+    #[wasm_bindgen(getter, return_description = "The center of the frame.")]
+    pub fn center(&self) -> Point2D {
+        self.center
+    }
+    /// Returns the width of the frame in scene coordinates.
+    #[wasm_bindgen(getter, return_description = "The width of the frame.")]
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+    /// Returns the height of the frame in scene coordinates.
+    #[wasm_bindgen(getter, return_description = "The height of the frame.")]
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+    /// Returns the rotation of the frame in radians.
+    #[wasm_bindgen(getter, return_description = "The rotation of the frame in radians.")]
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+    /// Returns the width of the output in pixels.
+    #[wasm_bindgen(getter, return_description = "The width of the output in pixels.")]
+    pub fn output_width(&self) -> f32 {
+        self.output_width
+    }
+    /// Returns the height of the output in pixels.
+    #[wasm_bindgen(getter, return_description = "The height of the output in pixels.")]
+    pub fn output_height(&self) -> f32 {
+        self.output_height
+    }
+    /// Returns whether the y-axis of the scene points upwards.
+    #[wasm_bindgen(getter, return_description = "Whether the y-axis of the scene points upwards.")]
+    pub fn y_up(&self) -> bool {
+        self.y_up
+    }
+    /// Returns the bounding box of the frame in scene coordinates, ignoring the rotation.
+    #[wasm_bindgen(getter, return_description = "The bounding box of the frame.")]
+    pub fn frame(&self) -> BoundingBox {
+        BoundingBox::new(self.center.x - self.width / 2.0, self.center.y - self.height / 2.0, self.width, self.height).unwrap()
+    }
+    /// Returns a copy of the camera moved by the given offset in scene coordinates.
+    #[wasm_bindgen(return_description = "The panned camera.")]
+    pub fn pan(
+        &self,
+        #[wasm_bindgen(param_description = "The x offset.")]
+        dx: f32,
+        #[wasm_bindgen(param_description = "The y offset.")]
+        dy: f32,
+    ) -> Camera {
+        Camera { center: self.center + Point2D::new(dx, dy), ..*self }
+    }
+    /// Returns a copy of the camera centered at the given point.
+    #[wasm_bindgen(return_description = "The moved camera.")]
+    pub fn move_to(
+        &self,
+        #[wasm_bindgen(param_description = "The new center of the frame.")]
+        center: Point2D,
+    ) -> Camera {
+        Camera { center, ..*self }
+    }
+    /// Returns a copy of the camera zoomed by the given factor, where a factor greater than 1 zooms in.
+    #[wasm_bindgen(return_description = "The zoomed camera.")]
+    pub fn zoom(
+        &self,
+        #[wasm_bindgen(param_description = "The zoom factor.")]
+        factor: f32,
+        #[wasm_bindgen(param_description = "The point in scene coordinates that stays in place, default is the center of the frame.")]
+        about_point: Option<Point2D>,
+    ) -> Result<Camera, JsError> {
+        if factor <= 0.0 {
+            return Err(JsError::new("The zoom factor must be positive."));
+        }
+        let about_point = about_point.unwrap_or(self.center);
+        Ok(Camera {
+            center: about_point + (self.center - about_point) * (1.0 / factor),
+            width: self.width / factor,
+            height: self.height / factor,
+            ..*self
+        })
+    }
+    /// Returns a copy of the camera rotated by the given angle.
+    #[wasm_bindgen(return_description = "The rotated camera.")]
+    pub fn rotate(
+        &self,
+        #[wasm_bindgen(param_description = "The angle in radians to rotate the frame by.")]
+        angle: f32,
+    ) -> Camera {
+        Camera { rotation: self.rotation + angle, ..*self }
+    }
+    /// Returns a copy of the camera framing a bounding box, keeping the aspect ratio of the output.
+    #[wasm_bindgen(return_description = "The camera framing the bounding box.")]
+    pub fn frame_bounding_box(
+        &self,
+        #[wasm_bindgen(param_description = "The bounding box to frame.")]
+        bounding_box: &BoundingBox,
+        #[wasm_bindgen(param_description = "The margin to leave around the bounding box in scene units, default is 0.")]
+        margin: Option<f32>,
+    ) -> Camera {
+        let margin = margin.unwrap_or(0.0);
+        let aspect_ratio = self.output_height / self.output_width;
+        let width = (bounding_box.width() + 2.0 * margin).max((bounding_box.height() + 2.0 * margin) / aspect_ratio).max(f32::EPSILON);
+        Camera {
+            center: bounding_box.center(),
+            width,
+            height: width * aspect_ratio,
+            ..*self
+        }
+    }
+    /// Returns a copy of the camera centered at the center of an object, which can be used every frame to follow it.
+    #[wasm_bindgen(return_description = "The camera following the object.")]
+    pub fn follow(
+        &self,
+        #[wasm_bindgen(param_description = "The object to follow.")]
+        object: &VectorObject,
+    ) -> Camera {
+        match object.center() {
+            Some(center) => self.move_to(center),
+            None => *self,
+        }
+    }
+    /// Returns the TransformationMatrix that maps scene coordinates to output pixels.
+    #[wasm_bindgen(getter, return_description = "The matrix from scene coordinates to output pixels.")]
+    pub fn transform(&self) -> TransformationMatrix {
+        let scale_y = if self.y_up { -self.output_height / self.height } else { self.output_height / self.height };
+        TransformationMatrix::translate(self.output_width / 2.0, self.output_height / 2.0)
+            * TransformationMatrix::scale(self.output_width / self.width, scale_y)
+            * TransformationMatrix::rotate(-self.rotation)
+            * TransformationMatrix::translate(-self.center.x, -self.center.y)
+    }
+    /// Maps a point in scene coordinates to output pixels.
+    #[wasm_bindgen(return_description = "The point in output pixels.")]
+    pub fn to_pixel(
+        &self,
+        #[wasm_bindgen(param_description = "The point in scene coordinates.")]
+        point: Point2D,
+    ) -> Point2D {
+        self.transform() * point
+    }
+    /// Maps a point in output pixels to scene coordinates.
+    #[wasm_bindgen(return_description = "The point in scene coordinates.")]
+    pub fn to_scene(
+        &self,
+        #[wasm_bindgen(param_description = "The point in output pixels.")]
+        point: Point2D,
+    ) -> Point2D {
+        self.transform().inverse() * point
+    }
+    /// Returns a copy of an object mapped to output pixels, ready to be rendered.
+    #[wasm_bindgen(return_description = "The object in output pixels.")]
+    pub fn apply(
+        &self,
+        #[wasm_bindgen(param_description = "The object in scene coordinates.")]
+        object: &VectorObject,
+    ) -> VectorObject {
+        VectorObjectBuilder::new(object).apply_transform(self.transform(), None).build()
+    }
+    /// Linearly interpolates two cameras. The frame size is interpolated geometrically, so zooming looks uniform.
+    #[wasm_bindgen(return_description = "The interpolated camera.")]
+    pub fn lerp(
+        #[wasm_bindgen(param_description = "The start camera.")]
+        camera1: &Camera,
+        #[wasm_bindgen(param_description = "The end camera.")]
+        camera2: &Camera,
+        #[wasm_bindgen(param_description = "The progress value.")]
+        t: f32,
+    ) -> Camera {
+        Camera {
+            center: Point2D::lerp(&camera1.center, &camera2.center, t),
+            width: camera1.width * (camera2.width / camera1.width).powf(t),
+            height: camera1.height * (camera2.height / camera1.height).powf(t),
+            rotation: lerp(camera1.rotation, camera2.rotation, t),
+            output_width: lerp(camera1.output_width, camera2.output_width, t),
+            output_height: lerp(camera1.output_height, camera2.output_height, t),
+            y_up: if t < 0.5 { camera1.y_up } else { camera2.y_up },
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod camera;
+pub mod updater;
+pub mod value_tracker;
\ No newline at end of file
@@ -0,0 +1,261 @@
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+
+use crate::objects::vector_object::{VectorObject, VectorObjectBuilder};
+
+#[wasm_bindgen]
+extern "C" {
+    /// A JavaScript function that returns the updated version of an object.
+    #[wasm_bindgen(typescript_type = "(object: VectorObject, dt: number, scene: VectorObject[]) => VectorObject")]
+    pub type UpdaterCallback;
+    #[wasm_bindgen(method, catch, js_name = call)]
+    fn call(this: &UpdaterCallback, this_arg: &JsValue, object: VectorObject, dt: f32, scene: Vec<VectorObject>) -> Result<VectorObject, JsValue>;
+
+    /// A JavaScript function that builds an object from scratch.
+    #[wasm_bindgen(typescript_type = "(scene: VectorObject[]) => VectorObjectBuilder")]
+    pub type RedrawCallback;
+    #[wasm_bindgen(method, catch, js_name = call)]
+    fn call(this: &RedrawCallback, this_arg: &JsValue, scene: Vec<VectorObject>) -> Result<VectorObjectBuilder, JsValue>;
+}
+
+/// A function that receives an object, the time in seconds since the last update and the objects of the scene, and returns the updated object.
+pub type UpdaterFunction = Rc<dyn Fn(&VectorObject, f32, &[VectorObject]) -> Result<VectorObject, String>>;
+
+/// A function that receives the objects of the scene and returns a builder for an object drawn from scratch.
+pub type RedrawFunction = Rc<dyn Fn(&[VectorObject]) -> Result<VectorObjectBuilder, String>>;
+
+/// An updater of the UpdaterRegistry.
+#[derive(Clone)]
+struct Updater {
+    /// The name of the updater.
+    name: Rc<String>,
+    /// The name of the scene object the updater updates.
+    target: Rc<String>,
+    /// The names of the updaters that must run before this one.
+    dependencies: Rc<Vec<String>>,
+    /// The function that updates the object.
+    function: UpdaterFunction,
+    /// Whether the object is drawn from scratch, so it's added to the scene when it's missing.
+    redraw: bool,
+}
+
+/// An UpdaterRegistry holds the updaters of a scene, functions that recompute objects every frame from their previous state, the elapsed time and the other objects of the scene.
+/// Updaters run in the order of their dependencies, so an object can depend on objects that were already updated in the same frame.
+#[wasm_bindgen]
+#[derive(Clone, Default)]
+pub struct UpdaterRegistry {
+    /// The updaters in the order they were added.
+    updaters: Vec<Updater>,
+}
+
+#[wasm_bindgen]
+impl UpdaterRegistry {
+    /// Creates a new empty UpdaterRegistry.
+    #[wasm_bindgen(constructor, return_description = "A new updater registry.")]
+    pub fn new() -> UpdaterRegistry {
+        UpdaterRegistry::default()
+    }
+    /// Clones the updater registry.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn copy(&self) -> UpdaterRegistry {
+        self.clone()
+    }
+    /// Returns the names of the updaters in the order they were added.
+    #[wasm_bindgen(getter, return_description = "The names of the updaters.")]
+    pub fn names(&self) -> Vec<String> {
+        self.updaters.iter().map(|updater| updater.name.to_string()).collect()
+    }
+    /// Checks if the registry has no updaters.
+    #[wasm_bindgen(return_description = "A boolean indicating if the registry has no updaters.")]
+    pub fn is_empty(&self) -> bool {
+        self.updaters.is_empty()
+    }
+    /// Adds an updater that replaces the scene object with the given name by the result of a function every frame.
+    #[wasm_bindgen(return_description = "An error if an updater with the same name already exists.")]
+    pub fn add_updater(
+        &mut self,
+        #[wasm_bindgen(param_description = "The name of the updater, used to declare dependencies on it.")]
+        name: String,
+        #[wasm_bindgen(param_description = "The name of the scene object to update.")]
+        target: String,
+        #[wasm_bindgen(param_description = "The function that receives the object, the time in seconds since the last update and the objects of the scene, and returns the updated object. The returned object is consumed.")]
+        updater: UpdaterCallback,
+        #[wasm_bindgen(param_description = "The names of the updaters that must run before this one, default is none.")]
+        dependencies: Option<Vec<String>>,
+    ) -> Result<(), JsError> {
+        let function: UpdaterFunction = Rc::new(move |object, dt, scene| {
+            updater.call(&JsValue::NULL, object.clone(), dt, scene.to_vec())
+                .map_err(|error| error.as_string().unwrap_or("The updater threw an error.".to_string()))
+        });
+        self.add_updater_function(name, target, function, dependencies.unwrap_or_default())
+            .map_err(|error| JsError::new(&error))
+    }
+    /// Adds an updater that rebuilds the scene object with the given name from scratch every frame, adding it to the scene if it's missing.
+    #[wasm_bindgen(return_description = "An error if an updater with the same name already exists.")]
+    pub fn always_redraw(
+        &mut self,
+        #[wasm_bindgen(param_description = "The name of the updater, which is also the name given to the redrawn object.")]
+        name: String,
+        #[wasm_bindgen(param_description = "The function that receives the objects of the scene and returns a vector object builder for the object. The returned builder is consumed.")]
+        factory: RedrawCallback,
+        #[wasm_bindgen(param_description = "The names of the updaters that must run before this one, default is none.")]
+        dependencies: Option<Vec<String>>,
+    ) -> Result<(), JsError> {
+        let factory: RedrawFunction = Rc::new(move |scene| {
+            factory.call(&JsValue::NULL, scene.to_vec())
+                .map_err(|error| error.as_string().unwrap_or("The redraw factory threw an error.".to_string()))
+        });
+        self.always_redraw_function(name, factory, dependencies.unwrap_or_default())
+            .map_err(|error| JsError::new(&error))
+    }
+    /// Adds an updater that rebuilds the scene object with the given name from a builder every frame, such as one bound to value trackers, adding it to the scene if it's missing.
+    #[wasm_bindgen(return_description = "An error if an updater with the same name already exists.")]
+    pub fn always_rebuild(
+        &mut self,
+        #[wasm_bindgen(param_description = "The name of the updater, which is also the name given to the rebuilt object.")]
+        name: String,
+        #[wasm_bindgen(param_description = "The builder to build every frame.")]
+        builder: &VectorObjectBuilder,
+        #[wasm_bindgen(param_description = "The names of the updaters that must run before this one, default is none.")]
+        dependencies: Option<Vec<String>>,
+    ) -> Result<(), JsError> {
+        let builder = builder.clone();
+        let factory: RedrawFunction = Rc::new(move |_| Ok(builder.clone()));
+        self.always_redraw_function(name, factory, dependencies.unwrap_or_default())
+            .map_err(|error| JsError::new(&error))
+    }
+    /// Removes the updater with the given name.
+    #[wasm_bindgen(return_description = "A boolean indicating if the updater was removed.")]
+    pub fn remove_updater(
+        &mut self,
+        #[wasm_bindgen(param_description = "The name of the updater to remove.")]
+        name: String,
+    ) -> bool {
+        let length = self.updaters.len();
+        self.updaters.retain(|updater| *updater.name != name);
+        self.updaters.len() != length
+    }
+    /// Removes all the updaters.
+    #[wasm_bindgen]
+    pub fn clear(&mut self) {
+        self.updaters.clear();
+    }
+    /// Returns the names of the updaters in the order they run.
+    #[wasm_bindgen(return_description = "The names of the updaters in the order they run.")]
+    pub fn order(&self) -> Result<Vec<String>, JsError> {
+        self.sorted_indices()
+            .map(|indices| indices.iter().map(|&index| self.updaters[index].name.to_string()).collect())
+            .map_err(|error| JsError::new(&error))
+    }
+    /// Runs every updater once and returns the updated objects of the scene.
+    #[wasm_bindgen(return_description = "The updated objects of the scene.")]
+    pub fn update(
+        &self,
+        #[wasm_bindgen(param_description = "The objects of the scene.")]
+        objects: Vec<VectorObject>,
+        #[wasm_bindgen(param_description = "The time in seconds since the last update.")]
+        dt: f32,
+    ) -> Result<Vec<VectorObject>, JsError> {
+        self.update_objects(objects, dt).map_err(|error| JsError::new(&error))
+    }
+}
+
+impl UpdaterRegistry {
+    /// Adds an updater from a Rust function.
+    pub fn add_updater_function(
+        &mut self,
+        name: String,
+        target: String,
+        function: UpdaterFunction,
+        dependencies: Vec<String>,
+    ) -> Result<(), String> {
+        self.push_updater(name, target, function, dependencies, false)
+    }
+    fn push_updater(
+        &mut self,
+        name: String,
+        target: String,
+        function: UpdaterFunction,
+        dependencies: Vec<String>,
+        redraw: bool,
+    ) -> Result<(), String> {
+        if self.updaters.iter().any(|updater| *updater.name == name) {
+            return Err(format!("An updater named \"{}\" already exists.", name));
+        }
+        self.updaters.push(Updater {
+            name: Rc::new(name),
+            target: Rc::new(target),
+            dependencies: Rc::new(dependencies),
+            function,
+            redraw,
+        });
+        Ok(())
+    }
+    /// Adds an always redraw updater from a Rust factory.
+    pub fn always_redraw_function(
+        &mut self,
+        name: String,
+        factory: RedrawFunction,
+        dependencies: Vec<String>,
+    ) -> Result<(), String> {
+        let object_name = name.clone();
+        let function: UpdaterFunction = Rc::new(move |_, _, scene| {
+            Ok(factory(scene)?.set_name(Some(object_name.clone())).build())
+        });
+        self.push_updater(name.clone(), name, function, dependencies, true)
+    }
+    /// Returns the indices of the updaters sorted so every updater comes after its dependencies, keeping the insertion order otherwise.
+    pub fn sorted_indices(&self) -> Result<Vec<usize>, String> {
+        let mut dependencies = Vec::with_capacity(self.updaters.len());
+        for updater in self.updaters.iter() {
+            let mut indices = Vec::with_capacity(updater.dependencies.len());
+            for dependency in updater.dependencies.iter() {
+                let index = self.updaters.iter().position(|other| *other.name == *dependency)
+                    .ok_or_else(|| format!("Updater \"{}\" depends on unknown updater \"{}\".", updater.name, dependency))?;
+                indices.push(index);
+            }
+            dependencies.push(indices);
+        }
+        let mut sorted = Vec::with_capacity(self.updaters.len());
+        let mut done = vec![false; self.updaters.len()];
+        while sorted.len() < self.updaters.len() {
+            let next = (0..self.updaters.len())
+                .find(|&index| !done[index] && dependencies[index].iter().all(|&dependency| done[dependency]));
+            match next {
+                Some(index) => {
+                    done[index] = true;
+                    sorted.push(index);
+                }
+                None => {
+                    let cycle = (0..self.updaters.len())
+                        .filter(|&index| !done[index])
+                        .map(|index| format!("\"{}\"", self.updaters[index].name))
+                        .collect::<Vec<String>>()
+                        .join(", ");
+                    return Err(format!("The dependencies between updaters {} form a cycle.", cycle));
+                }
+            }
+        }
+        Ok(sorted)
+    }
+    /// Runs every updater once in dependency order, so later updaters see the objects updated by earlier ones.
+    pub fn update_objects(&self, mut objects: Vec<VectorObject>, dt: f32) -> Result<Vec<VectorObject>, String> {
+        for index in self.sorted_indices()? {
+            let updater = &self.updaters[index];
+            let position = objects.iter().position(|object| object.name().is_some_and(|name| name == *updater.target));
+            match position {
+                Some(position) => {
+                    objects[position] = (updater.function)(&objects[position], dt, &objects)?;
+                }
+                None if updater.redraw => {
+                    let object = (updater.function)(&VectorObject::default(), dt, &objects)?;
+                    objects.push(object);
+                }
+                // The target may be added to the scene later, so the updater waits for it.
+                None => {}
+            }
+        }
+        Ok(objects)
+    }
+}
@@ -0,0 +1,198 @@
+use std::{cell::RefCell, rc::Rc};
+
+use wasm_bindgen::prelude::*;
+
+use crate::utils::{color_space::ColorSpace, point2d::Point2D, style::Color};
+
+/// The value held by a ValueTracker.
+#[derive(Clone, Copy, Debug)]
+pub enum TrackedValue {
+    /// A number, kept in double precision so large values such as counters keep all their digits.
+    Number(f64),
+    /// A 2D point.
+    Point(Point2D),
+    /// A color.
+    Color(Color),
+}
+
+impl TrackedValue {
+    /// Returns the name of the kind of the value.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            TrackedValue::Number(_) => "number",
+            TrackedValue::Point(_) => "point",
+            TrackedValue::Color(_) => "color",
+        }
+    }
+    /// Linearly interpolates two values of the same kind, interpolating colors in the given color space.
+    pub fn lerp(value1: &TrackedValue, value2: &TrackedValue, t: f32, color_space: Option<ColorSpace>) -> Result<TrackedValue, String> {
+        match (value1, value2) {
+            (TrackedValue::Number(number1), TrackedValue::Number(number2)) => Ok(TrackedValue::Number(number1 + (number2 - number1) * t as f64)),
+            (TrackedValue::Point(point1), TrackedValue::Point(point2)) => Ok(TrackedValue::Point(Point2D::lerp(point1, point2, t))),
+            (TrackedValue::Color(color1), TrackedValue::Color(color2)) => Ok(TrackedValue::Color(Color::lerp(color1, color2, t, color_space))),
+            _ => Err(format!("Can't interpolate a {} value with a {} value.", value1.kind(), value2.kind())),
+        }
+    }
+}
+
+/// A ValueTracker holds a number, a Point2D or a Color that can be animated like an object and read by builders.
+/// Cloning it in Rust gives another handle to the same value, so every builder bound to it sees its changes.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct ValueTracker {
+    /// The shared value.
+    value: Rc<RefCell<TrackedValue>>,
+}
+
+#[wasm_bindgen]
+impl ValueTracker {
+    /// Creates a new ValueTracker holding a number.
+    #[wasm_bindgen(constructor, return_description = "A new value tracker holding a number.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The initial number.")]
+        value: f64,
+    ) -> ValueTracker {
+        ValueTracker::from_value(TrackedValue::Number(value))
+    }
+    /// Creates a new ValueTracker holding a Point2D.
+    #[wasm_bindgen(return_description = "A new value tracker holding a point.")]
+    pub fn from_point(
+        #[wasm_bindgen(param_description = "The initial point.")]
+        point: Point2D,
+    ) -> ValueTracker {
+        ValueTracker::from_value(TrackedValue::Point(point))
+    }
+    /// Creates a new ValueTracker holding a Color.
+    #[wasm_bindgen(return_description = "A new value tracker holding a color.")]
+    pub fn from_color(
+        #[wasm_bindgen(param_description = "The initial color.")]
+        color: Color,
+    ) -> ValueTracker {
+        ValueTracker::from_value(TrackedValue::Color(color))
+    }
+    /// Clones the value tracker into an independent one with the same current value.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn copy(&self) -> ValueTracker {
+        ValueTracker::from_value(self.get())
+    }
+    /// Returns another handle to the same value, so changes through either of them are seen by both.
+    #[wasm_bindgen(return_description = "A value tracker sharing the value.")]
+    pub fn handle(&self) -> ValueTracker {
+        self.clone()
+    }
+    /// Returns the kind of the value, which is "number", "point" or "color".
+    #[wasm_bindgen(getter, return_description = "The kind of the value.")]
+    pub fn kind(&self) -> String {
+        self.get().kind().to_string()
+    }
+    /// Returns the number held by the tracker.
+    #[wasm_bindgen(getter, return_description = "The number held by the tracker.")]
+    pub fn number(&self) -> Result<f64, JsError> {
+        self.number_value().map_err(|error| JsError::new(&error))
+    }
+    /// Returns the point held by the tracker.
+    #[wasm_bindgen(getter, return_description = "The point held by the tracker.")]
+    pub fn point(&self) -> Result<Point2D, JsError> {
+        self.point_value().map_err(|error| JsError::new(&error))
+    }
+    /// Returns the color held by the tracker.
+    #[wasm_bindgen(getter, return_description = "The color held by the tracker.")]
+    pub fn color(&self) -> Result<Color, JsError> {
+        self.color_value().map_err(|error| JsError::new(&error))
+    }
+    /// Sets the number held by the tracker.
+    #[wasm_bindgen(return_description = "An error if the tracker doesn't hold a number.")]
+    pub fn set_number(
+        &self,
+        #[wasm_bindgen(param_description = "The new number.")]
+        number: f64,
+    ) -> Result<(), JsError> {
+        self.set(TrackedValue::Number(number)).map_err(|error| JsError::new(&error))
+    }
+    /// Sets the point held by the tracker.
+    #[wasm_bindgen(return_description = "An error if the tracker doesn't hold a point.")]
+    pub fn set_point(
+        &self,
+        #[wasm_bindgen(param_description = "The new point.")]
+        point: Point2D,
+    ) -> Result<(), JsError> {
+        self.set(TrackedValue::Point(point)).map_err(|error| JsError::new(&error))
+    }
+    /// Sets the color held by the tracker.
+    #[wasm_bindgen(return_description = "An error if the tracker doesn't hold a color.")]
+    pub fn set_color(
+        &self,
+        #[wasm_bindgen(param_description = "The new color.")]
+        color: Color,
+    ) -> Result<(), JsError> {
+        self.set(TrackedValue::Color(color)).map_err(|error| JsError::new(&error))
+    }
+    /// Adds an amount to the number held by the tracker.
+    #[wasm_bindgen(return_description = "An error if the tracker doesn't hold a number.")]
+    pub fn increment(
+        &self,
+        #[wasm_bindgen(param_description = "The amount to add.")]
+        amount: f64,
+    ) -> Result<(), JsError> {
+        let number = self.number_value().map_err(|error| JsError::new(&error))?;
+        self.set(TrackedValue::Number(number + amount)).map_err(|error| JsError::new(&error))
+    }
+    /// Sets the value of the tracker to the linear interpolation between the values of two other trackers, which is how trackers are animated.
+    #[wasm_bindgen(return_description = "An error if the trackers don't hold values of the same kind.")]
+    pub fn interpolate(
+        &self,
+        #[wasm_bindgen(param_description = "The tracker with the start value.")]
+        start: &ValueTracker,
+        #[wasm_bindgen(param_description = "The tracker with the end value.")]
+        end: &ValueTracker,
+        #[wasm_bindgen(param_description = "The progress value.")]
+        t: f32,
+        #[wasm_bindgen(param_description = "The color space in which colors are interpolated, default is sRGB.")]
+        color_space: Option<ColorSpace>,
+    ) -> Result<(), JsError> {
+        TrackedValue::lerp(&start.get(), &end.get(), t, color_space)
+            .and_then(|value| self.set(value))
+            .map_err(|error| JsError::new(&error))
+    }
+}
+
+impl ValueTracker {
+    /// Creates a new ValueTracker from a TrackedValue.
+    pub fn from_value(value: TrackedValue) -> ValueTracker {
+        ValueTracker { value: Rc::new(RefCell::new(value)) }
+    }
+    /// Returns the current value.
+    pub fn get(&self) -> TrackedValue {
+        *self.value.borrow()
+    }
+    /// Replaces the current value with another one of the same kind.
+    pub fn set(&self, value: TrackedValue) -> Result<(), String> {
+        let current = self.get();
+        if current.kind() != value.kind() {
+            return Err(format!("Can't set a {} value to a tracker holding a {} value.", value.kind(), current.kind()));
+        }
+        *self.value.borrow_mut() = value;
+        Ok(())
+    }
+    /// Returns the number held by the tracker.
+    pub fn number_value(&self) -> Result<f64, String> {
+        match self.get() {
+            TrackedValue::Number(number) => Ok(number),
+            value => Err(format!("The tracker holds a {} value, not a number.", value.kind())),
+        }
+    }
+    /// Returns the point held by the tracker.
+    pub fn point_value(&self) -> Result<Point2D, String> {
+        match self.get() {
+            TrackedValue::Point(point) => Ok(point),
+            value => Err(format!("The tracker holds a {} value, not a point.", value.kind())),
+        }
+    }
+    /// Returns the color held by the tracker.
+    pub fn color_value(&self) -> Result<Color, String> {
+        match self.get() {
+            TrackedValue::Color(color) => Ok(color),
+            value => Err(format!("The tracker holds a {} value, not a color.", value.kind())),
+        }
+    }
+}
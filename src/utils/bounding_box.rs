@@ -196,4 +196,30 @@ impl BoundingBox {
             y: self.min_y + self.height / 2.0,
         }
     }
+
+    /// Returns the critical point of the bounding box, which is a corner, the middle of an edge or the center.
+    #[wasm_bindgen(return_description = "The critical point of the bounding box.")]
+    pub fn get_critical_point(
+        &self,
+        #[wasm_bindgen(param_description = "The x key of the critical point. If negative, the minimum x is returned. If positive, the maximum x is returned. If zero, the center x is returned.")]
+        key_x: f32,
+        #[wasm_bindgen(param_description = "The y key of the critical point. If negative, the minimum y is returned. If positive, the maximum y is returned. If zero, the center y is returned.")]
+        key_y: f32,
+    ) -> Point2D {
+        let x = if key_x < 0.0 {
+            self.min_x
+        } else if key_x > 0.0 {
+            self.min_x + self.width
+        } else {
+            self.min_x + self.width / 2.0
+        };
+        let y = if key_y < 0.0 {
+            self.min_y
+        } else if key_y > 0.0 {
+            self.min_y + self.height
+        } else {
+            self.min_y + self.height / 2.0
+        };
+        Point2D { x, y }
+    }
 }
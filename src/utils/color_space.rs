@@ -0,0 +1,372 @@
+use wasm_bindgen::prelude::*;
+
+use crate::utils::interpolation::lerp;
+
+/// A color space in which colors are interpolated.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// Gamma encoded sRGB, the space colors are stored in.
+    #[default]
+    Srgb,
+    /// Linear light sRGB, which mixes colors like light does.
+    LinearRgb,
+    /// Hue, saturation and lightness, with the hue interpolated along the shorter arc.
+    Hsl,
+    /// Hue, saturation and value, with the hue interpolated along the shorter arc.
+    Hsv,
+    /// The perceptually uniform Oklab space.
+    Oklab,
+    /// The polar form of Oklab with lightness, chroma and hue, with the hue interpolated along the shorter arc.
+    Oklch,
+}
+
+impl ColorSpace {
+    /// Returns the index of the hue component in the space, if it has one.
+    pub fn hue_index(&self) -> Option<usize> {
+        match self {
+            ColorSpace::Hsl | ColorSpace::Hsv => Some(0),
+            ColorSpace::Oklch => Some(2),
+            _ => None,
+        }
+    }
+    /// Returns the index of the component that makes the hue meaningless when it's close to zero, if the space has a hue.
+    pub fn chroma_index(&self) -> Option<usize> {
+        match self {
+            ColorSpace::Hsl | ColorSpace::Hsv | ColorSpace::Oklch => Some(1),
+            _ => None,
+        }
+    }
+    /// Converts gamma encoded sRGB components between 0 and 1 to the space.
+    pub fn encode(&self, rgb: [f32; 3]) -> [f32; 3] {
+        match self {
+            ColorSpace::Srgb => rgb,
+            ColorSpace::LinearRgb => rgb.map(srgb_to_linear),
+            ColorSpace::Hsl => rgb_to_hsl(rgb),
+            ColorSpace::Hsv => rgb_to_hsv(rgb),
+            ColorSpace::Oklab => linear_rgb_to_oklab(rgb.map(srgb_to_linear)),
+            ColorSpace::Oklch => oklab_to_oklch(linear_rgb_to_oklab(rgb.map(srgb_to_linear))),
+        }
+    }
+    /// Converts components of the space to gamma encoded sRGB components, which may be out of the 0 to 1 range.
+    pub fn decode(&self, components: [f32; 3]) -> [f32; 3] {
+        match self {
+            ColorSpace::Srgb => components,
+            ColorSpace::LinearRgb => components.map(linear_to_srgb),
+            ColorSpace::Hsl => hsl_to_rgb(components),
+            ColorSpace::Hsv => hsv_to_rgb(components),
+            ColorSpace::Oklab => oklab_to_linear_rgb(components).map(linear_to_srgb),
+            ColorSpace::Oklch => oklab_to_linear_rgb(oklch_to_oklab(components)).map(linear_to_srgb),
+        }
+    }
+    /// Interpolates components of the space, taking the shorter arc for hues. The hue of an achromatic color takes the hue of the other color.
+    pub fn lerp(&self, components1: [f32; 3], components2: [f32; 3], t: f32) -> [f32; 3] {
+        let mut components1 = components1;
+        let mut components2 = components2;
+        if let (Some(hue), Some(chroma)) = (self.hue_index(), self.chroma_index()) {
+            if components1[chroma] < 1e-4 {
+                components1[hue] = components2[hue];
+            }
+            if components2[chroma] < 1e-4 {
+                components2[hue] = components1[hue];
+            }
+        }
+        std::array::from_fn(|i| {
+            if Some(i) == self.hue_index() {
+                lerp_hue(components1[i], components2[i], t)
+            } else {
+                lerp(components1[i], components2[i], t)
+            }
+        })
+    }
+}
+
+/// Converts a gamma encoded sRGB component between 0 and 1 to linear light.
+pub fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a linear light component to gamma encoded sRGB.
+pub fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Interpolates two hues in degrees along the shorter arc.
+pub fn lerp_hue(hue1: f32, hue2: f32, t: f32) -> f32 {
+    let mut difference = (hue2 - hue1).rem_euclid(360.0);
+    if difference > 180.0 {
+        difference -= 360.0;
+    }
+    (hue1 + difference * t).rem_euclid(360.0)
+}
+
+/// Returns the hue in degrees, the maximum and the minimum of sRGB components.
+fn hue_max_min(rgb: [f32; 3]) -> (f32, f32, f32) {
+    let [red, green, blue] = rgb;
+    let max = red.max(green).max(blue);
+    let min = red.min(green).min(blue);
+    let delta = max - min;
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == red {
+        60.0 * ((green - blue) / delta).rem_euclid(6.0)
+    } else if max == green {
+        60.0 * ((blue - red) / delta + 2.0)
+    } else {
+        60.0 * ((red - green) / delta + 4.0)
+    };
+    (hue, max, min)
+}
+
+/// Converts sRGB components between 0 and 1 to hue in degrees, saturation and lightness between 0 and 1.
+pub fn rgb_to_hsl(rgb: [f32; 3]) -> [f32; 3] {
+    let (hue, max, min) = hue_max_min(rgb);
+    let lightness = (max + min) / 2.0;
+    let saturation = if max == min {
+        0.0
+    } else {
+        (max - min) / (1.0 - (2.0 * lightness - 1.0).abs())
+    };
+    [hue, saturation, lightness]
+}
+
+/// Converts hue in degrees, saturation and lightness between 0 and 1 to sRGB components.
+pub fn hsl_to_rgb(hsl: [f32; 3]) -> [f32; 3] {
+    let [hue, saturation, lightness] = hsl;
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    hue_chroma_to_rgb(hue, chroma, lightness - chroma / 2.0)
+}
+
+/// Converts sRGB components between 0 and 1 to hue in degrees, saturation and value between 0 and 1.
+pub fn rgb_to_hsv(rgb: [f32; 3]) -> [f32; 3] {
+    let (hue, max, min) = hue_max_min(rgb);
+    let saturation = if max == 0.0 { 0.0 } else { (max - min) / max };
+    [hue, saturation, max]
+}
+
+/// Converts hue in degrees, saturation and value between 0 and 1 to sRGB components.
+pub fn hsv_to_rgb(hsv: [f32; 3]) -> [f32; 3] {
+    let [hue, saturation, value] = hsv;
+    let chroma = value * saturation;
+    hue_chroma_to_rgb(hue, chroma, value - chroma)
+}
+
+/// Builds sRGB components from a hue in degrees, a chroma and the value added to every component.
+fn hue_chroma_to_rgb(hue: f32, chroma: f32, offset: f32) -> [f32; 3] {
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+    let [red, green, blue] = match sector as u32 {
+        0 => [chroma, x, 0.0],
+        1 => [x, chroma, 0.0],
+        2 => [0.0, chroma, x],
+        3 => [0.0, x, chroma],
+        4 => [x, 0.0, chroma],
+        _ => [chroma, 0.0, x],
+    };
+    [red + offset, green + offset, blue + offset]
+}
+
+/// Converts linear sRGB components to Oklab lightness, a and b.
+pub fn linear_rgb_to_oklab(rgb: [f32; 3]) -> [f32; 3] {
+    let [red, green, blue] = rgb;
+    let l = (0.41222146 * red + 0.53633255 * green + 0.051445995 * blue).cbrt();
+    let m = (0.2119035 * red + 0.6806995 * green + 0.10739696 * blue).cbrt();
+    let s = (0.08830246 * red + 0.28171885 * green + 0.6299787 * blue).cbrt();
+    [
+        0.21045426 * l + 0.7936178 * m - 0.004072047 * s,
+        1.9779985 * l - 2.4285922 * m + 0.4505937 * s,
+        0.025904037 * l + 0.78277177 * m - 0.80867577 * s,
+    ]
+}
+
+/// Converts Oklab lightness, a and b to linear sRGB components.
+pub fn oklab_to_linear_rgb(lab: [f32; 3]) -> [f32; 3] {
+    let [lightness, a, b] = lab;
+    let l = (lightness + 0.39633778 * a + 0.21580376 * b).powi(3);
+    let m = (lightness - 0.105561346 * a - 0.06385417 * b).powi(3);
+    let s = (lightness - 0.08948418 * a - 1.2914855 * b).powi(3);
+    [
+        4.0767417 * l - 3.3077116 * m + 0.23096994 * s,
+        -1.268438 * l + 2.6097574 * m - 0.34131938 * s,
+        -0.0041960864 * l - 0.7034186 * m + 1.7076147 * s,
+    ]
+}
+
+/// Converts Oklab lightness, a and b to Oklch lightness, chroma and hue in degrees.
+pub fn oklab_to_oklch(lab: [f32; 3]) -> [f32; 3] {
+    let [lightness, a, b] = lab;
+    [lightness, a.hypot(b), b.atan2(a).to_degrees().rem_euclid(360.0)]
+}
+
+/// Converts Oklch lightness, chroma and hue in degrees to Oklab lightness, a and b.
+pub fn oklch_to_oklab(lch: [f32; 3]) -> [f32; 3] {
+    let [lightness, chroma, hue] = lch;
+    let (sin, cos) = hue.to_radians().sin_cos();
+    [lightness, chroma * cos, chroma * sin]
+}
+
+/// Returns the red, green and blue components of a CSS named color, ignoring case.
+pub fn named_color(name: &str) -> Option<[u8; 3]> {
+    let name = name.to_ascii_lowercase();
+    NAMED_COLORS.iter().find(|(color_name, _)| *color_name == name).map(|(_, rgb)| *rgb)
+}
+
+/// The CSS named colors.
+const NAMED_COLORS: [(&str, [u8; 3]); 148] = [
+    ("aliceblue", [240, 248, 255]),
+    ("antiquewhite", [250, 235, 215]),
+    ("aqua", [0, 255, 255]),
+    ("aquamarine", [127, 255, 212]),
+    ("azure", [240, 255, 255]),
+    ("beige", [245, 245, 220]),
+    ("bisque", [255, 228, 196]),
+    ("black", [0, 0, 0]),
+    ("blanchedalmond", [255, 235, 205]),
+    ("blue", [0, 0, 255]),
+    ("blueviolet", [138, 43, 226]),
+    ("brown", [165, 42, 42]),
+    ("burlywood", [222, 184, 135]),
+    ("cadetblue", [95, 158, 160]),
+    ("chartreuse", [127, 255, 0]),
+    ("chocolate", [210, 105, 30]),
+    ("coral", [255, 127, 80]),
+    ("cornflowerblue", [100, 149, 237]),
+    ("cornsilk", [255, 248, 220]),
+    ("crimson", [220, 20, 60]),
+    ("cyan", [0, 255, 255]),
+    ("darkblue", [0, 0, 139]),
+    ("darkcyan", [0, 139, 139]),
+    ("darkgoldenrod", [184, 134, 11]),
+    ("darkgray", [169, 169, 169]),
+    ("darkgreen", [0, 100, 0]),
+    ("darkgrey", [169, 169, 169]),
+    ("darkkhaki", [189, 183, 107]),
+    ("darkmagenta", [139, 0, 139]),
+    ("darkolivegreen", [85, 107, 47]),
+    ("darkorange", [255, 140, 0]),
+    ("darkorchid", [153, 50, 204]),
+    ("darkred", [139, 0, 0]),
+    ("darksalmon", [233, 150, 122]),
+    ("darkseagreen", [143, 188, 143]),
+    ("darkslateblue", [72, 61, 139]),
+    ("darkslategray", [47, 79, 79]),
+    ("darkslategrey", [47, 79, 79]),
+    ("darkturquoise", [0, 206, 209]),
+    ("darkviolet", [148, 0, 211]),
+    ("deeppink", [255, 20, 147]),
+    ("deepskyblue", [0, 191, 255]),
+    ("dimgray", [105, 105, 105]),
+    ("dimgrey", [105, 105, 105]),
+    ("dodgerblue", [30, 144, 255]),
+    ("firebrick", [178, 34, 34]),
+    ("floralwhite", [255, 250, 240]),
+    ("forestgreen", [34, 139, 34]),
+    ("fuchsia", [255, 0, 255]),
+    ("gainsboro", [220, 220, 220]),
+    ("ghostwhite", [248, 248, 255]),
+    ("gold", [255, 215, 0]),
+    ("goldenrod", [218, 165, 32]),
+    ("gray", [128, 128, 128]),
+    ("green", [0, 128, 0]),
+    ("greenyellow", [173, 255, 47]),
+    ("grey", [128, 128, 128]),
+    ("honeydew", [240, 255, 240]),
+    ("hotpink", [255, 105, 180]),
+    ("indianred", [205, 92, 92]),
+    ("indigo", [75, 0, 130]),
+    ("ivory", [255, 255, 240]),
+    ("khaki", [240, 230, 140]),
+    ("lavender", [230, 230, 250]),
+    ("lavenderblush", [255, 240, 245]),
+    ("lawngreen", [124, 252, 0]),
+    ("lemonchiffon", [255, 250, 205]),
+    ("lightblue", [173, 216, 230]),
+    ("lightcoral", [240, 128, 128]),
+    ("lightcyan", [224, 255, 255]),
+    ("lightgoldenrodyellow", [250, 250, 210]),
+    ("lightgray", [211, 211, 211]),
+    ("lightgreen", [144, 238, 144]),
+    ("lightgrey", [211, 211, 211]),
+    ("lightpink", [255, 182, 193]),
+    ("lightsalmon", [255, 160, 122]),
+    ("lightseagreen", [32, 178, 170]),
+    ("lightskyblue", [135, 206, 250]),
+    ("lightslategray", [119, 136, 153]),
+    ("lightslategrey", [119, 136, 153]),
+    ("lightsteelblue", [176, 196, 222]),
+    ("lightyellow", [255, 255, 224]),
+    ("lime", [0, 255, 0]),
+    ("limegreen", [50, 205, 50]),
+    ("linen", [250, 240, 230]),
+    ("magenta", [255, 0, 255]),
+    ("maroon", [128, 0, 0]),
+    ("mediumaquamarine", [102, 205, 170]),
+    ("mediumblue", [0, 0, 205]),
+    ("mediumorchid", [186, 85, 211]),
+    ("mediumpurple", [147, 112, 219]),
+    ("mediumseagreen", [60, 179, 113]),
+    ("mediumslateblue", [123, 104, 238]),
+    ("mediumspringgreen", [0, 250, 154]),
+    ("mediumturquoise", [72, 209, 204]),
+    ("mediumvioletred", [199, 21, 133]),
+    ("midnightblue", [25, 25, 112]),
+    ("mintcream", [245, 255, 250]),
+    ("mistyrose", [255, 228, 225]),
+    ("moccasin", [255, 228, 181]),
+    ("navajowhite", [255, 222, 173]),
+    ("navy", [0, 0, 128]),
+    ("oldlace", [253, 245, 230]),
+    ("olive", [128, 128, 0]),
+    ("olivedrab", [107, 142, 35]),
+    ("orange", [255, 165, 0]),
+    ("orangered", [255, 69, 0]),
+    ("orchid", [218, 112, 214]),
+    ("palegoldenrod", [238, 232, 170]),
+    ("palegreen", [152, 251, 152]),
+    ("paleturquoise", [175, 238, 238]),
+    ("palevioletred", [219, 112, 147]),
+    ("papayawhip", [255, 239, 213]),
+    ("peachpuff", [255, 218, 185]),
+    ("peru", [205, 133, 63]),
+    ("pink", [255, 192, 203]),
+    ("plum", [221, 160, 221]),
+    ("powderblue", [176, 224, 230]),
+    ("purple", [128, 0, 128]),
+    ("rebeccapurple", [102, 51, 153]),
+    ("red", [255, 0, 0]),
+    ("rosybrown", [188, 143, 143]),
+    ("royalblue", [65, 105, 225]),
+    ("saddlebrown", [139, 69, 19]),
+    ("salmon", [250, 128, 114]),
+    ("sandybrown", [244, 164, 96]),
+    ("seagreen", [46, 139, 87]),
+    ("seashell", [255, 245, 238]),
+    ("sienna", [160, 82, 45]),
+    ("silver", [192, 192, 192]),
+    ("skyblue", [135, 206, 235]),
+    ("slateblue", [106, 90, 205]),
+    ("slategray", [112, 128, 144]),
+    ("slategrey", [112, 128, 144]),
+    ("snow", [255, 250, 250]),
+    ("springgreen", [0, 255, 127]),
+    ("steelblue", [70, 130, 180]),
+    ("tan", [210, 180, 140]),
+    ("teal", [0, 128, 128]),
+    ("thistle", [216, 191, 216]),
+    ("tomato", [255, 99, 71]),
+    ("turquoise", [64, 224, 208]),
+    ("violet", [238, 130, 238]),
+    ("wheat", [245, 222, 179]),
+    ("white", [255, 255, 255]),
+    ("whitesmoke", [245, 245, 245]),
+    ("yellow", [255, 255, 0]),
+    ("yellowgreen", [154, 205, 50]),
+];
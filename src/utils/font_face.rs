@@ -1,4 +1,4 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 use wasm_bindgen::prelude::*;
 
@@ -6,8 +6,8 @@ use wasm_bindgen::prelude::*;
 #[wasm_bindgen]
 #[derive(Clone, Debug, PartialEq)]
 pub struct FontFace {
-    /// The data of the font face.
-    data: Rc<Vec<u8>>,
+    /// The data of the font face, shared between clones and with the font databases it's loaded into.
+    data: Arc<[u8]>,
 }
 
 #[wasm_bindgen]
@@ -18,7 +18,7 @@ impl FontFace {
         #[wasm_bindgen(param_description = "The data of the font face.")]
         data: Vec<u8>,
     ) -> FontFace {
-        FontFace { data: Rc::new(data) }
+        FontFace { data: Arc::from(data) }
     }
 
     /// Returns the data of the font face.
@@ -32,4 +32,49 @@ impl FontFace {
     pub fn copy(&self) -> FontFace {
         self.clone()
     }
+
+    /// Returns the font faces used when none are given, which are the bundled fonts if the `bundled-fonts` feature is enabled and none otherwise. The fonts installed on the system are never included, an engine opts into them with TypstEngine::with_system_fonts.
+    #[wasm_bindgen(return_description = "The default font faces.")]
+    pub fn defaults() -> Vec<FontFace> {
+        #[cfg(feature = "bundled-fonts")]
+        return FontFace::bundled();
+        #[cfg(not(feature = "bundled-fonts"))]
+        Vec::new()
+    }
+}
+
+impl FontFace {
+    /// Returns the data of the font face without copying it.
+    pub(crate) fn shared_data(&self) -> Arc<[u8]> {
+        Arc::clone(&self.data)
+    }
+}
+
+#[cfg(feature = "bundled-fonts")]
+impl FontFace {
+    /// Returns the bundled font faces, which are the fonts Typst ships with in typst-assets, such as New Computer Modern Math and Libertinus Serif.
+    pub fn bundled() -> Vec<FontFace> {
+        // The fonts are only copied once, clones share their data.
+        static BUNDLED: std::sync::OnceLock<Vec<FontFace>> = std::sync::OnceLock::new();
+        BUNDLED.get_or_init(|| {
+            typst_assets::fonts().map(|data| FontFace { data: Arc::from(data) }).collect()
+        }).clone()
+    }
+}
+
+#[cfg(all(feature = "system-fonts", not(target_arch = "wasm32")))]
+impl FontFace {
+    /// Returns the font faces installed on the system, found with fontdb. Only the first face of each font collection is returned.
+    pub fn system() -> Vec<FontFace> {
+        // The system is only scanned once, clones share their data.
+        static SYSTEM: std::sync::OnceLock<Vec<FontFace>> = std::sync::OnceLock::new();
+        SYSTEM.get_or_init(|| {
+            let mut database = fontdb::Database::new();
+            database.load_system_fonts();
+            database.faces()
+                .filter(|face| face.index == 0)
+                .filter_map(|face| database.with_face_data(face.id, |data, _| FontFace { data: Arc::from(data) }))
+                .collect()
+        }).clone()
+    }
 }
\ No newline at end of file
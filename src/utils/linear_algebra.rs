@@ -2,7 +2,7 @@ use std::ops::{Mul, MulAssign};
 
 use wasm_bindgen::prelude::*;
 
-use super::point2d::{Path2D, Point2D};
+use super::{interpolation::lerp, point2d::{Path2D, Point2D}};
 
 /// A TransformationMatrix is a 2D transformation matrix following the CSS matrix transform format.
 #[wasm_bindgen]
@@ -134,14 +134,35 @@ impl TransformationMatrix {
             f: (self.b * self.e - self.a * self.f) / det,
         }
     }
+
+    /// Linearly interpolates the components of two TransformationMatrix objects.
+    #[wasm_bindgen(return_description = "The interpolated matrix.")]
+    pub fn lerp(
+        #[wasm_bindgen(param_description = "The start matrix.")]
+        matrix1: &TransformationMatrix,
+        #[wasm_bindgen(param_description = "The end matrix.")]
+        matrix2: &TransformationMatrix,
+        #[wasm_bindgen(param_description = "The progress value.")]
+        t: f32
+    ) -> TransformationMatrix {
+        TransformationMatrix {
+            a: lerp(matrix1.a, matrix2.a, t),
+            b: lerp(matrix1.b, matrix2.b, t),
+            c: lerp(matrix1.c, matrix2.c, t),
+            d: lerp(matrix1.d, matrix2.d, t),
+            e: lerp(matrix1.e, matrix2.e, t),
+            f: lerp(matrix1.f, matrix2.f, t),
+        }
+    }
 }
 
 impl TransformationMatrix {
+    /// Converts a usvg transform. usvg's `ky` is the vertical skew that multiplies x, which is `b` in the CSS matrix format, and its `kx` multiplies y, which is `c`.
     pub fn from_svg_transform(transform_matrix: usvg::Transform) -> TransformationMatrix {
         TransformationMatrix {
             a: transform_matrix.sx,
-            b: transform_matrix.kx,
-            c: transform_matrix.ky,
+            b: transform_matrix.ky,
+            c: transform_matrix.kx,
             d: transform_matrix.sy,
             e: transform_matrix.tx,
             f: transform_matrix.ty,
@@ -7,4 +7,8 @@ pub mod point2d;
 pub mod bounding_box;
 pub mod font_face;
 pub mod image_library;
-pub mod interval;
\ No newline at end of file
+pub mod interval;
+pub mod selector;
+pub mod point3d;
+pub mod color_space;
+pub mod tracing;
\ No newline at end of file
@@ -0,0 +1,271 @@
+use std::{ops::{Add, Mul, Neg, Sub}, rc::Rc};
+
+use wasm_bindgen::prelude::*;
+
+use crate::utils::interpolation::lerp;
+
+/// A 3D point.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Point3D {
+    /// The x-coordinate of the point.
+    pub x: f32,
+    /// The y-coordinate of the point.
+    pub y: f32,
+    /// The z-coordinate of the point.
+    pub z: f32,
+}
+
+#[wasm_bindgen]
+impl Point3D {
+    /// Creates a new Point3D with the given coordinates.
+    #[wasm_bindgen(constructor, return_description = "A 3D point.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The x-coordinate of the point.")]
+        x: f32,
+        #[wasm_bindgen(param_description = "The y-coordinate of the point.")]
+        y: f32,
+        #[wasm_bindgen(param_description = "The z-coordinate of the point.")]
+        z: f32,
+    ) -> Point3D {
+        Point3D { x, y, z }
+    }
+    /// Linearly interpolates between two Point3D objects.
+    #[wasm_bindgen(return_description = "The interpolated point.")]
+    pub fn lerp(
+        #[wasm_bindgen(param_description = "The start point.")]
+        point1: &Point3D,
+        #[wasm_bindgen(param_description = "The end point.")]
+        point2: &Point3D,
+        #[wasm_bindgen(param_description = "The progress value.")]
+        t: f32,
+    ) -> Point3D {
+        Point3D {
+            x: lerp(point1.x, point2.x, t),
+            y: lerp(point1.y, point2.y, t),
+            z: lerp(point1.z, point2.z, t),
+        }
+    }
+    /// Clones the point.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn copy(&self) -> Point3D {
+        *self
+    }
+    /// Returns the dot product of the point with another point, seen as vectors.
+    #[wasm_bindgen(return_description = "The dot product of the points.")]
+    pub fn dot(
+        &self,
+        #[wasm_bindgen(param_description = "The other point.")]
+        other: &Point3D,
+    ) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+    /// Returns the cross product of the point with another point, seen as vectors.
+    #[wasm_bindgen(return_description = "The cross product of the points.")]
+    pub fn cross(
+        &self,
+        #[wasm_bindgen(param_description = "The other point.")]
+        other: &Point3D,
+    ) -> Point3D {
+        Point3D {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+    /// Returns the magnitude of the point, seen as a vector.
+    #[wasm_bindgen(getter, return_description = "The magnitude of the point.")]
+    pub fn magnitude(&self) -> f32 {
+        self.dot(self).sqrt()
+    }
+    /// Returns the point scaled to magnitude 1, or the point itself if its magnitude is 0.
+    #[wasm_bindgen(getter, return_description = "The normalized point.")]
+    pub fn normalized(&self) -> Point3D {
+        let magnitude = self.magnitude();
+        if magnitude == 0.0 {
+            return *self;
+        }
+        *self * (1.0 / magnitude)
+    }
+    /// Returns the distance between the point and another point.
+    #[wasm_bindgen(return_description = "The distance between the points.")]
+    pub fn distance(
+        &self,
+        #[wasm_bindgen(param_description = "The other point.")]
+        other: &Point3D,
+    ) -> f32 {
+        (*self - *other).magnitude()
+    }
+    /// Rotates the point around the x-axis.
+    #[wasm_bindgen(return_description = "The rotated point.")]
+    pub fn rotate_x(
+        &self,
+        #[wasm_bindgen(param_description = "The angle in radians.")]
+        angle: f32,
+    ) -> Point3D {
+        let (sin, cos) = angle.sin_cos();
+        Point3D::new(self.x, self.y * cos - self.z * sin, self.y * sin + self.z * cos)
+    }
+    /// Rotates the point around the y-axis.
+    #[wasm_bindgen(return_description = "The rotated point.")]
+    pub fn rotate_y(
+        &self,
+        #[wasm_bindgen(param_description = "The angle in radians.")]
+        angle: f32,
+    ) -> Point3D {
+        let (sin, cos) = angle.sin_cos();
+        Point3D::new(self.x * cos + self.z * sin, self.y, -self.x * sin + self.z * cos)
+    }
+    /// Rotates the point around the z-axis.
+    #[wasm_bindgen(return_description = "The rotated point.")]
+    pub fn rotate_z(
+        &self,
+        #[wasm_bindgen(param_description = "The angle in radians.")]
+        angle: f32,
+    ) -> Point3D {
+        let (sin, cos) = angle.sin_cos();
+        Point3D::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos, self.z)
+    }
+    /// Rotates the point around the x, y and z axes, in that order.
+    #[wasm_bindgen(return_description = "The rotated point.")]
+    pub fn rotate(
+        &self,
+        #[wasm_bindgen(param_description = "The angles in radians around each axis.")]
+        angles: &Point3D,
+    ) -> Point3D {
+        self.rotate_x(angles.x).rotate_y(angles.y).rotate_z(angles.z)
+    }
+    /// Checks if the point has finite coordinates.
+    #[wasm_bindgen(return_description = "A boolean indicating whether the point is finite.")]
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+}
+
+impl Add<Point3D> for Point3D {
+    type Output = Point3D;
+
+    fn add(self, other: Point3D) -> Point3D {
+        Point3D::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub<Point3D> for Point3D {
+    type Output = Point3D;
+
+    fn sub(self, other: Point3D) -> Point3D {
+        Point3D::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Mul<f32> for Point3D {
+    type Output = Point3D;
+
+    fn mul(self, scalar: f32) -> Point3D {
+        Point3D::new(self.x * scalar, self.y * scalar, self.z * scalar)
+    }
+}
+
+impl Neg for Point3D {
+    type Output = Point3D;
+
+    fn neg(self) -> Point3D {
+        Point3D::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/// A Path3D is a 3D path made of cubic Bézier curves, where every four points are the start anchor, first control, second control and end anchor of a curve.
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Path3D {
+    /// The points of the path.
+    points: Rc<Vec<Point3D>>,
+}
+
+#[wasm_bindgen]
+impl Path3D {
+    /// Creates a new Path3D with the given points.
+    #[wasm_bindgen(constructor, return_description = "A 3D path.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The points of the path.")]
+        points: Vec<Point3D>,
+    ) -> Path3D {
+        Path3D { points: Rc::new(points) }
+    }
+    /// Creates a closed Path3D of straight segments through the given vertices.
+    #[wasm_bindgen(return_description = "A closed polygonal 3D path.")]
+    pub fn polygon(
+        #[wasm_bindgen(param_description = "The vertices of the polygon.")]
+        vertices: Vec<Point3D>,
+    ) -> Path3D {
+        let mut points = Vec::with_capacity(vertices.len() * 4);
+        for i in 0..vertices.len() {
+            let start = vertices[i];
+            let end = vertices[(i + 1) % vertices.len()];
+            points.extend([start, Point3D::lerp(&start, &end, 1.0 / 3.0), Point3D::lerp(&start, &end, 2.0 / 3.0), end]);
+        }
+        Path3D { points: Rc::new(points) }
+    }
+    /// Clones the Path3D.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn copy(&self) -> Path3D {
+        self.clone()
+    }
+    /// Returns the Point3Ds of the Path3D.
+    #[wasm_bindgen(getter, return_description = "The points of the path.")]
+    pub fn points(&self) -> Vec<Point3D> {
+        self.points.to_vec()
+    }
+    /// Returns whether the Path3D is empty.
+    #[wasm_bindgen(getter, return_description = "A boolean indicating whether the path is empty.")]
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+    /// Returns the number of points of the Path3D.
+    #[wasm_bindgen(getter, return_description = "The number of points of the path.")]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+    /// Returns the anchors of the Path3D, the start anchors of its curves followed by the last end anchor.
+    #[wasm_bindgen(getter, return_description = "The anchors of the path.")]
+    pub fn anchors(&self) -> Vec<Point3D> {
+        let mut anchors = self.points.iter().step_by(4).copied().collect::<Vec<Point3D>>();
+        if let Some(last) = self.points.last() {
+            anchors.push(*last);
+        }
+        anchors
+    }
+    /// Returns the average of the points of the Path3D.
+    #[wasm_bindgen(getter, return_description = "The centroid of the path.")]
+    pub fn centroid(&self) -> Option<Point3D> {
+        if self.points.is_empty() {
+            return None;
+        }
+        let sum = self.points.iter().fold(Point3D::default(), |sum, point| sum + *point);
+        Some(sum * (1.0 / self.points.len() as f32))
+    }
+    /// Returns the unit normal of the Path3D computed with Newell's method, which works for any planar or nearly planar closed path.
+    #[wasm_bindgen(getter, return_description = "The unit normal of the path.")]
+    pub fn normal(&self) -> Option<Point3D> {
+        let anchors = self.anchors();
+        let mut normal = Point3D::default();
+        for i in 0..anchors.len() {
+            let current = anchors[i];
+            let next = anchors[(i + 1) % anchors.len()];
+            normal.x += (current.y - next.y) * (current.z + next.z);
+            normal.y += (current.z - next.z) * (current.x + next.x);
+            normal.z += (current.x - next.x) * (current.y + next.y);
+        }
+        if normal.magnitude() == 0.0 {
+            return None;
+        }
+        Some(normal.normalized())
+    }
+}
+
+impl Path3D {
+    /// Returns a new Path3D with a function applied to every point.
+    pub fn map(&self, function: impl Fn(Point3D) -> Point3D) -> Path3D {
+        Path3D { points: Rc::new(self.points.iter().map(|point| function(*point)).collect()) }
+    }
+}
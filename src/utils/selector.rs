@@ -0,0 +1,212 @@
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+
+/// The part of a selector segment that is matched against the name of a child.
+#[derive(Clone, Debug, PartialEq)]
+enum NamePattern {
+    /// Matches any child, named or not.
+    Any,
+    /// Matches every descendant, including the object itself.
+    Recursive,
+    /// Matches the children whose name matches the glob, where `*` matches any sequence of characters.
+    Glob(Rc<String>),
+}
+
+/// A slice of the matches of a selector segment, with negative indices counting from the end.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum IndexRange {
+    /// A single match.
+    Single(i64),
+    /// A half-open range of matches.
+    Range(Option<i64>, Option<i64>),
+}
+
+/// A segment of a selector, the text between two slashes.
+#[derive(Clone, Debug, PartialEq)]
+struct SelectorSegment {
+    /// The name pattern of the segment.
+    pattern: NamePattern,
+    /// The matches to keep, if any.
+    range: Option<IndexRange>,
+}
+
+/// A Selector is a path-like query over the children of a VectorObject, such as `x_axis/tick[3]` or `glyph[2..5]`.
+/// Segments are separated by slashes, a single asterisk matches any child, a double asterisk matches any depth, and `[i]` or `[a..b]` keeps some of the matches of each parent.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Selector {
+    /// The source of the selector.
+    source: Rc<String>,
+    /// The parsed segments of the selector.
+    segments: Rc<Vec<SelectorSegment>>,
+}
+
+#[wasm_bindgen]
+impl Selector {
+    /// Creates a new Selector by parsing a selector string.
+    #[wasm_bindgen(constructor, return_description = "A new selector.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The selector string, such as `x_axis/tick[3]` or `glyph[2..5]`.")]
+        source: String,
+    ) -> Result<Selector, JsError> {
+        Selector::parse(&source).map_err(|error| JsError::new(&error))
+    }
+    /// Returns the source of the selector.
+    #[wasm_bindgen(getter, return_description = "The source of the selector.")]
+    pub fn source(&self) -> String {
+        self.source.to_string()
+    }
+    /// Clones the selector.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn copy(&self) -> Selector {
+        self.clone()
+    }
+    /// Checks if a name matches a glob pattern, where `*` matches any sequence of characters.
+    #[wasm_bindgen(return_description = "Whether the name matches the pattern.")]
+    pub fn glob_matches(
+        #[wasm_bindgen(param_description = "The glob pattern.")]
+        pattern: &str,
+        #[wasm_bindgen(param_description = "The name to check.")]
+        name: &str,
+    ) -> bool {
+        let parts = pattern.split('*').collect::<Vec<&str>>();
+        if parts.len() == 1 {
+            return pattern == name;
+        }
+        let first = parts[0];
+        let last = parts[parts.len() - 1];
+        if name.len() < first.len() + last.len() {
+            return false;
+        }
+        // The name is long enough for both ends, so the rest starts before the suffix and both slices are at character boundaries.
+        let Some(mut rest) = name.strip_prefix(first).and_then(|rest| rest.strip_suffix(last)) else {
+            return false;
+        };
+        for part in &parts[1..parts.len() - 1] {
+            match rest.find(part) {
+                Some(position) => rest = &rest[position + part.len()..],
+                None => return false,
+            }
+        }
+        true
+    }
+}
+
+impl Selector {
+    /// Parses a selector string.
+    pub fn parse(source: &str) -> Result<Selector, String> {
+        let trimmed = source.trim().trim_matches('/');
+        if trimmed.is_empty() {
+            return Err("The selector must not be empty.".to_string());
+        }
+        let segments = trimmed
+            .split('/')
+            .map(|segment| Selector::parse_segment(segment.trim(), source))
+            .collect::<Result<Vec<SelectorSegment>, String>>()?;
+        Ok(Selector { source: Rc::new(source.to_string()), segments: Rc::new(segments) })
+    }
+    fn parse_segment(segment: &str, source: &str) -> Result<SelectorSegment, String> {
+        if segment.is_empty() {
+            return Err(format!("Empty segment in selector \"{}\".", source));
+        }
+        let (name, range) = match segment.find('[') {
+            Some(start) => {
+                if !segment.ends_with(']') {
+                    return Err(format!("Unclosed index in selector segment \"{}\".", segment));
+                }
+                let range = Selector::parse_range(&segment[start + 1..segment.len() - 1], segment)?;
+                (&segment[..start], Some(range))
+            }
+            None => (segment, None),
+        };
+        let pattern = match name {
+            "**" => NamePattern::Recursive,
+            "*" => NamePattern::Any,
+            "" => return Err(format!("Missing name in selector segment \"{}\".", segment)),
+            name => NamePattern::Glob(Rc::new(name.to_string())),
+        };
+        if pattern == NamePattern::Recursive && range.is_some() {
+            return Err(format!("The recursive wildcard can't be indexed in selector segment \"{}\".", segment));
+        }
+        Ok(SelectorSegment { pattern, range })
+    }
+    fn parse_range(range: &str, segment: &str) -> Result<IndexRange, String> {
+        let parse_index = |index: &str| -> Result<Option<i64>, String> {
+            let index = index.trim();
+            if index.is_empty() {
+                return Ok(None);
+            }
+            index.parse::<i64>().map(Some).map_err(|_| format!("Invalid index \"{}\" in selector segment \"{}\".", index, segment))
+        };
+        match range.split_once("..") {
+            Some((start, end)) => Ok(IndexRange::Range(parse_index(start)?, parse_index(end)?)),
+            None => parse_index(range)?
+                .map(IndexRange::Single)
+                .ok_or_else(|| format!("Missing index in selector segment \"{}\".", segment)),
+        }
+    }
+    /// Returns the index paths of the descendants of the object tree that match the selector, relative to the root.
+    /// Each path is a list of child indices, starting from the children of the root.
+    pub fn match_paths(&self, children_of: &dyn Fn(&[usize]) -> Vec<Option<String>>) -> Vec<Vec<usize>> {
+        let mut current: Vec<Vec<usize>> = vec![vec![]];
+        for segment in self.segments.iter() {
+            let mut next: Vec<Vec<usize>> = Vec::new();
+            for path in current.iter() {
+                let candidates = match &segment.pattern {
+                    NamePattern::Recursive => {
+                        let mut descendants = vec![path.clone()];
+                        Selector::collect_descendants(path, children_of, &mut descendants);
+                        descendants
+                    }
+                    NamePattern::Any => (0..children_of(path).len())
+                        .map(|index| [path.as_slice(), &[index]].concat())
+                        .collect(),
+                    NamePattern::Glob(glob) => children_of(path)
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, name)| name.as_ref().is_some_and(|name| Selector::glob_matches(glob, name)))
+                        .map(|(index, _)| [path.as_slice(), &[index]].concat())
+                        .collect(),
+                };
+                for candidate in Selector::slice_matches(candidates, segment.range) {
+                    if !next.contains(&candidate) {
+                        next.push(candidate);
+                    }
+                }
+            }
+            current = next;
+        }
+        current.retain(|path| !path.is_empty());
+        current
+    }
+    fn collect_descendants(path: &[usize], children_of: &dyn Fn(&[usize]) -> Vec<Option<String>>, descendants: &mut Vec<Vec<usize>>) {
+        for index in 0..children_of(path).len() {
+            let child = [path, &[index]].concat();
+            descendants.push(child.clone());
+            Selector::collect_descendants(&child, children_of, descendants);
+        }
+    }
+    fn slice_matches(matches: Vec<Vec<usize>>, range: Option<IndexRange>) -> Vec<Vec<usize>> {
+        let length = matches.len() as i64;
+        let resolve = |index: i64| if index < 0 { length + index } else { index };
+        match range {
+            None => matches,
+            Some(IndexRange::Single(index)) => {
+                let index = resolve(index);
+                if index < 0 || index >= length {
+                    return Vec::new();
+                }
+                vec![matches[index as usize].clone()]
+            }
+            Some(IndexRange::Range(start, end)) => {
+                let start = resolve(start.unwrap_or(0)).clamp(0, length) as usize;
+                let end = resolve(end.unwrap_or(length)).clamp(0, length) as usize;
+                if start >= end {
+                    return Vec::new();
+                }
+                matches[start..end].to_vec()
+            }
+        }
+    }
+}
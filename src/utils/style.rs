@@ -1,13 +1,13 @@
 use std::rc::Rc;
 
-use image::{codecs::png::PngEncoder, guess_format, load_from_memory_with_format, ImageBuffer, RgbaImage};
+use image::{codecs::png::PngEncoder, guess_format, imageops::{self, FilterType}, load_from_memory_with_format, ImageBuffer, RgbaImage};
 use usvg::{Opacity, Paint};
 use wasm_bindgen::prelude::*;
 use base64::{prelude::BASE64_STANDARD, Engine};
 
-use crate::utils::interpolation::{inverse_lerp, lerp};
+use crate::{objects::{geometry::{arc::Circle, line::Line}, vector_object::{VectorObject, VectorObjectBuilder}}, utils::interpolation::{inverse_lerp, lerp}};
 
-use super::{console::log, point2d::Point2D};
+use super::{color_space::{named_color, ColorSpace}, console::log, linear_algebra::TransformationMatrix, point2d::{Path2D, Point2D}};
 
 /// A color with red, green, blue, and alpha components.
 #[wasm_bindgen]
@@ -60,29 +60,287 @@ impl Color {
     pub fn default_color() -> Color {
         Color::default()
     }
-    /// Linearly interpolates between two Colors given a progress value.
-    #[wasm_bindgen]
+    /// Parses a Color from a hexadecimal string like `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`. The leading `#` is optional.
+    #[wasm_bindgen(return_description = "The parsed color.")]
+    pub fn from_hex(
+        #[wasm_bindgen(param_description = "The hexadecimal string.")]
+        hex: &str
+    ) -> Result<Color, JsError> {
+        Color::parse_hex(hex).map_err(|e| JsError::new(&e))
+    }
+    /// Parses a Color from a CSS color string, which can be a hexadecimal color, an `rgb()`, `rgba()`, `hsl()` or `hsla()` function, a named color or `transparent`.
+    #[wasm_bindgen(return_description = "The parsed color.")]
+    pub fn from_css(
+        #[wasm_bindgen(param_description = "The CSS color string.")]
+        css: &str
+    ) -> Result<Color, JsError> {
+        Color::parse(css).map_err(|e| JsError::new(&e))
+    }
+    /// Returns the Color as a hexadecimal string `#rrggbb`, or `#rrggbbaa` if it isn't fully opaque.
+    #[wasm_bindgen(return_description = "The hexadecimal string of the color.")]
+    pub fn to_hex(&self) -> String {
+        let hex = format!("#{:02x}{:02x}{:02x}", self.red, self.green, self.blue);
+        if self.alpha < 1.0 {
+            format!("{}{:02x}", hex, (self.alpha.clamp(0.0, 1.0) * 255.0).round() as u8)
+        } else {
+            hex
+        }
+    }
+    /// Returns the Color as a CSS `rgba()` string.
+    #[wasm_bindgen(return_description = "The CSS string of the color.")]
+    pub fn to_css(&self) -> String {
+        format!("rgba({}, {}, {}, {})", self.red, self.green, self.blue, self.alpha)
+    }
+    /// Creates a Color from the components of a color space, see `to_color_space` for their meaning.
+    #[wasm_bindgen(return_description = "A new color.")]
+    pub fn from_color_space(
+        #[wasm_bindgen(param_description = "The three components of the color in the color space.")]
+        components: Vec<f32>,
+        #[wasm_bindgen(param_description = "The color space of the components.")]
+        color_space: ColorSpace,
+        #[wasm_bindgen(param_description = "The alpha component of the color, default is 1.")]
+        alpha: Option<f32>
+    ) -> Result<Color, JsError> {
+        if components.len() != 3 {
+            return Err(JsError::new("A color must have exactly three components."));
+        }
+        Ok(Color::from_components([components[0], components[1], components[2]], color_space, alpha.unwrap_or(1.0)))
+    }
+    /// Returns the components of the Color in a color space. Hues are in degrees, and saturation, lightness and value are between 0 and 1. sRGB components are between 0 and 1.
+    #[wasm_bindgen(return_description = "The three components of the color in the color space.")]
+    pub fn to_color_space(
+        &self,
+        #[wasm_bindgen(param_description = "The color space to convert the color to.")]
+        color_space: ColorSpace
+    ) -> Vec<f32> {
+        self.components(color_space).to_vec()
+    }
+    /// Creates a Color from a hue in degrees and saturation and lightness between 0 and 1.
+    #[wasm_bindgen(return_description = "A new color.")]
+    pub fn from_hsl(
+        #[wasm_bindgen(param_description = "The hue of the color in degrees.")]
+        hue: f32,
+        #[wasm_bindgen(param_description = "The saturation of the color, between 0 and 1.")]
+        saturation: f32,
+        #[wasm_bindgen(param_description = "The lightness of the color, between 0 and 1.")]
+        lightness: f32,
+        #[wasm_bindgen(param_description = "The alpha component of the color, default is 1.")]
+        alpha: Option<f32>
+    ) -> Color {
+        Color::from_components([hue, saturation, lightness], ColorSpace::Hsl, alpha.unwrap_or(1.0))
+    }
+    /// Returns the hue in degrees, saturation and lightness of the Color.
+    #[wasm_bindgen(return_description = "The hue, saturation and lightness of the color.")]
+    pub fn to_hsl(&self) -> Vec<f32> {
+        self.to_color_space(ColorSpace::Hsl)
+    }
+    /// Creates a Color from a hue in degrees and saturation and value between 0 and 1.
+    #[wasm_bindgen(return_description = "A new color.")]
+    pub fn from_hsv(
+        #[wasm_bindgen(param_description = "The hue of the color in degrees.")]
+        hue: f32,
+        #[wasm_bindgen(param_description = "The saturation of the color, between 0 and 1.")]
+        saturation: f32,
+        #[wasm_bindgen(param_description = "The value of the color, between 0 and 1.")]
+        value: f32,
+        #[wasm_bindgen(param_description = "The alpha component of the color, default is 1.")]
+        alpha: Option<f32>
+    ) -> Color {
+        Color::from_components([hue, saturation, value], ColorSpace::Hsv, alpha.unwrap_or(1.0))
+    }
+    /// Returns the hue in degrees, saturation and value of the Color.
+    #[wasm_bindgen(return_description = "The hue, saturation and value of the color.")]
+    pub fn to_hsv(&self) -> Vec<f32> {
+        self.to_color_space(ColorSpace::Hsv)
+    }
+    /// Creates a Color from linear light sRGB components between 0 and 1.
+    #[wasm_bindgen(return_description = "A new color.")]
+    pub fn from_linear_rgb(
+        #[wasm_bindgen(param_description = "The linear red component, between 0 and 1.")]
+        red: f32,
+        #[wasm_bindgen(param_description = "The linear green component, between 0 and 1.")]
+        green: f32,
+        #[wasm_bindgen(param_description = "The linear blue component, between 0 and 1.")]
+        blue: f32,
+        #[wasm_bindgen(param_description = "The alpha component of the color, default is 1.")]
+        alpha: Option<f32>
+    ) -> Color {
+        Color::from_components([red, green, blue], ColorSpace::LinearRgb, alpha.unwrap_or(1.0))
+    }
+    /// Returns the linear light sRGB components of the Color.
+    #[wasm_bindgen(return_description = "The linear red, green and blue components of the color.")]
+    pub fn to_linear_rgb(&self) -> Vec<f32> {
+        self.to_color_space(ColorSpace::LinearRgb)
+    }
+    /// Creates a Color from Oklab lightness, a and b.
+    #[wasm_bindgen(return_description = "A new color.")]
+    pub fn from_oklab(
+        #[wasm_bindgen(param_description = "The perceptual lightness, between 0 and 1.")]
+        lightness: f32,
+        #[wasm_bindgen(param_description = "The green to red axis.")]
+        a: f32,
+        #[wasm_bindgen(param_description = "The blue to yellow axis.")]
+        b: f32,
+        #[wasm_bindgen(param_description = "The alpha component of the color, default is 1.")]
+        alpha: Option<f32>
+    ) -> Color {
+        Color::from_components([lightness, a, b], ColorSpace::Oklab, alpha.unwrap_or(1.0))
+    }
+    /// Returns the Oklab lightness, a and b of the Color.
+    #[wasm_bindgen(return_description = "The Oklab lightness, a and b of the color.")]
+    pub fn to_oklab(&self) -> Vec<f32> {
+        self.to_color_space(ColorSpace::Oklab)
+    }
+    /// Creates a Color from Oklch lightness, chroma and hue in degrees.
+    #[wasm_bindgen(return_description = "A new color.")]
+    pub fn from_oklch(
+        #[wasm_bindgen(param_description = "The perceptual lightness, between 0 and 1.")]
+        lightness: f32,
+        #[wasm_bindgen(param_description = "The chroma of the color.")]
+        chroma: f32,
+        #[wasm_bindgen(param_description = "The hue of the color in degrees.")]
+        hue: f32,
+        #[wasm_bindgen(param_description = "The alpha component of the color, default is 1.")]
+        alpha: Option<f32>
+    ) -> Color {
+        Color::from_components([lightness, chroma, hue], ColorSpace::Oklch, alpha.unwrap_or(1.0))
+    }
+    /// Returns the Oklch lightness, chroma and hue in degrees of the Color.
+    #[wasm_bindgen(return_description = "The Oklch lightness, chroma and hue of the color.")]
+    pub fn to_oklch(&self) -> Vec<f32> {
+        self.to_color_space(ColorSpace::Oklch)
+    }
+    /// Interpolates between two Colors given a progress value, in a given color space.
+    #[wasm_bindgen(return_description = "The interpolated color.")]
     pub fn lerp(
         #[wasm_bindgen(param_description = "The start color.")]
         color1: &Color,
         #[wasm_bindgen(param_description = "The end color.")]
         color2: &Color,
         #[wasm_bindgen(param_description = "The progress value.")]
-        t: f32
+        t: f32,
+        #[wasm_bindgen(param_description = "The color space to interpolate in, default is sRGB.")]
+        color_space: Option<ColorSpace>
     ) -> Color {
-        let red = lerp(color1.red as f32, color2.red as f32, t) as u8;
-        let green = lerp(color1.green as f32, color2.green as f32, t) as u8;
-        let blue = lerp(color1.blue as f32, color2.blue as f32, t) as u8;
         let alpha = lerp(color1.alpha, color2.alpha, t);
+        let color_space = color_space.unwrap_or_default();
+        if color_space == ColorSpace::Srgb {
+            let red = lerp(color1.red as f32, color2.red as f32, t) as u8;
+            let green = lerp(color1.green as f32, color2.green as f32, t) as u8;
+            let blue = lerp(color1.blue as f32, color2.blue as f32, t) as u8;
+            return Color {
+                red,
+                green,
+                blue,
+                alpha,
+            };
+        }
+        let components = color_space.lerp(color1.components(color_space), color2.components(color_space), t);
+        Color::from_components(components, color_space, alpha)
+    }
+}
+
+impl Color {
+    /// Returns the components of the color in a color space.
+    pub fn components(&self, color_space: ColorSpace) -> [f32; 3] {
+        color_space.encode([self.red as f32 / 255.0, self.green as f32 / 255.0, self.blue as f32 / 255.0])
+    }
+    /// Creates a color from the components of a color space, clamping it to the sRGB gamut.
+    pub fn from_components(components: [f32; 3], color_space: ColorSpace, alpha: f32) -> Color {
+        let [red, green, blue] = color_space.decode(components).map(|value| (value.clamp(0.0, 1.0) * 255.0).round() as u8);
         Color {
             red,
             green,
             blue,
-            alpha,
+            alpha: alpha.clamp(0.0, 1.0),
+        }
+    }
+    /// Parses a color from a hexadecimal string.
+    pub fn parse_hex(hex: &str) -> Result<Color, String> {
+        let digits = hex.trim().trim_start_matches('#');
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("Invalid hexadecimal color: {}.", hex));
+        }
+        let expanded = match digits.len() {
+            3 | 4 => digits.chars().flat_map(|c| [c, c]).collect::<String>(),
+            6 | 8 => digits.to_string(),
+            _ => return Err(format!("A hexadecimal color must have 3, 4, 6 or 8 digits: {}.", hex)),
+        };
+        let channel = |i: usize| u8::from_str_radix(&expanded[2 * i..2 * i + 2], 16).unwrap();
+        let alpha = if expanded.len() == 8 { channel(3) as f32 / 255.0 } else { 1.0 };
+        Ok(Color::new(channel(0), channel(1), channel(2), alpha))
+    }
+    /// Parses a color from a CSS color string.
+    pub fn parse(css: &str) -> Result<Color, String> {
+        let css = css.trim().to_ascii_lowercase();
+        if css.starts_with('#') {
+            return Color::parse_hex(&css);
+        }
+        if css == "transparent" {
+            return Ok(Color::default());
+        }
+        if let Some([red, green, blue]) = named_color(&css) {
+            return Ok(Color::new(red, green, blue, 1.0));
+        }
+        let (function, arguments) = css
+            .strip_suffix(')')
+            .and_then(|css| css.split_once('('))
+            .ok_or(format!("Unknown color: {}.", css))?;
+        let arguments = arguments
+            .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+            .filter(|argument| !argument.is_empty())
+            .collect::<Vec<&str>>();
+        if arguments.len() != 3 && arguments.len() != 4 {
+            return Err(format!("A color function must have 3 or 4 arguments: {}.", css));
+        }
+        let alpha = match arguments.get(3) {
+            Some(argument) => parse_css_number(argument, 1.0)?,
+            None => 1.0,
+        };
+        match function.trim() {
+            "rgb" | "rgba" => {
+                let mut rgb = [0.0; 3];
+                for (component, argument) in rgb.iter_mut().zip(&arguments) {
+                    *component = parse_css_number(argument, 255.0)? / 255.0;
+                }
+                Ok(Color::from_components(rgb, ColorSpace::Srgb, alpha))
+            }
+            "hsl" | "hsla" => {
+                let hue = parse_css_angle(arguments[0])?;
+                let saturation = parse_css_number(arguments[1], 100.0)? / 100.0;
+                let lightness = parse_css_number(arguments[2], 100.0)? / 100.0;
+                Ok(Color::from_components([hue, saturation.clamp(0.0, 1.0), lightness.clamp(0.0, 1.0)], ColorSpace::Hsl, alpha))
+            }
+            _ => Err(format!("Unknown color function: {}.", function)),
         }
     }
 }
 
+/// Parses a CSS number, where a percentage is relative to the given full value.
+fn parse_css_number(argument: &str, full: f32) -> Result<f32, String> {
+    let result = match argument.strip_suffix('%') {
+        Some(percentage) => percentage.parse::<f32>().map(|value| value / 100.0 * full),
+        None => argument.parse::<f32>(),
+    };
+    result.map_err(|_| format!("Invalid number in color: {}.", argument))
+}
+
+/// Parses a CSS angle in degrees, radians, gradians or turns, and returns it in degrees.
+fn parse_css_angle(argument: &str) -> Result<f32, String> {
+    let (value, factor) = if let Some(value) = argument.strip_suffix("deg") {
+        (value, 1.0)
+    } else if let Some(value) = argument.strip_suffix("grad") {
+        (value, 0.9)
+    } else if let Some(value) = argument.strip_suffix("rad") {
+        (value, 180.0 / std::f32::consts::PI)
+    } else if let Some(value) = argument.strip_suffix("turn") {
+        (value, 360.0)
+    } else {
+        (argument, 1.0)
+    };
+    value.parse::<f32>().map(|value| value * factor).map_err(|_| format!("Invalid angle in color: {}.", argument))
+}
+
 /// A color stop for a gradient with a color and a position.
 #[wasm_bindgen]
 #[derive(Debug, Clone, Copy)]
@@ -119,6 +377,129 @@ impl ColorStop {
     }
 }
 
+/// How a gradient paints the area beyond its first and last offsets, following the SVG spreadMethod attribute.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SpreadMethod {
+    /// The colors of the first and last stops extend beyond the gradient.
+    #[default]
+    Pad,
+    /// The gradient repeats mirrored back and forth.
+    Reflect,
+    /// The gradient repeats from start to end.
+    Repeat,
+}
+
+impl SpreadMethod {
+    /// Maps an offset outside of 0 to 1 to the offset of the color that paints it.
+    pub fn apply(&self, offset: f32) -> f32 {
+        match self {
+            SpreadMethod::Pad => offset,
+            SpreadMethod::Reflect => {
+                let offset = offset.rem_euclid(2.0);
+                if offset > 1.0 { 2.0 - offset } else { offset }
+            }
+            SpreadMethod::Repeat => {
+                if offset == 1.0 { offset } else { offset.rem_euclid(1.0) }
+            }
+        }
+    }
+    /// Converts a usvg spread method.
+    pub fn from_svg_spread_method(spread_method: usvg::SpreadMethod) -> SpreadMethod {
+        match spread_method {
+            usvg::SpreadMethod::Pad => SpreadMethod::Pad,
+            usvg::SpreadMethod::Reflect => SpreadMethod::Reflect,
+            usvg::SpreadMethod::Repeat => SpreadMethod::Repeat,
+        }
+    }
+}
+
+/// Returns the color stops with a finite position, sorted by position. Stops at the same position keep their order, so they make a hard transition. Gradients keep their stops this way, so they're sorted once instead of every time they're sampled.
+fn sorted_color_stops(mut color_stops: Vec<ColorStop>) -> Vec<ColorStop> {
+    color_stops.retain(|stop| stop.position.is_finite());
+    color_stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+    color_stops
+}
+
+/// Gets the Color at an offset of the color stops sorted by position, padding the colors of the first and last stops.
+fn color_at_stops_offset(stops: &[ColorStop], color_space: ColorSpace, position: f32) -> Color {
+    for i in 0..stops.len() {
+        if position < stops[i].position {
+            if i == 0 {
+                return stops[i].color;
+            }
+            let t = inverse_lerp(stops[i - 1].position, stops[i].position, position);
+            return Color::lerp(&stops[i - 1].color, &stops[i].color, t, Some(color_space));
+        }
+    }
+    stops.last().map(|stop| stop.color).unwrap_or_default()
+}
+
+/// Interpolates two lists of color stops. Lists with the same number of stops are interpolated pairwise, otherwise both are resampled at the union of their offsets, each in the color space of its gradient.
+fn lerp_color_stops(color_stops1: &[ColorStop], color_space1: ColorSpace, color_stops2: &[ColorStop], color_space2: ColorSpace, t: f32, color_space: Option<ColorSpace>) -> Vec<ColorStop> {
+    if color_stops1.len() == color_stops2.len() {
+        return color_stops1.iter().zip(color_stops2.iter()).map(|(stop1, stop2)| {
+            ColorStop {
+                color: Color::lerp(&stop1.color, &stop2.color, t, color_space),
+                position: lerp(stop1.position, stop2.position, t),
+            }
+        }).collect();
+    }
+    let mut positions = color_stops1.iter().chain(color_stops2.iter()).map(|stop| stop.position).collect::<Vec<f32>>();
+    positions.sort_by(|a, b| a.total_cmp(b));
+    positions.dedup();
+    positions.into_iter().map(|position| {
+        let color1 = color_at_stops_offset(color_stops1, color_space1, position);
+        let color2 = color_at_stops_offset(color_stops2, color_space2, position);
+        ColorStop {
+            color: Color::lerp(&color1, &color2, t, color_space),
+            position,
+        }
+    }).collect()
+}
+
+/// Gets the average of the colors of color stops, which is transparent if there are none.
+fn mean_color(color_stops: &[ColorStop]) -> Color {
+    if color_stops.is_empty() {
+        return Color::default();
+    }
+    let count = color_stops.len() as f32;
+    let mean = |channel: fn(&Color) -> f32| color_stops.iter().map(|stop| channel(&stop.color)).sum::<f32>() / count;
+    Color::new(
+        mean(|color| color.red as f32).round() as u8,
+        mean(|color| color.green as f32).round() as u8,
+        mean(|color| color.blue as f32).round() as u8,
+        mean(|color| color.alpha),
+    )
+}
+
+/// Returns color stops at the offsets of other color stops, all with the same color. Two stops at 0 and 1 are returned if there are no other stops.
+fn recolor_stops(color_stops: &[ColorStop], color: Color) -> Vec<ColorStop> {
+    if color_stops.is_empty() {
+        return vec![ColorStop { color, position: 0.0 }, ColorStop { color, position: 1.0 }];
+    }
+    color_stops.iter().map(|stop| ColorStop { color, position: stop.position }).collect()
+}
+
+/// Returns the color stops sorted by position, with stops interpolated in a color space added between every two consecutive stops.
+fn sample_color_stops(stops: &[ColorStop], color_space: ColorSpace, samples: usize) -> Vec<ColorStop> {
+    let stops = stops.to_vec();
+    if color_space == ColorSpace::Srgb || stops.len() < 2 {
+        return stops;
+    }
+    let mut sampled = vec![stops[0]];
+    for pair in stops.windows(2) {
+        for i in 1..=samples + 1 {
+            let t = i as f32 / (samples + 1) as f32;
+            sampled.push(ColorStop {
+                color: Color::lerp(&pair[0].color, &pair[1].color, t, Some(color_space)),
+                position: lerp(pair[0].position, pair[1].position, t),
+            });
+        }
+    }
+    sampled
+}
+
 /// A linear gradient with a start and end point and color stops.
 #[wasm_bindgen]
 #[derive(Debug, Clone)]
@@ -129,6 +510,12 @@ pub struct LinearGradient {
     pub p2: Point2D,
     /// The color stops of the gradient.
     color_stops: Rc<Vec<ColorStop>>,
+    /// The color space in which the colors between stops are interpolated.
+    color_space: ColorSpace,
+    /// How the gradient paints the area beyond its first and last offsets.
+    spread_method: SpreadMethod,
+    /// The transformation from the gradient's coordinate system to the coordinates of the object it paints.
+    transform: TransformationMatrix,
 }
 
 impl Default for LinearGradient {
@@ -137,6 +524,9 @@ impl Default for LinearGradient {
             p1: Point2D::default(),
             p2: Point2D::default(),
             color_stops: Rc::new(vec![]),
+            color_space: ColorSpace::Srgb,
+            spread_method: SpreadMethod::Pad,
+            transform: TransformationMatrix::identity(),
         }
     }
 }
@@ -151,12 +541,21 @@ impl LinearGradient {
         #[wasm_bindgen(param_description = "The end point of the gradient.")]
         p2: Point2D,
         #[wasm_bindgen(param_description = "The color stops of the gradient.")]
-        color_stops: Vec<ColorStop>
+        color_stops: Vec<ColorStop>,
+        #[wasm_bindgen(param_description = "The color space in which the colors between stops are interpolated, default is sRGB.")]
+        color_space: Option<ColorSpace>,
+        #[wasm_bindgen(param_description = "How the gradient paints the area beyond its first and last offsets, default is pad.")]
+        spread_method: Option<SpreadMethod>,
+        #[wasm_bindgen(param_description = "The transformation from the gradient's coordinate system to the coordinates of the object it paints, default is the identity.")]
+        transform: Option<TransformationMatrix>
     ) -> LinearGradient {
         LinearGradient {
             p1,
             p2,
-            color_stops: Rc::new(color_stops),
+            color_stops: Rc::new(sorted_color_stops(color_stops)),
+            color_space: color_space.unwrap_or_default(),
+            spread_method: spread_method.unwrap_or_default(),
+            transform: transform.unwrap_or(TransformationMatrix::identity()),
         }
     }
     /// Returns the default LinearGradient, which is a gradient from the start to the end the same color.
@@ -180,6 +579,9 @@ impl LinearGradient {
             p1,
             p2,
             color_stops: Rc::new(color_stops),
+            color_space: ColorSpace::Srgb,
+            spread_method: SpreadMethod::Pad,
+            transform: TransformationMatrix::identity(),
         }
     }
     /// Returns the default LinearGradient, which is a gradient from the origin to the origin with no ColorStops.
@@ -199,28 +601,67 @@ impl LinearGradient {
         #[wasm_bindgen(param_description = "The color stops of the gradient.")]
         color_stops: Vec<ColorStop>
     ) {
-        self.color_stops = Rc::new(color_stops);
+        self.color_stops = Rc::new(sorted_color_stops(color_stops));
+    }
+    /// Gets the color space in which the colors between stops are interpolated.
+    #[wasm_bindgen(getter, return_description = "The color space of the gradient.")]
+    pub fn color_space(&self) -> ColorSpace {
+        self.color_space
+    }
+    /// Sets the color space in which the colors between stops are interpolated.
+    #[wasm_bindgen(setter)]
+    pub fn set_color_space(
+        &mut self,
+        #[wasm_bindgen(param_description = "The color space of the gradient.")]
+        color_space: ColorSpace
+    ) {
+        self.color_space = color_space;
+    }
+    /// Gets how the gradient paints the area beyond its first and last offsets.
+    #[wasm_bindgen(getter, return_description = "The spread method of the gradient.")]
+    pub fn spread_method(&self) -> SpreadMethod {
+        self.spread_method
+    }
+    /// Sets how the gradient paints the area beyond its first and last offsets.
+    #[wasm_bindgen(setter)]
+    pub fn set_spread_method(
+        &mut self,
+        #[wasm_bindgen(param_description = "The spread method of the gradient.")]
+        spread_method: SpreadMethod
+    ) {
+        self.spread_method = spread_method;
+    }
+    /// Gets the transformation from the gradient's coordinate system to the coordinates of the object it paints.
+    #[wasm_bindgen(getter, return_description = "The transform of the gradient.")]
+    pub fn transform(&self) -> TransformationMatrix {
+        self.transform
+    }
+    /// Sets the transformation from the gradient's coordinate system to the coordinates of the object it paints.
+    #[wasm_bindgen(setter)]
+    pub fn set_transform(
+        &mut self,
+        #[wasm_bindgen(param_description = "The transform of the gradient.")]
+        transform: TransformationMatrix
+    ) {
+        self.transform = transform;
+    }
+    /// Gets ColorStops that approximate the gradient when its colors are interpolated in sRGB, as SVG and canvas renderers do. Stops are added between the original stops unless the color space of the gradient is sRGB.
+    #[wasm_bindgen(return_description = "The color stops to render the gradient with.")]
+    pub fn sampled_color_stops(
+        &self,
+        #[wasm_bindgen(param_description = "The number of stops to add between every two consecutive stops, default is 8.")]
+        samples: Option<usize>
+    ) -> Vec<ColorStop> {
+        sample_color_stops(&self.color_stops, self.color_space, samples.unwrap_or(8))
     }
-    /// Gets the Color at a given offset along the gradient.
+    /// Gets the Color at a given offset along the gradient, after applying its spread method.
     #[wasm_bindgen(return_description = "The color at the given offset.")]
     pub fn color_at_offset(
         &self,
         #[wasm_bindgen(param_description = "The offset to get the color at.")]
         position: f32
     ) -> Color {
-        let mut the_stops = Rc::clone(&self.color_stops);
-        let stops = Rc::make_mut(&mut the_stops);
-        stops.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap());
-        for i in 0..stops.len() {
-            if position < stops[i].position {
-                if i == 0 {
-                    return stops[i].color;
-                }
-                let t = inverse_lerp(stops[i - 1].position, stops[i].position, position);
-                return Color::lerp(&stops[i - 1].color, &stops[i].color, t);
-            }
-        }
-        stops[stops.len() - 1].color
+        color_at_stops_offset(&self.color_stops, self.color_space, self.spread_method.apply(position))
     }
     /// Gets the Color at a given point along the gradient.
     #[wasm_bindgen(return_description = "The color at the given point.")]
@@ -229,8 +670,14 @@ impl LinearGradient {
         #[wasm_bindgen(param_description = "The point to get the color at.")]
         p: Point2D
     ) -> Color {
-        let t = p.project_onto_line(&self.p1, &self.p2);
-        self.color_at_offset(t)
+        let p = self.transform.inverse() * p;
+        let line = self.p2 - self.p1;
+        let length_squared = line.dot(&line);
+        if length_squared == 0.0 {
+            return self.color_at_offset(0.0);
+        }
+        // The offset isn't clamped so that the spread method can paint beyond the end points.
+        self.color_at_offset(line.dot(&(p - self.p1)) / length_squared)
     }
     /// Linearly interpolates between two LinearGradients given a progress value.
     #[wasm_bindgen(return_description = "The interpolated linear gradient.")]
@@ -240,35 +687,20 @@ impl LinearGradient {
         #[wasm_bindgen(param_description = "The second linear gradient.")]
         gradient2: &LinearGradient,
         #[wasm_bindgen(param_description = "The progress value.")]
-        t: f32
+        t: f32,
+        #[wasm_bindgen(param_description = "The color space in which the colors of the stops are interpolated, default is sRGB.")]
+        color_space: Option<ColorSpace>
     ) -> LinearGradient {
         let p1 = Point2D::lerp(&gradient1.p1, &gradient2.p1, t);
         let p2 = Point2D::lerp(&gradient1.p2, &gradient2.p2, t);
-        let length = gradient1.color_stops.len().max(gradient2.color_stops.len());
-        let mut color_stops = vec![];
-        for i in 0..length {
-            let color1 = if i < gradient1.color_stops.len() {
-                gradient1.color_stops[i].color
-            } else {
-                Color::default()
-            };
-            let color2 = if i < gradient2.color_stops.len() {
-                gradient2.color_stops[i].color
-            } else {
-                Color::default()
-            };
-            let color = Color::lerp(&color1, &color2, t);
-            let position = if i < gradient1.color_stops.len() {
-                lerp(gradient1.color_stops[i].position, gradient2.color_stops[i].position, t)
-            } else {
-                lerp(0.0, gradient2.color_stops[i].position, t)
-            };
-            color_stops.push(ColorStop { color, position });
-        }
+        let color_stops = lerp_color_stops(&gradient1.color_stops, gradient1.color_space, &gradient2.color_stops, gradient2.color_space, t, color_space);
         LinearGradient {
             p1,
             p2,
             color_stops: Rc::new(color_stops),
+            color_space: if t < 0.5 { gradient1.color_space } else { gradient2.color_space },
+            spread_method: if t < 0.5 { gradient1.spread_method } else { gradient2.spread_method },
+            transform: TransformationMatrix::lerp(&gradient1.transform, &gradient2.transform, t),
         }
     }
 }
@@ -285,6 +717,12 @@ pub struct RadialGradient {
     pub r: f32,
     /// The color stops of the gradient.
     color_stops: Rc<Vec<ColorStop>>,
+    /// The color space in which the colors between stops are interpolated.
+    color_space: ColorSpace,
+    /// How the gradient paints the area beyond its first and last offsets.
+    spread_method: SpreadMethod,
+    /// The transformation from the gradient's coordinate system to the coordinates of the object it paints.
+    transform: TransformationMatrix,
 }
 
 impl Default for RadialGradient {
@@ -294,6 +732,9 @@ impl Default for RadialGradient {
             c: Point2D::default(),
             r: 0.0,
             color_stops: Rc::new(vec![]),
+            color_space: ColorSpace::Srgb,
+            spread_method: SpreadMethod::Pad,
+            transform: TransformationMatrix::identity(),
         }
     }
 }
@@ -310,13 +751,22 @@ impl RadialGradient {
         #[wasm_bindgen(param_description = "The radius of the gradient.")]
         r: f32,
         #[wasm_bindgen(param_description = "The color stops of the gradient.")]
-        color_stops: Vec<ColorStop>
+        color_stops: Vec<ColorStop>,
+        #[wasm_bindgen(param_description = "The color space in which the colors between stops are interpolated, default is sRGB.")]
+        color_space: Option<ColorSpace>,
+        #[wasm_bindgen(param_description = "How the gradient paints the area beyond its first and last offsets, default is pad.")]
+        spread_method: Option<SpreadMethod>,
+        #[wasm_bindgen(param_description = "The transformation from the gradient's coordinate system to the coordinates of the object it paints, default is the identity.")]
+        transform: Option<TransformationMatrix>
     ) -> RadialGradient {
         RadialGradient {
             f,
             c,
             r,
-            color_stops: Rc::new(color_stops),
+            color_stops: Rc::new(sorted_color_stops(color_stops)),
+            color_space: color_space.unwrap_or_default(),
+            spread_method: spread_method.unwrap_or_default(),
+            transform: transform.unwrap_or(TransformationMatrix::identity()),
         }
     }
     /// Returns the default RadialGradient, which is a gradient from the focal point to the center with the same color.
@@ -336,28 +786,67 @@ impl RadialGradient {
         #[wasm_bindgen(param_description = "The color stops of the gradient.")]
         color_stops: Vec<ColorStop>
     ) {
-        self.color_stops = Rc::new(color_stops);
+        self.color_stops = Rc::new(sorted_color_stops(color_stops));
     }
-    /// Gets the Color at a given offset along the gradient.
+    /// Gets the color space in which the colors between stops are interpolated.
+    #[wasm_bindgen(getter, return_description = "The color space of the gradient.")]
+    pub fn color_space(&self) -> ColorSpace {
+        self.color_space
+    }
+    /// Sets the color space in which the colors between stops are interpolated.
+    #[wasm_bindgen(setter)]
+    pub fn set_color_space(
+        &mut self,
+        #[wasm_bindgen(param_description = "The color space of the gradient.")]
+        color_space: ColorSpace
+    ) {
+        self.color_space = color_space;
+    }
+    /// Gets how the gradient paints the area beyond its first and last offsets.
+    #[wasm_bindgen(getter, return_description = "The spread method of the gradient.")]
+    pub fn spread_method(&self) -> SpreadMethod {
+        self.spread_method
+    }
+    /// Sets how the gradient paints the area beyond its first and last offsets.
+    #[wasm_bindgen(setter)]
+    pub fn set_spread_method(
+        &mut self,
+        #[wasm_bindgen(param_description = "The spread method of the gradient.")]
+        spread_method: SpreadMethod
+    ) {
+        self.spread_method = spread_method;
+    }
+    /// Gets the transformation from the gradient's coordinate system to the coordinates of the object it paints.
+    #[wasm_bindgen(getter, return_description = "The transform of the gradient.")]
+    pub fn transform(&self) -> TransformationMatrix {
+        self.transform
+    }
+    /// Sets the transformation from the gradient's coordinate system to the coordinates of the object it paints.
+    #[wasm_bindgen(setter)]
+    pub fn set_transform(
+        &mut self,
+        #[wasm_bindgen(param_description = "The transform of the gradient.")]
+        transform: TransformationMatrix
+    ) {
+        self.transform = transform;
+    }
+    /// Gets ColorStops that approximate the gradient when its colors are interpolated in sRGB, as SVG and canvas renderers do. Stops are added between the original stops unless the color space of the gradient is sRGB.
+    #[wasm_bindgen(return_description = "The color stops to render the gradient with.")]
+    pub fn sampled_color_stops(
+        &self,
+        #[wasm_bindgen(param_description = "The number of stops to add between every two consecutive stops, default is 8.")]
+        samples: Option<usize>
+    ) -> Vec<ColorStop> {
+        sample_color_stops(&self.color_stops, self.color_space, samples.unwrap_or(8))
+    }
+    /// Gets the Color at a given offset along the gradient, after applying its spread method.
     #[wasm_bindgen(return_description = "The color at the given offset.")]
     pub fn color_at_offset(
         &self,
         #[wasm_bindgen(param_description = "The offset to get the color at.")]
         position: f32
     ) -> Color {
-        let mut stops = Rc::clone(&self.color_stops);
-        let stops = Rc::make_mut(&mut stops);
-        stops.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap());
-        for i in 0..stops.len() {
-            if position < stops[i].position {
-                if i == 0 {
-                    return stops[i].color;
-                }
-                let t = inverse_lerp(stops[i - 1].position, stops[i].position, position);
-                return Color::lerp(&stops[i - 1].color, &stops[i].color, t);
-            }
-        }
-        stops[stops.len() - 1].color
+        color_at_stops_offset(&self.color_stops, self.color_space, self.spread_method.apply(position))
     }
     /// Gets the Color at a given point along the gradient.
     #[wasm_bindgen(return_description = "The color at the given point.")]
@@ -366,6 +855,7 @@ impl RadialGradient {
         #[wasm_bindgen(param_description = "The point to get the color at.")]
         p: Point2D
     ) -> Color {
+        let p = self.transform.inverse() * p;
         // We need first to solve this equation: dist(c, f + (p - f) * t) = r
         // where dist is the distance between two points, c is the center point, f is the focal point, p is the point, and r is the radius.
         // This simplifies to a quadratic equation At^2 + Bt + C = 0, where:
@@ -381,9 +871,8 @@ impl RadialGradient {
             return self.color_at_offset(0.0);
         }
         let t = (-b + (b.powi(2) - 4.0 * a * c).sqrt()) / (2.0 * a);
-        // Then let's project the point onto the line segment from f to f + (p - f) * t
-        let projection = p.project_onto_line(&self.f, &(self.f + (p - self.f) * t));
-        self.color_at_offset(projection)
+        // The point is at offset 1 / t along the segment from f to f + (p - f) * t, which isn't clamped so that the spread method can paint beyond the circle.
+        self.color_at_offset(if t > 0.0 { 1.0 / t } else { 1.0 })
     }
     /// Returns a single color radial gradient.
     #[wasm_bindgen(return_description = "A single color radial gradient.")]
@@ -409,6 +898,9 @@ impl RadialGradient {
             c,
             r,
             color_stops: Rc::new(color_stops),
+            color_space: ColorSpace::Srgb,
+            spread_method: SpreadMethod::Pad,
+            transform: TransformationMatrix::identity(),
         }
     }
     /// Linearly interpolates between two RadialGradients given a progress value.
@@ -419,41 +911,599 @@ impl RadialGradient {
         #[wasm_bindgen(param_description = "The second radial gradient.")]
         gradient2: &RadialGradient,
         #[wasm_bindgen(param_description = "The progress value.")]
-        t: f32
+        t: f32,
+        #[wasm_bindgen(param_description = "The color space in which the colors of the stops are interpolated, default is sRGB.")]
+        color_space: Option<ColorSpace>
     ) -> RadialGradient {
         let f = Point2D::lerp(&gradient1.f, &gradient2.f, t);
         let c = Point2D::lerp(&gradient1.c, &gradient2.c, t);
         let r = lerp(gradient1.r, gradient2.r, t);
-        let length = gradient1.color_stops.len().max(gradient2.color_stops.len());
-        let mut color_stops = vec![];
-        for i in 0..length {
-            let color1 = if i < gradient1.color_stops.len() {
-                gradient1.color_stops[i].color
-            } else {
-                Color::default()
-            };
-            let color2 = if i < gradient2.color_stops.len() {
-                gradient2.color_stops[i].color
-            } else {
-                Color::default()
-            };
-            let color = Color::lerp(&color1, &color2, t);
-            let position = if i < gradient1.color_stops.len() {
-                lerp(gradient1.color_stops[i].position, gradient2.color_stops[i].position, t)
-            } else {
-                lerp(0.0, gradient2.color_stops[i].position, t)
-            };
-            color_stops.push(ColorStop { color, position });
-        }
+        let color_stops = lerp_color_stops(&gradient1.color_stops, gradient1.color_space, &gradient2.color_stops, gradient2.color_space, t, color_space);
         RadialGradient {
             f,
             c,
             r,
             color_stops: Rc::new(color_stops),
+            color_space: if t < 0.5 { gradient1.color_space } else { gradient2.color_space },
+            spread_method: if t < 0.5 { gradient1.spread_method } else { gradient2.spread_method },
+            transform: TransformationMatrix::lerp(&gradient1.transform, &gradient2.transform, t),
+        }
+    }
+}
+
+/// A conic gradient, also known as a sweep gradient, whose offsets go around a center point from a start angle.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct ConicGradient {
+    /// The center point of the gradient.
+    pub center: Point2D,
+    /// The angle in radians where offset 0 starts, measured from the positive x-axis towards the positive y-axis.
+    pub angle: f32,
+    /// The color stops of the gradient.
+    color_stops: Rc<Vec<ColorStop>>,
+    /// The color space in which the colors between stops are interpolated.
+    color_space: ColorSpace,
+    /// The transformation from the gradient's coordinate system to the coordinates of the object it paints.
+    transform: TransformationMatrix,
+}
+
+impl Default for ConicGradient {
+    fn default() -> Self {
+        ConicGradient {
+            center: Point2D::default(),
+            angle: 0.0,
+            color_stops: Rc::new(vec![]),
+            color_space: ColorSpace::Srgb,
+            transform: TransformationMatrix::identity(),
         }
     }
 }
 
+#[wasm_bindgen]
+impl ConicGradient {
+    /// Creates a new ConicGradient with the given center point, start angle, and ColorStops.
+    #[wasm_bindgen(constructor, return_description = "A new conic gradient.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The center point of the gradient.")]
+        center: Point2D,
+        #[wasm_bindgen(param_description = "The angle in radians where offset 0 starts, measured from the positive x-axis towards the positive y-axis.")]
+        angle: f32,
+        #[wasm_bindgen(param_description = "The color stops of the gradient, with offsets from 0 to 1 going once around the center.")]
+        color_stops: Vec<ColorStop>,
+        #[wasm_bindgen(param_description = "The color space in which the colors between stops are interpolated, default is sRGB.")]
+        color_space: Option<ColorSpace>,
+        #[wasm_bindgen(param_description = "The transformation from the gradient's coordinate system to the coordinates of the object it paints, default is the identity.")]
+        transform: Option<TransformationMatrix>
+    ) -> ConicGradient {
+        ConicGradient {
+            center,
+            angle,
+            color_stops: Rc::new(sorted_color_stops(color_stops)),
+            color_space: color_space.unwrap_or_default(),
+            transform: transform.unwrap_or(TransformationMatrix::identity()),
+        }
+    }
+    /// Creates a ConicGradient going around the hue wheel, useful for color wheels and phase plots.
+    #[wasm_bindgen(return_description = "A hue wheel conic gradient.")]
+    pub fn hue_wheel(
+        #[wasm_bindgen(param_description = "The center point of the gradient.")]
+        center: Point2D,
+        #[wasm_bindgen(param_description = "The angle in radians where red starts, default is 0.")]
+        angle: Option<f32>,
+        #[wasm_bindgen(param_description = "The color space whose hue goes around the wheel, it must be HSL, HSV or Oklch, default is HSL.")]
+        color_space: Option<ColorSpace>
+    ) -> Result<ConicGradient, JsError> {
+        let color_space = color_space.unwrap_or(ColorSpace::Hsl);
+        let red = Color::new(255, 0, 0, 1.0).components(color_space);
+        let hue_index = color_space.hue_index().ok_or_else(|| JsError::new("The color space must have a hue."))?;
+        let color_stops = (0..=6).map(|i| {
+            let mut components = red;
+            components[hue_index] += 60.0 * i as f32;
+            ColorStop { color: Color::from_components(components, color_space, 1.0), position: i as f32 / 6.0 }
+        }).collect();
+        Ok(ConicGradient::new(center, angle.unwrap_or(0.0), color_stops, Some(color_space), None))
+    }
+    /// Returns a single color conic gradient.
+    #[wasm_bindgen(return_description = "A single color conic gradient.")]
+    pub fn single_color_gradient(
+        #[wasm_bindgen(param_description = "The center point of the gradient.")]
+        center: Point2D,
+        #[wasm_bindgen(param_description = "The angle in radians where offset 0 starts.")]
+        angle: f32,
+        #[wasm_bindgen(param_description = "The color of the gradient.")]
+        color: Color,
+        #[wasm_bindgen(param_description = "Number of times to repeat the color.")]
+        repeats: Option<usize>
+    ) -> ConicGradient {
+        let repeats = repeats.unwrap_or(2);
+        let color_stops = (0..repeats).map(|i| ColorStop { color, position: i as f32 / (repeats - 1) as f32 }).collect();
+        ConicGradient::new(center, angle, color_stops, None, None)
+    }
+    /// Returns the default ConicGradient, which is centered at the origin with no ColorStops.
+    #[wasm_bindgen(return_description = "The default conic gradient.")]
+    pub fn default_conic_gradient() -> ConicGradient {
+        ConicGradient::default()
+    }
+    /// Gets the ColorStops of the gradient.
+    #[wasm_bindgen(getter, return_description = "The color stops of the gradient.")]
+    pub fn color_stops(&self) -> Vec<ColorStop> {
+        self.color_stops.to_vec()
+    }
+    /// Sets the ColorStops of the gradient.
+    #[wasm_bindgen(setter)]
+    pub fn set_color_stops(
+        &mut self,
+        #[wasm_bindgen(param_description = "The color stops of the gradient.")]
+        color_stops: Vec<ColorStop>
+    ) {
+        self.color_stops = Rc::new(sorted_color_stops(color_stops));
+    }
+    /// Gets the color space in which the colors between stops are interpolated.
+    #[wasm_bindgen(getter, return_description = "The color space of the gradient.")]
+    pub fn color_space(&self) -> ColorSpace {
+        self.color_space
+    }
+    /// Sets the color space in which the colors between stops are interpolated.
+    #[wasm_bindgen(setter)]
+    pub fn set_color_space(
+        &mut self,
+        #[wasm_bindgen(param_description = "The color space of the gradient.")]
+        color_space: ColorSpace
+    ) {
+        self.color_space = color_space;
+    }
+    /// Gets the transformation from the gradient's coordinate system to the coordinates of the object it paints.
+    #[wasm_bindgen(getter, return_description = "The transform of the gradient.")]
+    pub fn transform(&self) -> TransformationMatrix {
+        self.transform
+    }
+    /// Sets the transformation from the gradient's coordinate system to the coordinates of the object it paints.
+    #[wasm_bindgen(setter)]
+    pub fn set_transform(
+        &mut self,
+        #[wasm_bindgen(param_description = "The transform of the gradient.")]
+        transform: TransformationMatrix
+    ) {
+        self.transform = transform;
+    }
+    /// Gets ColorStops that approximate the gradient when its colors are interpolated in sRGB. Stops are added between the original stops unless the color space of the gradient is sRGB.
+    #[wasm_bindgen(return_description = "The color stops to render the gradient with.")]
+    pub fn sampled_color_stops(
+        &self,
+        #[wasm_bindgen(param_description = "The number of stops to add between every two consecutive stops, default is 8.")]
+        samples: Option<usize>
+    ) -> Vec<ColorStop> {
+        sample_color_stops(&self.color_stops, self.color_space, samples.unwrap_or(8))
+    }
+    /// Gets the Color at a given offset around the gradient.
+    #[wasm_bindgen(return_description = "The color at the given offset.")]
+    pub fn color_at_offset(
+        &self,
+        #[wasm_bindgen(param_description = "The offset to get the color at, from 0 to 1.")]
+        position: f32
+    ) -> Color {
+        color_at_stops_offset(&self.color_stops, self.color_space, position)
+    }
+    /// Gets the Color at a given point around the gradient.
+    #[wasm_bindgen(return_description = "The color at the given point.")]
+    pub fn color_at(
+        &self,
+        #[wasm_bindgen(param_description = "The point to get the color at.")]
+        p: Point2D
+    ) -> Color {
+        let p = self.transform.inverse() * p;
+        let angle = (p.y - self.center.y).atan2(p.x - self.center.x) - self.angle;
+        self.color_at_offset(angle.rem_euclid(std::f32::consts::TAU) / std::f32::consts::TAU)
+    }
+    /// Linearly interpolates between two ConicGradients given a progress value.
+    #[wasm_bindgen(return_description = "The interpolated conic gradient.")]
+    pub fn lerp(
+        #[wasm_bindgen(param_description = "The first conic gradient.")]
+        gradient1: &ConicGradient,
+        #[wasm_bindgen(param_description = "The second conic gradient.")]
+        gradient2: &ConicGradient,
+        #[wasm_bindgen(param_description = "The progress value.")]
+        t: f32,
+        #[wasm_bindgen(param_description = "The color space in which the colors of the stops are interpolated, default is sRGB.")]
+        color_space: Option<ColorSpace>
+    ) -> ConicGradient {
+        ConicGradient {
+            center: Point2D::lerp(&gradient1.center, &gradient2.center, t),
+            angle: lerp(gradient1.angle, gradient2.angle, t),
+            color_stops: Rc::new(lerp_color_stops(&gradient1.color_stops, gradient1.color_space, &gradient2.color_stops, gradient2.color_space, t, color_space)),
+            color_space: if t < 0.5 { gradient1.color_space } else { gradient2.color_space },
+            transform: TransformationMatrix::lerp(&gradient1.transform, &gradient2.transform, t),
+        }
+    }
+}
+
+/// A shape of a Pattern's tile flattened to polygons, used to sample the pattern at a point.
+#[derive(Debug, Clone)]
+struct PatternShape {
+    /// The flattened subpaths of the shape.
+    polygons: Vec<Vec<Point2D>>,
+    /// The fill style of the shape.
+    fill: Style,
+    /// The stroke style of the shape.
+    stroke: Style,
+    /// The stroke width of the shape.
+    stroke_width: f32,
+    /// Whether the shape is filled with the even-odd fill rule instead of the nonzero one.
+    even_odd: bool,
+}
+
+/// Number of line segments each cubic bezier curve of a tile is flattened to.
+const PATTERN_CURVE_SAMPLES: usize = 8;
+
+/// Flattens a VectorObject and its children into PatternShapes in paint order.
+fn flatten_tile(object: &VectorObject, shapes: &mut Vec<PatternShape>) {
+    let polygons = object.subpaths().iter().map(|subpath| {
+        let subpath = subpath.transform(&object.transform());
+        let mut points = vec![subpath.first()];
+        for tuple in subpath.cubic_bezier_tuples() {
+            for i in 1..=PATTERN_CURVE_SAMPLES {
+                points.push(tuple.point_at(i as f32 / PATTERN_CURVE_SAMPLES as f32));
+            }
+        }
+        points
+    }).collect::<Vec<Vec<Point2D>>>();
+    if !polygons.is_empty() {
+        shapes.push(PatternShape {
+            polygons,
+            fill: object.fill(),
+            stroke: object.stroke(),
+            stroke_width: object.stroke_width(),
+            even_odd: object.fill_rule() == "evenodd",
+        });
+    }
+    for child in object.children() {
+        flatten_tile(&child, shapes);
+    }
+}
+
+/// Gets the winding number of a set of polygons, closed implicitly, around a point.
+fn winding_number(polygons: &[Vec<Point2D>], p: Point2D) -> i32 {
+    let mut winding = 0;
+    for polygon in polygons {
+        for i in 0..polygon.len() {
+            let a = polygon[i];
+            let b = polygon[(i + 1) % polygon.len()];
+            let cross = (b.x - a.x) * (p.y - a.y) - (p.x - a.x) * (b.y - a.y);
+            if a.y <= p.y && b.y > p.y && cross > 0.0 {
+                winding += 1;
+            } else if a.y > p.y && b.y <= p.y && cross < 0.0 {
+                winding -= 1;
+            }
+        }
+    }
+    winding
+}
+
+/// Checks whether a point is within a distance of the segments of a set of polylines.
+fn is_near_polylines(polygons: &[Vec<Point2D>], p: Point2D, distance: f32) -> bool {
+    polygons.iter().any(|polygon| {
+        polygon.windows(2).any(|segment| {
+            let t = p.project_onto_line(&segment[0], &segment[1]);
+            Point2D::lerp(&segment[0], &segment[1], t).distance_squared(&p) <= distance * distance
+        })
+    })
+}
+
+/// Composites a Color over another one.
+fn composite_over(source: Color, destination: Color) -> Color {
+    let alpha = source.alpha + destination.alpha * (1.0 - source.alpha);
+    if alpha == 0.0 {
+        return Color::default();
+    }
+    let channel = |source_channel: u8, destination_channel: u8| {
+        ((source_channel as f32 * source.alpha + destination_channel as f32 * destination.alpha * (1.0 - source.alpha)) / alpha).round() as u8
+    };
+    Color::new(
+        channel(source.red, destination.red),
+        channel(source.green, destination.green),
+        channel(source.blue, destination.blue),
+        alpha,
+    )
+}
+
+/// Interpolates two tiles with the same structure, so that their paths have the same number of points and they have the same number of children.
+fn lerp_tile(tile1: &VectorObject, tile2: &VectorObject, t: f32, color_space: Option<ColorSpace>) -> Result<VectorObject, String> {
+    let points1 = tile1.path().points();
+    let points2 = tile2.path().points();
+    let children1 = tile1.children();
+    let children2 = tile2.children();
+    if points1.len() != points2.len() || children1.len() != children2.len() {
+        return Err("The tiles of both patterns must have the same structure.".to_string());
+    }
+    let path = Path2D::new(points1.iter().zip(points2.iter()).map(|(p1, p2)| Point2D::lerp(p1, p2, t)).collect());
+    let fill = Style::lerp(&tile1.fill(), &tile2.fill(), t, None, None, None, None, None, None, color_space)?;
+    let stroke = Style::lerp(&tile1.stroke(), &tile2.stroke(), t, None, None, None, None, None, None, color_space)?;
+    let children = children1.iter().zip(children2.iter()).map(|(child1, child2)| {
+        lerp_tile(child1, child2, t, color_space).map(|child| VectorObjectBuilder::new(&child))
+    }).collect::<Result<Vec<VectorObjectBuilder>, String>>()?;
+    Ok(VectorObjectBuilder::new(tile1)
+        .set_path(path)
+        .set_fill(fill, Some(false))
+        .set_stroke(stroke, Some(false))
+        .set_stroke_width(lerp(tile1.stroke_width(), tile2.stroke_width(), t), Some(false))
+        .set_transform(TransformationMatrix::lerp(&tile1.transform(), &tile2.transform(), t), Some(false))
+        .set_children(children)
+        .build())
+}
+
+/// A pattern that tiles a VectorObject over the plane, like the SVG pattern element. Tiles are separated by a spacing which is left transparent.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    /// The width of a tile.
+    pub width: f32,
+    /// The height of a tile.
+    pub height: f32,
+    /// The horizontal spacing between two consecutive tiles.
+    pub spacing_x: f32,
+    /// The vertical spacing between two consecutive tiles.
+    pub spacing_y: f32,
+    /// The top left corner of the tile at the origin of the pattern's coordinate system.
+    pub origin: Point2D,
+    /// The vector object drawn in every tile, with coordinates relative to the top left corner of the tile.
+    tile: Rc<VectorObject>,
+    /// The tile flattened to polygons.
+    shapes: Rc<Vec<PatternShape>>,
+    /// The transformation from the pattern's coordinate system to the coordinates of the object it paints.
+    transform: TransformationMatrix,
+}
+
+#[wasm_bindgen]
+impl Pattern {
+    /// Creates a new Pattern that tiles a VectorObject.
+    #[wasm_bindgen(constructor, return_description = "A new pattern.")]
+    pub fn new(
+        #[wasm_bindgen(param_description = "The vector object drawn in every tile, with coordinates relative to the top left corner of the tile. Whatever lies outside of the tile is clipped.")]
+        tile: VectorObject,
+        #[wasm_bindgen(param_description = "The width of a tile.")]
+        width: f32,
+        #[wasm_bindgen(param_description = "The height of a tile.")]
+        height: f32,
+        #[wasm_bindgen(param_description = "The horizontal spacing between two consecutive tiles, default is 0.")]
+        spacing_x: Option<f32>,
+        #[wasm_bindgen(param_description = "The vertical spacing between two consecutive tiles, default is 0.")]
+        spacing_y: Option<f32>,
+        #[wasm_bindgen(param_description = "The top left corner of the tile at the origin, default is (0, 0).")]
+        origin: Option<Point2D>,
+        #[wasm_bindgen(param_description = "The transformation from the pattern's coordinate system to the coordinates of the object it paints, default is the identity.")]
+        transform: Option<TransformationMatrix>
+    ) -> Pattern {
+        let mut shapes = vec![];
+        flatten_tile(&tile, &mut shapes);
+        Pattern {
+            width,
+            height,
+            spacing_x: spacing_x.unwrap_or(0.0),
+            spacing_y: spacing_y.unwrap_or(0.0),
+            origin: origin.unwrap_or_default(),
+            tile: Rc::new(tile),
+            shapes: Rc::new(shapes),
+            transform: transform.unwrap_or(TransformationMatrix::identity()),
+        }
+    }
+    /// Creates a Pattern of parallel lines, as used to shade regions in textbooks.
+    #[wasm_bindgen(return_description = "A hatch pattern.")]
+    pub fn hatch(
+        #[wasm_bindgen(param_description = "The stroke style of the lines.")]
+        stroke: Style,
+        #[wasm_bindgen(param_description = "The distance between two consecutive lines.")]
+        spacing: f32,
+        #[wasm_bindgen(param_description = "The angle in radians of the lines, measured from the positive x-axis towards the positive y-axis, default is pi / 4.")]
+        angle: Option<f32>,
+        #[wasm_bindgen(param_description = "The stroke width of the lines, default is 1.")]
+        stroke_width: Option<f32>
+    ) -> Pattern {
+        let tile = Line::new(Point2D::new(0.0, spacing / 2.0), Point2D::new(spacing, spacing / 2.0))
+            .vector_object_builder()
+            .set_stroke(stroke, None)
+            .set_stroke_width(stroke_width.unwrap_or(1.0), None)
+            .build();
+        Pattern::new(tile, spacing, spacing, None, None, None, Some(TransformationMatrix::rotate(angle.unwrap_or(std::f32::consts::FRAC_PI_4))))
+    }
+    /// Creates a Pattern of two sets of perpendicular parallel lines.
+    #[wasm_bindgen(return_description = "A crosshatch pattern.")]
+    pub fn crosshatch(
+        #[wasm_bindgen(param_description = "The stroke style of the lines.")]
+        stroke: Style,
+        #[wasm_bindgen(param_description = "The distance between two consecutive parallel lines.")]
+        spacing: f32,
+        #[wasm_bindgen(param_description = "The angle in radians of the first set of lines, measured from the positive x-axis towards the positive y-axis, default is pi / 4.")]
+        angle: Option<f32>,
+        #[wasm_bindgen(param_description = "The stroke width of the lines, default is 1.")]
+        stroke_width: Option<f32>
+    ) -> Pattern {
+        let horizontal = Line::new(Point2D::new(0.0, spacing / 2.0), Point2D::new(spacing, spacing / 2.0)).vector_object_builder();
+        let vertical = Line::new(Point2D::new(spacing / 2.0, 0.0), Point2D::new(spacing / 2.0, spacing)).vector_object_builder();
+        let tile = VectorObjectBuilder::default()
+            .add_children(vec![horizontal, vertical])
+            .set_stroke(stroke, None)
+            .set_stroke_width(stroke_width.unwrap_or(1.0), None)
+            .build();
+        Pattern::new(tile, spacing, spacing, None, None, None, Some(TransformationMatrix::rotate(angle.unwrap_or(std::f32::consts::FRAC_PI_4))))
+    }
+    /// Creates a Pattern of dots placed on a square grid.
+    #[wasm_bindgen(return_description = "A dots pattern.")]
+    pub fn dots(
+        #[wasm_bindgen(param_description = "The fill style of the dots.")]
+        fill: Style,
+        #[wasm_bindgen(param_description = "The distance between the centers of two consecutive dots.")]
+        spacing: f32,
+        #[wasm_bindgen(param_description = "The radius of the dots, default is a quarter of the spacing.")]
+        radius: Option<f32>,
+        #[wasm_bindgen(param_description = "The angle in radians of the grid, default is 0.")]
+        angle: Option<f32>
+    ) -> Pattern {
+        let tile = Circle::new(Point2D::new(spacing / 2.0, spacing / 2.0), radius.unwrap_or(spacing / 4.0))
+            .vector_object_builder(None)
+            .set_fill(fill, None)
+            .build();
+        Pattern::new(tile, spacing, spacing, None, None, None, Some(TransformationMatrix::rotate(angle.unwrap_or(0.0))))
+    }
+    /// Gets the vector object drawn in every tile.
+    #[wasm_bindgen(getter, return_description = "The tile of the pattern.")]
+    pub fn tile(&self) -> VectorObject {
+        (*self.tile).clone()
+    }
+    /// Sets the vector object drawn in every tile.
+    #[wasm_bindgen(setter)]
+    pub fn set_tile(
+        &mut self,
+        #[wasm_bindgen(param_description = "The tile of the pattern.")]
+        tile: VectorObject
+    ) {
+        let mut shapes = vec![];
+        flatten_tile(&tile, &mut shapes);
+        self.tile = Rc::new(tile);
+        self.shapes = Rc::new(shapes);
+    }
+    /// Gets the transformation from the pattern's coordinate system to the coordinates of the object it paints.
+    #[wasm_bindgen(getter, return_description = "The transform of the pattern.")]
+    pub fn transform(&self) -> TransformationMatrix {
+        self.transform
+    }
+    /// Sets the transformation from the pattern's coordinate system to the coordinates of the object it paints.
+    #[wasm_bindgen(setter)]
+    pub fn set_transform(
+        &mut self,
+        #[wasm_bindgen(param_description = "The transform of the pattern.")]
+        transform: TransformationMatrix
+    ) {
+        self.transform = transform;
+    }
+    /// Fades the tile of the Pattern by a given amount.
+    #[wasm_bindgen(return_description = "The faded pattern.")]
+    pub fn fade(
+        &self,
+        #[wasm_bindgen(param_description = "The amount to fade the pattern by.")]
+        amount: f32
+    ) -> Pattern {
+        let mut pattern = self.clone();
+        pattern.set_tile(VectorObjectBuilder::new(&self.tile).fade_fill(amount, None).fade_stroke(amount, None).build());
+        pattern
+    }
+    /// Gets the Color at a given point of the pattern, compositing the shapes of the tile in paint order.
+    #[wasm_bindgen(return_description = "The color at the given point.")]
+    pub fn color_at(
+        &self,
+        #[wasm_bindgen(param_description = "The point to get the color at.")]
+        p: Point2D
+    ) -> Color {
+        let p = self.transform.inverse() * p - self.origin;
+        let x = p.x.rem_euclid(self.width + self.spacing_x);
+        let y = p.y.rem_euclid(self.height + self.spacing_y);
+        if x > self.width || y > self.height {
+            return Color::default();
+        }
+        let p = Point2D::new(x, y);
+        let mut color = Color::default();
+        for shape in self.shapes.iter() {
+            let winding = winding_number(&shape.polygons, p);
+            let inside = if shape.even_odd { winding % 2 != 0 } else { winding != 0 };
+            if inside {
+                color = composite_over(shape.fill.color_at(p), color);
+            }
+            if shape.stroke_width > 0.0 && is_near_polylines(&shape.polygons, p, shape.stroke_width / 2.0) {
+                color = composite_over(shape.stroke.color_at(p), color);
+            }
+        }
+        color
+    }
+    /// Linearly interpolates between two Patterns given a progress value. Their tiles must have the same structure, so that their paths have the same number of points and they have the same number of children.
+    #[wasm_bindgen(return_description = "The interpolated pattern.")]
+    pub fn lerp(
+        #[wasm_bindgen(param_description = "The first pattern.")]
+        pattern1: &Pattern,
+        #[wasm_bindgen(param_description = "The second pattern.")]
+        pattern2: &Pattern,
+        #[wasm_bindgen(param_description = "The progress value.")]
+        t: f32,
+        #[wasm_bindgen(param_description = "The color space in which the colors of the tiles are interpolated, default is sRGB.")]
+        color_space: Option<ColorSpace>
+    ) -> Result<Pattern, String> {
+        let tile = lerp_tile(&pattern1.tile, &pattern2.tile, t, color_space)?;
+        Ok(Pattern::new(
+            tile,
+            lerp(pattern1.width, pattern2.width, t),
+            lerp(pattern1.height, pattern2.height, t),
+            Some(lerp(pattern1.spacing_x, pattern2.spacing_x, t)),
+            Some(lerp(pattern1.spacing_y, pattern2.spacing_y, t)),
+            Some(Point2D::lerp(&pattern1.origin, &pattern2.origin, t)),
+            Some(TransformationMatrix::lerp(&pattern1.transform, &pattern2.transform, t)),
+        ))
+    }
+}
+
+/// How an image is resampled when it's drawn at a different resolution or transformed.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImageResampling {
+    /// The color of the nearest pixel is used, keeping pixels sharp.
+    Nearest,
+    /// The colors of the 4 nearest pixels are linearly interpolated.
+    #[default]
+    Bilinear,
+    /// The colors of the 16 nearest pixels are interpolated with a Catmull-Rom spline, which is sharper than bilinear.
+    Bicubic,
+}
+
+/// The Catmull-Rom weights of the 4 pixels around a fractional offset between the second and the third one.
+fn catmull_rom_weights(t: f32) -> [f32; 4] {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    [
+        -0.5 * t3 + t2 - 0.5 * t,
+        1.5 * t3 - 2.5 * t2 + 1.0,
+        -1.5 * t3 + 2.0 * t2 + 0.5 * t,
+        0.5 * t3 - 0.5 * t2,
+    ]
+}
+
+/// The filter used to compute the pixels of a resized image.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ResizeFilter {
+    /// The color of the nearest pixel is used.
+    Nearest,
+    /// The colors of the nearest pixels are linearly interpolated.
+    #[default]
+    Bilinear,
+    /// The colors of the nearest pixels are interpolated with a Catmull-Rom spline.
+    Bicubic,
+    /// The colors of the nearest pixels are weighted with a Lanczos window of radius 3, which keeps the most detail when downscaling.
+    Lanczos,
+}
+
+impl ResizeFilter {
+    /// Gets the filter type of the image crate.
+    pub fn filter_type(&self) -> FilterType {
+        match self {
+            ResizeFilter::Nearest => FilterType::Nearest,
+            ResizeFilter::Bilinear => FilterType::Triangle,
+            ResizeFilter::Bicubic => FilterType::CatmullRom,
+            ResizeFilter::Lanczos => FilterType::Lanczos3,
+        }
+    }
+}
+
+/// Rec. 709 luma coefficients, used to get the luminance of a color.
+pub const LUMA: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+/// Gets the 4x5 color matrix that mixes every color with its luminance, where a saturation of 0 is grayscale and 1 is the identity.
+fn saturation_matrix(saturation: f32) -> [f32; 20] {
+    let [r, g, b] = LUMA.map(|luma| luma * (1.0 - saturation));
+    [
+        r + saturation, g, b, 0.0, 0.0,
+        r, g + saturation, b, 0.0, 0.0,
+        r, g, b + saturation, 0.0, 0.0,
+        0.0, 0.0, 0.0, 1.0, 0.0,
+    ]
+}
+
 /// An image bitmap with pixel data.
 #[wasm_bindgen]
 #[derive(Debug, Clone)]
@@ -472,6 +1522,8 @@ pub struct ImageBitmap {
     pub data_height: usize,
     /// Rgba data of the bitmap.
     rgba_image: ImageBuffer<image::Rgba<u8>, Vec<u8>>,
+    /// How the bitmap is resampled when it's drawn at a different resolution.
+    resampling: ImageResampling,
 }
 
 impl Default for ImageBitmap {
@@ -483,7 +1535,8 @@ impl Default for ImageBitmap {
             height: 0.0,
             data_width: 0,
             data_height: 0,
-            rgba_image: RgbaImage::new(0, 0)
+            rgba_image: RgbaImage::new(0, 0),
+            resampling: ImageResampling::default(),
         }
     }
 }
@@ -526,6 +1579,38 @@ impl ImageBitmap {
             data_width,
             data_height,
             rgba_image,
+            resampling: ImageResampling::default(),
+        })
+    }
+    /// Creates a new ImageBitmap by decoding PNG, JPEG or WebP data, taking the number of pixels from the decoded image.
+    #[wasm_bindgen(return_description = "A new image bitmap with the decoded image.")]
+    pub fn decode(
+        #[wasm_bindgen(param_description = "The x coordinate of the bitmap.")]
+        x: f32,
+        #[wasm_bindgen(param_description = "The y coordinate of the bitmap.")]
+        y: f32,
+        #[wasm_bindgen(param_description = "The encoded image data.")]
+        data: &[u8],
+        #[wasm_bindgen(param_description = "The width of the bitmap, default is the number of pixels in a row of the image.")]
+        width: Option<f32>,
+        #[wasm_bindgen(param_description = "The height of the bitmap, default is the number of pixels in a column of the image.")]
+        height: Option<f32>
+    ) -> Result<ImageBitmap, JsError> {
+        let rgba_image = guess_format(data)
+            .and_then(|format| load_from_memory_with_format(data, format))
+            .map_err(|e| JsError::new(&format!("Failed to decode image: {}", e)))?
+            .to_rgba8();
+        let data_width = rgba_image.width() as usize;
+        let data_height = rgba_image.height() as usize;
+        Ok(ImageBitmap {
+            x,
+            y,
+            width: width.unwrap_or(data_width as f32),
+            height: height.unwrap_or(data_height as f32),
+            data_width,
+            data_height,
+            rgba_image,
+            resampling: ImageResampling::default(),
         })
     }
     /// Gets the pixel data of the bitmap.
@@ -566,6 +1651,66 @@ impl ImageBitmap {
         let pixel = self.rgba_image.get_pixel(x, y);
         Color::new(pixel[0], pixel[1], pixel[2], pixel[3] as f32 / 255.0)
     }
+    /// Gets how the bitmap is resampled when it's drawn at a different resolution.
+    #[wasm_bindgen(getter, return_description = "The resampling of the bitmap.")]
+    pub fn resampling(&self) -> ImageResampling {
+        self.resampling
+    }
+    /// Sets how the bitmap is resampled when it's drawn at a different resolution.
+    #[wasm_bindgen(setter)]
+    pub fn set_resampling(
+        &mut self,
+        #[wasm_bindgen(param_description = "The resampling of the bitmap.")]
+        resampling: ImageResampling
+    ) {
+        self.resampling = resampling;
+    }
+    /// Samples the color of the bitmap at a Point2D with the bitmap's resampling. The bitmap repeats beyond its bounds, while the pixels on its edges are extended when resampling.
+    #[wasm_bindgen(return_description = "The resampled color at the point.")]
+    pub fn sample(
+        &self,
+        #[wasm_bindgen(param_description = "The point to sample the color at.")]
+        p: Point2D
+    ) -> Color {
+        let pixels_x = self.rgba_image.width() as i64;
+        let pixels_y = self.rgba_image.height() as i64;
+        if pixels_x == 0 || pixels_y == 0 || self.width == 0.0 || self.height == 0.0 {
+            return Color::default();
+        }
+        // Pixel centers are at half-integer coordinates.
+        let u = (p.x - self.x).rem_euclid(self.width) / self.width * pixels_x as f32 - 0.5;
+        let v = (p.y - self.y).rem_euclid(self.height) / self.height * pixels_y as f32 - 0.5;
+        let pixel = |i: i64, j: i64| self.rgba_image.get_pixel(i.clamp(0, pixels_x - 1) as u32, j.clamp(0, pixels_y - 1) as u32);
+        let (i0, j0) = (u.floor() as i64, v.floor() as i64);
+        let (fx, fy) = (u - u.floor(), v - v.floor());
+        let (offsets, weights_x, weights_y) = match self.resampling {
+            ImageResampling::Nearest => {
+                let pixel = pixel(u.round() as i64, v.round() as i64);
+                return Color::new(pixel[0], pixel[1], pixel[2], pixel[3] as f32 / 255.0);
+            }
+            ImageResampling::Bilinear => (vec![0, 1], vec![1.0 - fx, fx], vec![1.0 - fy, fy]),
+            ImageResampling::Bicubic => (vec![-1, 0, 1, 2], catmull_rom_weights(fx).to_vec(), catmull_rom_weights(fy).to_vec()),
+        };
+        // Colors are premultiplied by alpha so that transparent pixels don't bleed their color.
+        let mut sum = [0.0; 4];
+        for (dj, weight_y) in offsets.iter().zip(weights_y.iter()) {
+            for (di, weight_x) in offsets.iter().zip(weights_x.iter()) {
+                let pixel = pixel(i0 + di, j0 + dj);
+                let alpha = pixel[3] as f32 / 255.0;
+                let weight = weight_x * weight_y;
+                sum[0] += pixel[0] as f32 * alpha * weight;
+                sum[1] += pixel[1] as f32 * alpha * weight;
+                sum[2] += pixel[2] as f32 * alpha * weight;
+                sum[3] += alpha * weight;
+            }
+        }
+        let alpha = sum[3].clamp(0.0, 1.0);
+        if sum[3] <= 0.0 {
+            return Color::default();
+        }
+        let channel = |value: f32| (value / sum[3]).round().clamp(0.0, 255.0) as u8;
+        Color::new(channel(sum[0]), channel(sum[1]), channel(sum[2]), alpha)
+    }
     /// Sets a pixel color at a Point2D in the bitmap.
     pub fn set_pixel(
         &mut self,
@@ -605,6 +1750,7 @@ impl ImageBitmap {
             data_width,
             data_height,
             rgba_image: rgba_image.clone(),
+            resampling: ImageResampling::default(),
         }
     }
     /// Gets a bitmap that is filled with a linear gradient.
@@ -638,6 +1784,7 @@ impl ImageBitmap {
             data_width,
             data_height,
             rgba_image: rgba_image.clone(),
+            resampling: ImageResampling::default(),
         }
     }
     /// Gets a bitmap that is filled with a radial gradient.
@@ -671,8 +1818,175 @@ impl ImageBitmap {
             data_width,
             data_height,
             rgba_image: rgba_image.clone(),
+            resampling: ImageResampling::default(),
         }
     }
+    /// Gets a bitmap that is filled with a conic gradient.
+    #[wasm_bindgen(return_description = "The filled image bitmap.")]
+    pub fn fill_conic_gradient(
+        #[wasm_bindgen(param_description = "The x coordinate of the bitmap.")]
+        x: f32,
+        #[wasm_bindgen(param_description = "The y coordinate of the bitmap.")]
+        y: f32,
+        #[wasm_bindgen(param_description = "The width of the bitmap.")]
+        width: f32,
+        #[wasm_bindgen(param_description = "The height of the bitmap.")]
+        height: f32,
+        #[wasm_bindgen(param_description = "Number of pixels in a row of the bitmap.")]
+        data_width: usize,
+        #[wasm_bindgen(param_description = "Number of pixels in a column of the bitmap.")]
+        data_height: usize,
+        #[wasm_bindgen(param_description = "The conic gradient to fill the bitmap with.")]
+        gradient: &ConicGradient,
+    ) -> ImageBitmap {
+        let rgba_image = RgbaImage::from_fn(data_width as u32, data_height as u32, |x_raw, y_raw| {
+            let p = Point2D::new(x_raw as f32 / data_width as f32 * width + x, y_raw as f32 / data_height as f32 * height + y);
+            let color = gradient.color_at(p);
+            image::Rgba([color.red, color.green, color.blue, (color.alpha * 255.0) as u8])
+        });
+        ImageBitmap {
+            x,
+            y,
+            width,
+            height,
+            data_width,
+            data_height,
+            rgba_image: rgba_image.clone(),
+            resampling: ImageResampling::default(),
+        }
+    }
+    /// Resizes the bitmap to a given number of pixels, keeping its rectangle.
+    #[wasm_bindgen(return_description = "The resized image bitmap.")]
+    pub fn resize(
+        &self,
+        #[wasm_bindgen(param_description = "Number of pixels in a row of the resized bitmap.")]
+        data_width: usize,
+        #[wasm_bindgen(param_description = "Number of pixels in a column of the resized bitmap.")]
+        data_height: usize,
+        #[wasm_bindgen(param_description = "The filter used to compute the pixels, default is bilinear.")]
+        filter: Option<ResizeFilter>
+    ) -> ImageBitmap {
+        let rgba_image = imageops::resize(&self.rgba_image, data_width as u32, data_height as u32, filter.unwrap_or_default().filter_type());
+        self.with_rgba_image(rgba_image)
+    }
+    /// Crops the bitmap to a rectangle of pixels, shrinking its rectangle accordingly.
+    #[wasm_bindgen(return_description = "The cropped image bitmap.")]
+    pub fn crop(
+        &self,
+        #[wasm_bindgen(param_description = "The column of the top left pixel of the crop.")]
+        pixel_x: usize,
+        #[wasm_bindgen(param_description = "The row of the top left pixel of the crop.")]
+        pixel_y: usize,
+        #[wasm_bindgen(param_description = "Number of pixels in a row of the crop.")]
+        data_width: usize,
+        #[wasm_bindgen(param_description = "Number of pixels in a column of the crop.")]
+        data_height: usize
+    ) -> Result<ImageBitmap, JsError> {
+        let (pixels_x, pixels_y) = (self.rgba_image.width() as usize, self.rgba_image.height() as usize);
+        if pixel_x + data_width > pixels_x || pixel_y + data_height > pixels_y {
+            return Err(JsError::new("The crop must be inside of the bitmap."));
+        }
+        let rgba_image = imageops::crop_imm(&self.rgba_image, pixel_x as u32, pixel_y as u32, data_width as u32, data_height as u32).to_image();
+        let pixel_width = self.width / pixels_x as f32;
+        let pixel_height = self.height / pixels_y as f32;
+        Ok(ImageBitmap {
+            x: self.x + pixel_x as f32 * pixel_width,
+            y: self.y + pixel_y as f32 * pixel_height,
+            width: data_width as f32 * pixel_width,
+            height: data_height as f32 * pixel_height,
+            data_width,
+            data_height,
+            rgba_image,
+            resampling: self.resampling,
+        })
+    }
+    /// Blurs the bitmap with a Gaussian kernel.
+    #[wasm_bindgen(return_description = "The blurred image bitmap.")]
+    pub fn blur(
+        &self,
+        #[wasm_bindgen(param_description = "The standard deviation of the Gaussian kernel in pixels.")]
+        sigma: f32
+    ) -> ImageBitmap {
+        self.with_rgba_image(imageops::blur(&self.rgba_image, sigma))
+    }
+    /// Transforms the colors of the bitmap with a 4x5 matrix in row-major order, like the SVG feColorMatrix filter. Every row computes a channel of red, green, blue and alpha from 0 to 1 as a weighted sum of those channels plus a constant.
+    #[wasm_bindgen(return_description = "The image bitmap with transformed colors.")]
+    pub fn color_matrix(
+        &self,
+        #[wasm_bindgen(param_description = "The 20 entries of the matrix in row-major order.")]
+        matrix: Vec<f32>
+    ) -> Result<ImageBitmap, JsError> {
+        let matrix: [f32; 20] = matrix.try_into().map_err(|_| JsError::new("The color matrix must have 20 entries."))?;
+        Ok(self.apply_color_matrix(&matrix))
+    }
+    /// Scales the color channels of the bitmap, where 1 keeps the bitmap unchanged and 0 turns it black.
+    #[wasm_bindgen(return_description = "The image bitmap with adjusted brightness.")]
+    pub fn brightness(
+        &self,
+        #[wasm_bindgen(param_description = "The factor to scale the color channels by.")]
+        factor: f32
+    ) -> ImageBitmap {
+        self.apply_color_matrix(&[
+            factor, 0.0, 0.0, 0.0, 0.0,
+            0.0, factor, 0.0, 0.0, 0.0,
+            0.0, 0.0, factor, 0.0, 0.0,
+            0.0, 0.0, 0.0, 1.0, 0.0,
+        ])
+    }
+    /// Scales the distance of the color channels of the bitmap to middle gray, where 1 keeps the bitmap unchanged and 0 turns it gray.
+    #[wasm_bindgen(return_description = "The image bitmap with adjusted contrast.")]
+    pub fn contrast(
+        &self,
+        #[wasm_bindgen(param_description = "The factor to scale the contrast by.")]
+        factor: f32
+    ) -> ImageBitmap {
+        let offset = 0.5 * (1.0 - factor);
+        self.apply_color_matrix(&[
+            factor, 0.0, 0.0, 0.0, offset,
+            0.0, factor, 0.0, 0.0, offset,
+            0.0, 0.0, factor, 0.0, offset,
+            0.0, 0.0, 0.0, 1.0, 0.0,
+        ])
+    }
+    /// Scales the saturation of the bitmap, where 1 keeps the bitmap unchanged and 0 turns it grayscale.
+    #[wasm_bindgen(return_description = "The image bitmap with adjusted saturation.")]
+    pub fn saturation(
+        &self,
+        #[wasm_bindgen(param_description = "The factor to scale the saturation by.")]
+        factor: f32
+    ) -> ImageBitmap {
+        self.apply_color_matrix(&saturation_matrix(factor))
+    }
+    /// Turns the bitmap into grayscale using the luminance of its colors.
+    #[wasm_bindgen(return_description = "The grayscale image bitmap.")]
+    pub fn grayscale(&self) -> ImageBitmap {
+        self.apply_color_matrix(&saturation_matrix(0.0))
+    }
+    /// Composites another bitmap over this one, placing it at its rectangle and resampling it with its resampling.
+    #[wasm_bindgen(return_description = "The composited image bitmap.")]
+    pub fn composite(
+        &self,
+        #[wasm_bindgen(param_description = "The bitmap to draw over this one.")]
+        source: &ImageBitmap,
+        #[wasm_bindgen(param_description = "The opacity of the source bitmap, default is 1.")]
+        opacity: Option<f32>
+    ) -> ImageBitmap {
+        let opacity = opacity.unwrap_or(1.0);
+        let (pixels_x, pixels_y) = (self.rgba_image.width(), self.rgba_image.height());
+        let rgba_image = RgbaImage::from_fn(pixels_x, pixels_y, |i, j| {
+            let pixel = self.rgba_image.get_pixel(i, j);
+            let destination = Color::new(pixel[0], pixel[1], pixel[2], pixel[3] as f32 / 255.0);
+            let p = Point2D::new(
+                self.x + (i as f32 + 0.5) / pixels_x as f32 * self.width,
+                self.y + (j as f32 + 0.5) / pixels_y as f32 * self.height,
+            );
+            let mut color = source.sample_inside(p);
+            color.alpha *= opacity;
+            let color = composite_over(color, destination);
+            image::Rgba([color.red, color.green, color.blue, (color.alpha * 255.0).round() as u8])
+        });
+        self.with_rgba_image(rgba_image)
+    }
     /// Gets the data as base64 encoded string.
     #[wasm_bindgen(getter, return_description = "The base64 encoded string of the image bitmap.")]
     pub fn base64(&self) -> Result<String, String> {
@@ -682,7 +1996,7 @@ impl ImageBitmap {
         let base64 = BASE64_STANDARD.encode(&png_data);
         Ok(base64)
     }
-    /// Linearly interpolates between two ImageBitmaps given a progress value.
+    /// Linearly interpolates between two ImageBitmaps given a progress value. Both bitmaps are stretched to the interpolated rectangle and crossfaded there, with a number of pixels interpolated between theirs, so the result is never larger than the larger of the two.
     #[wasm_bindgen(return_description = "The interpolated image bitmap.")]
     pub fn lerp(
         #[wasm_bindgen(param_description = "The first image bitmap.")]
@@ -691,18 +2005,21 @@ impl ImageBitmap {
         bitmap2: &ImageBitmap,
         #[wasm_bindgen(param_description = "The progress value.")]
         t: f32,
+        #[wasm_bindgen(param_description = "The color space in which the pixels are interpolated, default is sRGB.")]
+        color_space: Option<ColorSpace>,
     ) -> ImageBitmap {
-        let x = bitmap1.x.min(bitmap2.x);
-        let y = bitmap1.y.min(bitmap2.y);
-        let width = bitmap1.width.max(bitmap2.width);
-        let height = bitmap1.height.max(bitmap2.height);
-        let data_width = bitmap1.data_width.max(bitmap2.data_width);
-        let data_height = bitmap1.data_height.max(bitmap2.data_height);
+        let x = lerp(bitmap1.x, bitmap2.x, t);
+        let y = lerp(bitmap1.y, bitmap2.y, t);
+        let width = lerp(bitmap1.width, bitmap2.width, t);
+        let height = lerp(bitmap1.height, bitmap2.height, t);
+        let data_width = lerp(bitmap1.rgba_image.width() as f32, bitmap2.rgba_image.width() as f32, t).round() as usize;
+        let data_height = lerp(bitmap1.rgba_image.height() as f32, bitmap2.rgba_image.height() as f32, t).round() as usize;
+        // The same fraction of each rectangle is sampled for a pixel, so the bitmaps move and resize with the frame.
+        let at_fraction = |bitmap: &ImageBitmap, u: f32, v: f32| bitmap.sample_inside(Point2D::new(bitmap.x + u * bitmap.width, bitmap.y + v * bitmap.height));
         let new_image = RgbaImage::from_fn(data_width as u32, data_height as u32, |x_raw, y_raw| {
-            let p = Point2D::new(x_raw as f32 / data_width as f32 * width + x, y_raw as f32 / data_height as f32 * height + y);
-            let color1 = bitmap1.get_pixel(p);
-            let color2 = bitmap2.get_pixel(p);
-            let color = Color::lerp(&color1, &color2, t);
+            let u = (x_raw as f32 + 0.5) / data_width as f32;
+            let v = (y_raw as f32 + 0.5) / data_height as f32;
+            let color = Color::lerp(&at_fraction(bitmap1, u, v), &at_fraction(bitmap2, u, v), t, color_space);
             image::Rgba([color.red, color.green, color.blue, (color.alpha * 255.0) as u8])
         });
         ImageBitmap {
@@ -712,12 +2029,53 @@ impl ImageBitmap {
             height,
             data_width,
             data_height,
-            rgba_image: new_image.clone(),
+            rgba_image: new_image,
+            resampling: bitmap1.resampling,
+        }
+    }
+}
+
+impl ImageBitmap {
+    /// Gets the RGBA pixels of the bitmap.
+    pub fn rgba_image(&self) -> &RgbaImage {
+        &self.rgba_image
+    }
+    /// Gets an ImageBitmap with the same rectangle and resampling and other pixels.
+    fn with_rgba_image(&self, rgba_image: RgbaImage) -> ImageBitmap {
+        ImageBitmap {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+            data_width: rgba_image.width() as usize,
+            data_height: rgba_image.height() as usize,
+            rgba_image,
+            resampling: self.resampling,
+        }
+    }
+    /// Transforms the colors of the bitmap with a 4x5 matrix in row-major order.
+    fn apply_color_matrix(&self, matrix: &[f32; 20]) -> ImageBitmap {
+        let mut rgba_image = self.rgba_image.clone();
+        for pixel in rgba_image.pixels_mut() {
+            let channels = pixel.0.map(|channel| channel as f32 / 255.0);
+            pixel.0 = std::array::from_fn(|row| {
+                let weights = &matrix[row * 5..row * 5 + 5];
+                let value = weights[4] + (0..4).map(|column| weights[column] * channels[column]).sum::<f32>();
+                (value.clamp(0.0, 1.0) * 255.0).round() as u8
+            });
+        }
+        self.with_rgba_image(rgba_image)
+    }
+    /// Samples the color of the bitmap at a Point2D, which is transparent outside of the bitmap's rectangle.
+    pub fn sample_inside(&self, p: Point2D) -> Color {
+        if p.x < self.x || p.y < self.y || p.x >= self.x + self.width || p.y >= self.y + self.height {
+            return Color::default();
         }
+        self.sample(p)
     }
 }
 
-/// A style with a color, linear gradient, radial gradient, or image.
+/// A style with a color, linear gradient, radial gradient, conic gradient, pattern, or image.
 #[wasm_bindgen]
 #[derive(Debug, Clone)]
 pub struct Style {
@@ -729,6 +2087,10 @@ pub struct Style {
     radial_gradient: Option<RadialGradient>,
     /// The image of the style.
     image: Option<ImageBitmap>,
+    /// The conic gradient of the style.
+    conic_gradient: Option<ConicGradient>,
+    /// The pattern of the style.
+    pattern: Option<Pattern>,
 }
 
 impl Default for Style {
@@ -738,13 +2100,15 @@ impl Default for Style {
             linear_gradient: None,
             radial_gradient: None,
             image: None,
+            conic_gradient: None,
+            pattern: None,
         }
     }
 }
 
 #[wasm_bindgen]
 impl Style {
-    /// Creates a new Style with the given color, linear gradient, radial gradient, or image. It must have exactly one of these.
+    /// Creates a new Style with the given color, linear gradient, radial gradient, image, conic gradient, or pattern. It must have exactly one of these.
     #[wasm_bindgen(constructor, return_description = "A new style.")]
     pub fn new(
         #[wasm_bindgen(param_description = "The color of the style, if provided.")]
@@ -754,7 +2118,11 @@ impl Style {
         #[wasm_bindgen(param_description = "The radial gradient of the style, if provided.")]
         radial_gradient: Option<RadialGradient>,
         #[wasm_bindgen(param_description = "The image of the style, if provided.")]
-        image: Option<ImageBitmap>
+        image: Option<ImageBitmap>,
+        #[wasm_bindgen(param_description = "The conic gradient of the style, if provided.")]
+        conic_gradient: Option<ConicGradient>,
+        #[wasm_bindgen(param_description = "The pattern of the style, if provided.")]
+        pattern: Option<Pattern>
     ) -> Result<Style, JsError> {
         let mut not_none = 0;
         if color.is_some() {
@@ -769,8 +2137,14 @@ impl Style {
         if image.is_some() {
             not_none += 1;
         }
+        if conic_gradient.is_some() {
+            not_none += 1;
+        }
+        if pattern.is_some() {
+            not_none += 1;
+        }
         if not_none != 1 {
-            let err = JsError::new("Exactly one of color, linear_gradient, radial_gradient, or image must be provided.");
+            let err = JsError::new("Exactly one of color, linear_gradient, radial_gradient, image, conic_gradient, or pattern must be provided.");
             return Err(err);
         }
         Ok(Style {
@@ -778,6 +2152,8 @@ impl Style {
             linear_gradient,
             radial_gradient,
             image,
+            conic_gradient,
+            pattern,
         })
     }
     /// Clones the Style.
@@ -791,7 +2167,7 @@ impl Style {
         #[wasm_bindgen(param_description = "The color of the style.")]
         color: Color
     ) -> Style {
-        Style::new(Some(color), None, None, None).unwrap()
+        Style::new(Some(color), None, None, None, None, None).unwrap()
     }
     /// Creates a new Style with the given linear gradient.
     #[wasm_bindgen(return_description = "A new style from the linear gradient.")]
@@ -799,12 +2175,12 @@ impl Style {
         #[wasm_bindgen(param_description = "The linear gradient of the style.")]
         gradient: LinearGradient
     ) -> Style {
-        Style::new(None, Some(gradient), None, None).unwrap()
+        Style::new(None, Some(gradient), None, None, None, None).unwrap()
     }
     /// Creates a new Style with the given radial gradient.
     #[wasm_bindgen(return_description = "A new style from the radial gradient.")]
     pub fn from_radial_gradient(gradient: RadialGradient) -> Style {
-        Style::new(None, None, Some(gradient), None).unwrap()
+        Style::new(None, None, Some(gradient), None, None, None).unwrap()
     }
     /// Creates a new Style with the given image.
     #[wasm_bindgen(return_description = "A new style from the image.")]
@@ -812,7 +2188,23 @@ impl Style {
         #[wasm_bindgen(param_description = "The image of the style.")]
         image: ImageBitmap
     ) -> Style {
-        Style::new(None, None, None, Some(image)).unwrap()
+        Style::new(None, None, None, Some(image), None, None).unwrap()
+    }
+    /// Creates a new Style with the given conic gradient.
+    #[wasm_bindgen(return_description = "A new style from the conic gradient.")]
+    pub fn from_conic_gradient(
+        #[wasm_bindgen(param_description = "The conic gradient of the style.")]
+        gradient: ConicGradient
+    ) -> Style {
+        Style::new(None, None, None, None, Some(gradient), None).unwrap()
+    }
+    /// Creates a new Style with the given pattern.
+    #[wasm_bindgen(return_description = "A new style from the pattern.")]
+    pub fn from_pattern(
+        #[wasm_bindgen(param_description = "The pattern of the style.")]
+        pattern: Pattern
+    ) -> Style {
+        Style::new(None, None, None, None, None, Some(pattern)).unwrap()
     }
     /// Returns the default Style, which is a color with the default color.
     #[wasm_bindgen(return_description = "The default style.")]
@@ -830,6 +2222,8 @@ impl Style {
         let mut linear_gradient = self.linear_gradient.clone();
         let mut radial_gradient = self.radial_gradient.clone();
         let mut image = self.image.clone();
+        let mut conic_gradient = self.conic_gradient.clone();
+        let pattern = self.pattern.as_ref().map(|pattern| pattern.fade(amount));
         if let Some(color) = &mut color {
             color.alpha = color.alpha * (1.0 - amount);
         }
@@ -843,6 +2237,11 @@ impl Style {
                 stop.color.alpha = stop.color.alpha * (1.0 - amount);
             }
         }
+        if let Some(conic_gradient) = &mut conic_gradient {
+            for stop in Rc::make_mut(&mut conic_gradient.color_stops) {
+                stop.color.alpha *= 1.0 - amount;
+            }
+        }
         if let Some(image) = &mut image {
             for pixel in image.rgba_image.pixels_mut() {
                 pixel[3] = (pixel[3] as f32 * (1.0 - amount)) as u8;
@@ -853,6 +2252,8 @@ impl Style {
             linear_gradient,
             radial_gradient,
             image,
+            conic_gradient,
+            pattern,
         }
     }
     /// Gets the Color of the style, if it's a color.
@@ -871,6 +2272,8 @@ impl Style {
         self.linear_gradient = None;
         self.radial_gradient = None;
         self.image = None;
+        self.conic_gradient = None;
+        self.pattern = None;
     }
     /// Gets the LinearGradient of the style, if it's a linear gradient.
     #[wasm_bindgen(getter, return_description = "The linear gradient of the style.")]
@@ -888,6 +2291,8 @@ impl Style {
         self.linear_gradient = Some(linear_gradient);
         self.radial_gradient = None;
         self.image = None;
+        self.conic_gradient = None;
+        self.pattern = None;
     }
     /// Gets the RadialGradient of the style, if it's a radial gradient.
     #[wasm_bindgen(getter, return_description = "The radial gradient of the style.")]
@@ -905,6 +2310,8 @@ impl Style {
         self.linear_gradient = None;
         self.radial_gradient = Some(radial_gradient);
         self.image = None;
+        self.conic_gradient = None;
+        self.pattern = None;
     }
     /// Gets the ImageBitmap of the style, if it's an image.
     #[wasm_bindgen(getter, return_description = "The image of the style.")]
@@ -918,6 +2325,46 @@ impl Style {
         self.linear_gradient = None;
         self.radial_gradient = None;
         self.image = Some(image);
+        self.conic_gradient = None;
+        self.pattern = None;
+    }
+    /// Gets the ConicGradient of the style, if it's a conic gradient.
+    #[wasm_bindgen(getter, return_description = "The conic gradient of the style.")]
+    pub fn conic_gradient(&self) -> Option<ConicGradient> {
+        self.conic_gradient.clone()
+    }
+    /// Sets the style to a ConicGradient.
+    #[wasm_bindgen(setter)]
+    pub fn set_conic_gradient(
+        &mut self,
+        #[wasm_bindgen(param_description = "The conic gradient of the style.")]
+        conic_gradient: ConicGradient
+    ) {
+        self.color = None;
+        self.linear_gradient = None;
+        self.radial_gradient = None;
+        self.image = None;
+        self.conic_gradient = Some(conic_gradient);
+        self.pattern = None;
+    }
+    /// Gets the Pattern of the style, if it's a pattern.
+    #[wasm_bindgen(getter, return_description = "The pattern of the style.")]
+    pub fn pattern(&self) -> Option<Pattern> {
+        self.pattern.clone()
+    }
+    /// Sets the style to a Pattern.
+    #[wasm_bindgen(setter)]
+    pub fn set_pattern(
+        &mut self,
+        #[wasm_bindgen(param_description = "The pattern of the style.")]
+        pattern: Pattern
+    ) {
+        self.color = None;
+        self.linear_gradient = None;
+        self.radial_gradient = None;
+        self.image = None;
+        self.conic_gradient = None;
+        self.pattern = Some(pattern);
     }
     /// Gets the Color at a given point.
     #[wasm_bindgen(return_description = "The color at the given point.")]
@@ -935,12 +2382,51 @@ impl Style {
         if let Some(radial_gradient) = self.radial_gradient() {
             return radial_gradient.color_at(p);
         }
-        if let Some(image) = self.image() {
-            return image.get_pixel(p);
+        if let Some(image) = &self.image {
+            return image.sample(p);
+        }
+        if let Some(conic_gradient) = &self.conic_gradient {
+            return conic_gradient.color_at(p);
+        }
+        if let Some(pattern) = &self.pattern {
+            return pattern.color_at(p);
         }
         Color::default()
     }
-    /// Linearly interpolates between two Styles given a progress value.
+    /// Rasterizes the Style into an ImageBitmap covering a given rectangle.
+    #[wasm_bindgen(return_description = "The rasterized style.")]
+    pub fn to_image_bitmap(
+        &self,
+        #[wasm_bindgen(param_description = "The x coordinate of the bitmap.")]
+        x: f32,
+        #[wasm_bindgen(param_description = "The y coordinate of the bitmap.")]
+        y: f32,
+        #[wasm_bindgen(param_description = "The width of the bitmap.")]
+        width: f32,
+        #[wasm_bindgen(param_description = "The height of the bitmap.")]
+        height: f32,
+        #[wasm_bindgen(param_description = "Number of pixels in a row of the bitmap.")]
+        data_width: usize,
+        #[wasm_bindgen(param_description = "Number of pixels in a column of the bitmap.")]
+        data_height: usize,
+    ) -> ImageBitmap {
+        let rgba_image = RgbaImage::from_fn(data_width as u32, data_height as u32, |x_raw, y_raw| {
+            let p = Point2D::new(x_raw as f32 / data_width as f32 * width + x, y_raw as f32 / data_height as f32 * height + y);
+            let color = self.color_at(p);
+            image::Rgba([color.red, color.green, color.blue, (color.alpha * 255.0) as u8])
+        });
+        ImageBitmap {
+            x,
+            y,
+            width,
+            height,
+            data_width,
+            data_height,
+            rgba_image,
+            resampling: ImageResampling::default(),
+        }
+    }
+    /// Linearly interpolates between two Styles given a progress value. Styles of the same kind are interpolated directly, a color and a gradient are interpolated by promoting the color to a single color gradient, gradients of different kinds both fade to the same solid color halfway, and any other pair of styles is rasterized into bitmaps which are interpolated. Styles of different kinds are returned unchanged at the ends, where t is 0 or 1.
     #[wasm_bindgen(return_description = "The interpolated style.")]
     pub fn lerp(
         #[wasm_bindgen(param_description = "The first style.")]
@@ -949,146 +2435,104 @@ impl Style {
         style2: &Style,
         #[wasm_bindgen(param_description = "The progress value.")]
         t: f32,
-        #[wasm_bindgen(param_description = "Top left x coordinate of the bitmap. Must be provided if both styles are images.")]
+        #[wasm_bindgen(param_description = "Top left x coordinate of the bitmap. Must be provided if the styles have to be rasterized.")]
         x: Option<f32>,
-        #[wasm_bindgen(param_description = "Top left y coordinate of the bitmap. Must be provided if both styles are images.")]
+        #[wasm_bindgen(param_description = "Top left y coordinate of the bitmap. Must be provided if the styles have to be rasterized.")]
         y: Option<f32>,
-        #[wasm_bindgen(param_description = "Width of the bitmap. Must be provided if both styles are images.")]
+        #[wasm_bindgen(param_description = "Width of the bitmap. Must be provided if the styles have to be rasterized.")]
         width: Option<f32>,
-        #[wasm_bindgen(param_description = "Height of the bitmap. Must be provided if both styles are images.")]
+        #[wasm_bindgen(param_description = "Height of the bitmap. Must be provided if the styles have to be rasterized.")]
         height: Option<f32>,
-        #[wasm_bindgen(param_description = "Number of pixels in a row of the bitmap. Must be provided if both styles are different kinds of gradients or one of them is an image.")]
+        #[wasm_bindgen(param_description = "Number of pixels in a row of the bitmap. Must be provided if the styles have to be rasterized.")]
         data_width: Option<usize>,
-        #[wasm_bindgen(param_description = "Number of pixels in a column of the bitmap. Must be provided if both styles are different kinds of gradients or one of them is an image.")]
-        data_height: Option<usize>
+        #[wasm_bindgen(param_description = "Number of pixels in a column of the bitmap. Must be provided if the styles have to be rasterized.")]
+        data_height: Option<usize>,
+        #[wasm_bindgen(param_description = "The color space in which colors are interpolated, default is sRGB.")]
+        color_space: Option<ColorSpace>
     ) -> Result<Style, String> {
-        let color1 = style1.color();
-        let color2 = style2.color();
-        let linear_gradient1 = style1.linear_gradient();
-        let linear_gradient2 = style2.linear_gradient();
-        let radial_gradient1 = style1.radial_gradient();
-        let radial_gradient2 = style2.radial_gradient();
-        let image1 = style1.image();
-        let image2 = style2.image();
-        if color1.is_some() && color2.is_some() {
-            let color = Color::lerp(&color1.unwrap(), &color2.unwrap(), t);
-            return Ok(Style::from_color(color));
-        }
-        if linear_gradient1.is_some() && linear_gradient2.is_some() {
-            let linear_gradient = LinearGradient::lerp(&linear_gradient1.unwrap(), &linear_gradient2.unwrap(), t);
-            return Ok(Style::from_linear_gradient(linear_gradient));
-        }
-        if radial_gradient1.is_some() && radial_gradient2.is_some() {
-            let radial_gradient = RadialGradient::lerp(&radial_gradient1.unwrap(), &radial_gradient2.unwrap(), t);
-            return Ok(Style::from_radial_gradient(radial_gradient));
-        }
-        if image1.is_some() && image2.is_some() {
-            let image = ImageBitmap::lerp(&image1.unwrap(), &image2.unwrap(), t);
-            return Ok(Style::from_image(image));
-        }
-        if color1.is_some() {
-            let color = color1.unwrap();
-            if linear_gradient2.is_some() {
-                let linear_gradient2 = linear_gradient2.unwrap();
-                let linear_gradient1 = LinearGradient::single_color_gradient(linear_gradient2.p1, linear_gradient2.p2, color, Some(linear_gradient2.color_stops.len()));
-                return Style::lerp(&Style::from_linear_gradient(linear_gradient1), &Style::from_linear_gradient(linear_gradient2), t, x, y, width, height, data_width, data_height);
-            }
-            if radial_gradient2.is_some() {
-                let radial_gradient2 = radial_gradient2.unwrap();
-                let radial_gradient1 = RadialGradient::single_color_gradient(radial_gradient2.f, radial_gradient2.c, radial_gradient2.r, color, Some(radial_gradient2.color_stops.len()));
-                return Style::lerp(&Style::from_radial_gradient(radial_gradient1), &Style::from_radial_gradient(radial_gradient2), t, x, y, width, height, data_width, data_height);
-            }
-            if image2.is_some() {
-                if x.is_none() || y.is_none() || width.is_none() || height.is_none() {
-                    return Err("Bitmap data must be provided if one of the styles is an image.".to_string());
-                }
-                let image2 = image2.unwrap();
-                let image1 = ImageBitmap::fill(x.unwrap(), y.unwrap(), width.unwrap(), height.unwrap(), data_width.unwrap(), data_height.unwrap(), &color);
-                return Style::lerp(&Style::from_image(image1), &Style::from_image(image2), t, x, y, width, height, data_width, data_height);
-            }
+        if let (Some(color1), Some(color2)) = (&style1.color, &style2.color) {
+            return Ok(Style::from_color(Color::lerp(color1, color2, t, color_space)));
         }
-        if linear_gradient1.is_some() {
-            let linear_gradient1 = linear_gradient1.unwrap();
-            if color2.is_some() {
-                let color2 = color2.unwrap();
-                let linear_gradient2 = LinearGradient::single_color_gradient(linear_gradient1.p1, linear_gradient1.p2, color2, Some(linear_gradient1.color_stops.len()));
-                return Style::lerp(&Style::from_linear_gradient(linear_gradient1), &Style::from_linear_gradient(linear_gradient2), t, x, y, width, height, data_width, data_height);
-            }
-            if radial_gradient2.is_some() {
-                if x.is_none() || y.is_none() || width.is_none() || height.is_none() {
-                    return Err("Bitmap data must be provided if both styles are different kinds of gradients.".to_string());
-                }
-                let radial_gradient2 = radial_gradient2.unwrap();
-                let image2 = ImageBitmap::fill_radial_gradient(x.unwrap(), y.unwrap(), width.unwrap(), height.unwrap(), data_width.unwrap(), data_height.unwrap(), &radial_gradient2);
-                let image1 = ImageBitmap::fill_linear_gradient(x.unwrap(), y.unwrap(), width.unwrap(), height.unwrap(), data_width.unwrap(), data_height.unwrap(), &linear_gradient1);
-                return Style::lerp(&Style::from_image(image1), &Style::from_image(image2), t, x, y, width, height, data_width, data_height);
-            }
-            if image2.is_some() {
-                if x.is_none() || y.is_none() || width.is_none() || height.is_none() {
-                    return Err("Bitmap data must be provided if both styles are different kinds of gradients.".to_string());
-                }
-                let image2 = image2.unwrap();
-                let image1 = ImageBitmap::fill_linear_gradient(x.unwrap(), y.unwrap(), width.unwrap(), height.unwrap(), data_width.unwrap(), data_height.unwrap(), &linear_gradient1);
-                return Style::lerp(&Style::from_image(image1), &Style::from_image(image2), t, x, y, width, height, data_width, data_height);
-            }
+        if let (Some(gradient1), Some(gradient2)) = (&style1.linear_gradient, &style2.linear_gradient) {
+            return Ok(Style::from_linear_gradient(LinearGradient::lerp(gradient1, gradient2, t, color_space)));
         }
-        if radial_gradient1.is_some() {
-            let radial_gradient1 = radial_gradient1.unwrap();
-            if color2.is_some() {
-                let color2 = color2.unwrap();
-                let radial_gradient2 = RadialGradient::single_color_gradient(radial_gradient1.f, radial_gradient1.c, radial_gradient1.r, color2, Some(radial_gradient1.color_stops.len()));
-                return Style::lerp(&Style::from_radial_gradient(radial_gradient1), &Style::from_radial_gradient(radial_gradient2), t, x, y, width, height, data_width, data_height);
-            }
-            if linear_gradient2.is_some() {
-                if x.is_none() || y.is_none() || width.is_none() || height.is_none() {
-                    return Err("Bitmap data must be provided if both styles are different kinds of gradients.".to_string());
-                }
-                let linear_gradient2 = linear_gradient2.unwrap();
-                let image2 = ImageBitmap::fill_linear_gradient(x.unwrap(), y.unwrap(), width.unwrap(), height.unwrap(), data_width.unwrap(), data_height.unwrap(), &linear_gradient2);
-                let image1 = ImageBitmap::fill_radial_gradient(x.unwrap(), y.unwrap(), width.unwrap(), height.unwrap(), data_width.unwrap(), data_height.unwrap(), &radial_gradient1);
-                return Style::lerp(&Style::from_image(image1), &Style::from_image(image2), t, x, y, width, height, data_width, data_height);
-            }
-            if image2.is_some() {
-                if x.is_none() || y.is_none() || width.is_none() || height.is_none() {
-                    return Err("Bitmap data must be provided if both styles are different kinds of gradients.".to_string());
-                }
-                let image2 = image2.unwrap();
-                let image1 = ImageBitmap::fill_radial_gradient(x.unwrap(), y.unwrap(), width.unwrap(), height.unwrap(), data_width.unwrap(), data_height.unwrap(), &radial_gradient1);
-                return Style::lerp(&Style::from_image(image1), &Style::from_image(image2), t, x, y, width, height, data_width, data_height);
+        if let (Some(gradient1), Some(gradient2)) = (&style1.radial_gradient, &style2.radial_gradient) {
+            return Ok(Style::from_radial_gradient(RadialGradient::lerp(gradient1, gradient2, t, color_space)));
+        }
+        if let (Some(gradient1), Some(gradient2)) = (&style1.conic_gradient, &style2.conic_gradient) {
+            return Ok(Style::from_conic_gradient(ConicGradient::lerp(gradient1, gradient2, t, color_space)));
+        }
+        if let (Some(image1), Some(image2)) = (&style1.image, &style2.image) {
+            return Ok(Style::from_image(ImageBitmap::lerp(image1, image2, t, color_space)));
+        }
+        if let (Some(pattern1), Some(pattern2)) = (&style1.pattern, &style2.pattern) {
+            if let Ok(pattern) = Pattern::lerp(pattern1, pattern2, t, color_space) {
+                return Ok(Style::from_pattern(pattern));
             }
         }
-        if image1.is_some() {
-            let image1 = image1.unwrap();
-            if color2.is_some() {
-                if x.is_none() || y.is_none() || width.is_none() || height.is_none() {
-                    return Err("Bitmap data must be provided if one of the styles is an image.".to_string());
-                }
-                let color2 = color2.unwrap();
-                let image2 = ImageBitmap::fill(x.unwrap(), y.unwrap(), width.unwrap(), height.unwrap(), data_width.unwrap(), data_height.unwrap(), &color2);
-                return Style::lerp(&Style::from_image(image1), &Style::from_image(image2), t, x, y, width, height, data_width, data_height);
+        // Styles of different kinds are only approximations of each other in between, so the ends are exact.
+        if t <= 0.0 {
+            return Ok(style1.clone());
+        }
+        if t >= 1.0 {
+            return Ok(style2.clone());
+        }
+        if let Some(color) = style1.color {
+            if let Some(promoted) = style2.with_color(color) {
+                return Style::lerp(&promoted, style2, t, x, y, width, height, data_width, data_height, color_space);
             }
-            if linear_gradient2.is_some() {
-                if x.is_none() || y.is_none() || width.is_none() || height.is_none() {
-                    return Err("Bitmap data must be provided if one of the styles is an image.".to_string());
-                }
-                let linear_gradient2 = linear_gradient2.unwrap();
-                let image2 = ImageBitmap::fill_linear_gradient(x.unwrap(), y.unwrap(), width.unwrap(), height.unwrap(), data_width.unwrap(), data_height.unwrap(), &linear_gradient2);
-                return Style::lerp(&Style::from_image(image1), &Style::from_image(image2), t, x, y, width, height, data_width, data_height);
+        }
+        if let Some(color) = style2.color {
+            if let Some(promoted) = style1.with_color(color) {
+                return Style::lerp(style1, &promoted, t, x, y, width, height, data_width, data_height, color_space);
             }
-            if radial_gradient2.is_some() {
-                if x.is_none() || y.is_none() || width.is_none() || height.is_none() {
-                    return Err("Bitmap data must be provided if one of the styles is an image.".to_string());
-                }
-                let radial_gradient2 = radial_gradient2.unwrap();
-                let image2 = ImageBitmap::fill_radial_gradient(x.unwrap(), y.unwrap(), width.unwrap(), height.unwrap(), data_width.unwrap(), data_height.unwrap(), &radial_gradient2);
-                return Style::lerp(&Style::from_image(image1), &Style::from_image(image2), t, x, y, width, height, data_width, data_height);
+        }
+        if let (Some(stops1), Some(stops2)) = (style1.gradient_color_stops(), style2.gradient_color_stops()) {
+            // Both gradients fade to the same solid color halfway, where switching from one kind to the other can't be seen.
+            let middle = Color::lerp(&mean_color(stops1), &mean_color(stops2), 0.5, color_space);
+            if let (Some(faded1), Some(faded2)) = (style1.with_color(middle), style2.with_color(middle)) {
+                return if t < 0.5 {
+                    Style::lerp(style1, &faded1, 2.0 * t, x, y, width, height, data_width, data_height, color_space)
+                } else {
+                    Style::lerp(&faded2, style2, 2.0 * t - 1.0, x, y, width, height, data_width, data_height, color_space)
+                };
             }
         }
-        Err("Exactly one of color, linear_gradient, radial_gradient, or image must be provided.".to_string())
+        let (Some(x), Some(y), Some(width), Some(height), Some(data_width), Some(data_height)) = (x, y, width, height, data_width, data_height) else {
+            return Err("Bitmap data must be provided to interpolate between these kinds of styles.".to_string());
+        };
+        let image1 = style1.image.clone().unwrap_or_else(|| style1.to_image_bitmap(x, y, width, height, data_width, data_height));
+        let image2 = style2.image.clone().unwrap_or_else(|| style2.to_image_bitmap(x, y, width, height, data_width, data_height));
+        Ok(Style::from_image(ImageBitmap::lerp(&image1, &image2, t, color_space)))
     }
 }
 
 impl Style {
+    /// Gets the color stops of the style if it's a gradient.
+    fn gradient_color_stops(&self) -> Option<&[ColorStop]> {
+        self.linear_gradient.as_ref().map(|gradient| gradient.color_stops.as_slice())
+            .or_else(|| self.radial_gradient.as_ref().map(|gradient| gradient.color_stops.as_slice()))
+            .or_else(|| self.conic_gradient.as_ref().map(|gradient| gradient.color_stops.as_slice()))
+    }
+    /// Gets a gradient Style of the same kind and geometry as this one, painted with a single color, or None if the style isn't a gradient.
+    fn with_color(&self, color: Color) -> Option<Style> {
+        if let Some(gradient) = &self.linear_gradient {
+            let mut gradient = gradient.clone();
+            gradient.color_stops = Rc::new(recolor_stops(&gradient.color_stops, color));
+            return Some(Style::from_linear_gradient(gradient));
+        }
+        if let Some(gradient) = &self.radial_gradient {
+            let mut gradient = gradient.clone();
+            gradient.color_stops = Rc::new(recolor_stops(&gradient.color_stops, color));
+            return Some(Style::from_radial_gradient(gradient));
+        }
+        if let Some(gradient) = &self.conic_gradient {
+            let mut gradient = gradient.clone();
+            gradient.color_stops = Rc::new(recolor_stops(&gradient.color_stops, color));
+            return Some(Style::from_conic_gradient(gradient));
+        }
+        None
+    }
     /// Creates a style given a Paint and a Opacity.
     pub fn from_paint_and_opacity(
         paint: &Paint,
@@ -1107,7 +2551,7 @@ impl Style {
                         color: Color::new(stop.color().red, stop.color().green, stop.color().blue, stop.opacity().get() * opacity.get()),
                         position: stop.offset().get(),
                     }
-                }).collect());
+                }).collect(), None, Some(SpreadMethod::from_svg_spread_method(gradient.spread_method())), Some(TransformationMatrix::from_svg_transform(gradient.transform())));
                 Style::from_linear_gradient(linear_gradient)
             }
             Paint::RadialGradient(gradient) => {
@@ -1118,7 +2562,7 @@ impl Style {
                         color: Color::new(stop.color().red, stop.color().green, stop.color().blue, stop.opacity().get() * opacity.get()),
                         position: stop.offset().get(),
                     }
-                }).collect());
+                }).collect(), None, Some(SpreadMethod::from_svg_spread_method(gradient.spread_method())), Some(TransformationMatrix::from_svg_transform(gradient.transform())));
                 Style::from_radial_gradient(radial_gradient)
             }
             Paint::Pattern(pattern) => {
@@ -1128,6 +2572,12 @@ impl Style {
                 let y = bounding_box.y();
                 let width = bounding_box.width();
                 let height = bounding_box.height();
+                if root.children().iter().any(|child| !matches!(child, usvg::Node::Image(_))) {
+                    let tile = VectorObjectBuilder::from_group(root).build();
+                    let transform = TransformationMatrix::from_svg_transform(pattern.transform());
+                    let pattern = Pattern::new(tile, width, height, None, None, Some(Point2D::new(x, y)), Some(transform));
+                    return Style::from_pattern(pattern.fade(1.0 - opacity.get()));
+                }
                 for child in root.children() {
                     return Style::from_pattern_child(&child, x, y, width, height);
                 }
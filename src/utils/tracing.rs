@@ -0,0 +1,201 @@
+use wasm_bindgen::prelude::*;
+
+use super::{point2d::{Path2D, Point2D}, style::LUMA};
+
+/// The channel of a bitmap that is thresholded or quantized when tracing it.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TraceChannel {
+    /// The luminance of the pixels, so dark pixels are traced.
+    #[default]
+    Luminance,
+    /// The red channel of the pixels, so pixels with little red are traced.
+    Red,
+    /// The green channel of the pixels, so pixels with little green are traced.
+    Green,
+    /// The blue channel of the pixels, so pixels with little blue are traced.
+    Blue,
+    /// The alpha channel of the pixels, so opaque pixels are traced regardless of their color.
+    Alpha,
+}
+
+impl TraceChannel {
+    /// Gets the intensity of a RGBA pixel from 0 to 1, where pixels with low intensity are traced.
+    pub fn intensity(&self, pixel: [u8; 4]) -> f32 {
+        let [red, green, blue, alpha] = pixel.map(|channel| channel as f32 / 255.0);
+        match self {
+            TraceChannel::Luminance => LUMA[0] * red + LUMA[1] * green + LUMA[2] * blue,
+            TraceChannel::Red => red,
+            TraceChannel::Green => green,
+            TraceChannel::Blue => blue,
+            TraceChannel::Alpha => 1.0 - alpha,
+        }
+    }
+    /// Checks whether a pixel takes part in tracing at all. Transparent pixels are ignored unless the alpha channel is traced.
+    pub fn is_visible(&self, pixel: [u8; 4]) -> bool {
+        *self == TraceChannel::Alpha || pixel[3] >= 128
+    }
+}
+
+/// A bitmap of pixels that are either set or unset, with pixels outside of it unset.
+#[derive(Clone, Debug)]
+pub struct BinaryBitmap {
+    /// Number of pixels in a row.
+    width: usize,
+    /// Number of pixels in a column.
+    height: usize,
+    /// Whether each pixel is set, in row-major order.
+    data: Vec<bool>,
+}
+
+impl BinaryBitmap {
+    /// Creates a BinaryBitmap setting the pixels for which a predicate holds.
+    pub fn from_fn(width: usize, height: usize, predicate: impl Fn(usize, usize) -> bool) -> BinaryBitmap {
+        let data = (0..width * height).map(|i| predicate(i % width, i / width)).collect();
+        BinaryBitmap { width, height, data }
+    }
+    /// Checks whether a pixel is set.
+    pub fn get(&self, x: i64, y: i64) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height && self.data[y as usize * self.width + x as usize]
+    }
+    /// Flips the pixels of a row from a column to the end of the row.
+    fn flip_row_from(&mut self, x: i64, y: i64) {
+        let start = y as usize * self.width;
+        for pixel in &mut self.data[start + x.max(0) as usize..start + self.width] {
+            *pixel = !*pixel;
+        }
+    }
+    /// Finds the first set pixel in row-major order, starting at an index.
+    fn find_set_pixel(&self, start: usize) -> Option<usize> {
+        (start..self.data.len()).find(|&i| self.data[i])
+    }
+}
+
+/// Follows the boundary of the region whose top left pixel is at a given position, keeping set pixels on the left. Diagonally adjacent set pixels are considered connected. Returns the corners of the boundary.
+fn find_path(bitmap: &BinaryBitmap, x: i64, y: i64) -> Vec<(i64, i64)> {
+    let start = (x, y);
+    let (mut px, mut py) = start;
+    let (mut dx, mut dy) = (0, 1);
+    let mut corners = vec![];
+    loop {
+        corners.push((px, py));
+        px += dx;
+        py += dy;
+        if (px, py) == start {
+            break;
+        }
+        // The pixels ahead of the current corner, to the left and to the right of the walking direction.
+        let left = bitmap.get(px + (dx + dy - 1).div_euclid(2), py + (dy - dx - 1).div_euclid(2));
+        let right = bitmap.get(px + (dx - dy - 1).div_euclid(2), py + (dy + dx - 1).div_euclid(2));
+        if right {
+            (dx, dy) = (-dy, dx);
+        } else if !left {
+            (dx, dy) = (dy, -dx);
+        }
+    }
+    corners
+}
+
+/// Flips the pixels inside a boundary, so that holes can be found as set regions.
+fn xor_path(bitmap: &mut BinaryBitmap, corners: &[(i64, i64)]) {
+    for i in 0..corners.len() {
+        let (x1, y1) = corners[i];
+        let (x2, y2) = corners[(i + 1) % corners.len()];
+        if x1 == x2 {
+            bitmap.flip_row_from(x1, y1.min(y2));
+        }
+    }
+}
+
+/// Gets the signed area of a polygon.
+fn area(points: &[Point2D]) -> f32 {
+    (0..points.len()).map(|i| {
+        let p1 = points[i];
+        let p2 = points[(i + 1) % points.len()];
+        p1.x * p2.y - p2.x * p1.y
+    }).sum::<f32>() / 2.0
+}
+
+/// Gets the distance from a point to the line through two points.
+fn distance_to_line(p: Point2D, start: Point2D, end: Point2D) -> f32 {
+    let line = end - start;
+    let length = line.magnitude();
+    if length == 0.0 {
+        return p.distance(&start);
+    }
+    ((p.x - start.x) * line.y - (p.y - start.y) * line.x).abs() / length
+}
+
+/// Simplifies an open polyline with the Ramer-Douglas-Peucker algorithm, keeping its first point but not its last one.
+fn simplify_polyline(points: &[Point2D], tolerance: f32, simplified: &mut Vec<Point2D>) {
+    let last = points.len() - 1;
+    let farthest = (1..last).map(|i| (i, distance_to_line(points[i], points[0], points[last])))
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+    match farthest {
+        Some((i, distance)) if distance > tolerance => {
+            simplify_polyline(&points[..=i], tolerance, simplified);
+            simplify_polyline(&points[i..], tolerance, simplified);
+        }
+        _ => simplified.push(points[0]),
+    }
+}
+
+/// Simplifies a closed boundary into a polygon whose edges stay within a tolerance of the boundary.
+fn simplify_polygon(points: &[Point2D], tolerance: f32) -> Vec<Point2D> {
+    let farthest = (1..points.len()).max_by(|&i, &j| points[0].distance_squared(&points[i]).partial_cmp(&points[0].distance_squared(&points[j])).unwrap()).unwrap_or(0);
+    let mut closed = points.to_vec();
+    closed.push(points[0]);
+    let mut simplified = vec![];
+    simplify_polyline(&closed[..=farthest], tolerance, &mut simplified);
+    simplify_polyline(&closed[farthest..], tolerance, &mut simplified);
+    if simplified.len() < 3 { points.to_vec() } else { simplified }
+}
+
+/// Smooths a polygon into a closed path of cubic bezier curves joining the midpoints of its edges, as in potrace. Vertices whose angle is sharper than the corner threshold are kept as corners.
+fn smooth_polygon(vertices: &[Point2D], corner_threshold: f32, points: &mut Vec<Point2D>) {
+    let n = vertices.len();
+    for j in 0..n {
+        let vertex_i = vertices[(j + n - 1) % n];
+        let vertex_j = vertices[j];
+        let vertex_k = vertices[(j + 1) % n];
+        let start = (vertex_i + vertex_j) * 0.5;
+        let end = (vertex_j + vertex_k) * 0.5;
+        // The denominator normalizes the distance of the vertex to the chord by the L-infinity orientation of the chord.
+        let chord = vertex_k - vertex_i;
+        let denominator = chord.x.signum() * chord.x + chord.y.signum() * chord.y;
+        let alpha = if denominator != 0.0 {
+            let parallelogram = (vertex_j.x - vertex_i.x) * chord.y - chord.x * (vertex_j.y - vertex_i.y);
+            let distance = (parallelogram / denominator).abs();
+            (if distance > 1.0 { 1.0 - 1.0 / distance } else { 0.0 }) / 0.75
+        } else {
+            4.0 / 3.0
+        };
+        if alpha >= corner_threshold {
+            points.extend([start, start, vertex_j, vertex_j]);
+            points.extend([vertex_j, vertex_j, end, end]);
+        } else {
+            let alpha = alpha.clamp(0.55, 1.0);
+            let t = 0.5 + 0.5 * alpha;
+            points.extend([start, vertex_i + (vertex_j - vertex_i) * t, vertex_k + (vertex_j - vertex_k) * t, end]);
+        }
+    }
+}
+
+/// Traces the set regions of a BinaryBitmap into a Path2D of closed cubic bezier subpaths in pixel coordinates, following potrace. Holes are subpaths too, so the path must be filled with the even-odd rule.
+pub fn trace(bitmap: &BinaryBitmap, turd_size: f32, corner_threshold: f32) -> Path2D {
+    let mut remaining = bitmap.clone();
+    let mut points = vec![];
+    let mut index = 0;
+    while let Some(found) = remaining.find_set_pixel(index) {
+        index = found;
+        let corners = find_path(&remaining, (found % remaining.width) as i64, (found / remaining.width) as i64);
+        xor_path(&mut remaining, &corners);
+        let boundary = corners.iter().map(|&(x, y)| Point2D::new(x as f32, y as f32)).collect::<Vec<Point2D>>();
+        if area(&boundary).abs() <= turd_size {
+            continue;
+        }
+        let polygon = simplify_polygon(&boundary, 1.0);
+        smooth_polygon(&polygon, corner_threshold, &mut points);
+    }
+    Path2D::new(points)
+}
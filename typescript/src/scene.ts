@@ -1,4 +1,4 @@
-import { VectorObject } from "@mathlikeanim-rs/mathlikeanim-rs";
+import { Camera, UpdaterRegistry, ValueTracker, VectorObject } from "@mathlikeanim-rs/mathlikeanim-rs";
 
 export type Animation = (oldObject: VectorObject, t: number) => VectorObject;
 export type Easing = (t: number) => number;
@@ -22,6 +22,16 @@ export default abstract class Scene {
      * @type {VectorObject[]}
      */
     objects: VectorObject[];
+    /**
+     * The updaters that run on every frame of an animation.
+     * @type {UpdaterRegistry}
+     */
+    updaters: UpdaterRegistry;
+    /**
+     * The camera that maps scene coordinates to pixels, or null to use pixel coordinates.
+     * @type {Camera | null}
+     */
+    camera: Camera | null;
     /**
      * Objects when previous animation was entirely finished.
      * @type {VectorObject[]}
@@ -45,6 +55,8 @@ export default abstract class Scene {
         this.height = height;
         this.objects = [];
         this.oldObjects = [];
+        this.updaters = new UpdaterRegistry();
+        this.camera = null;
     }
 
     /**
@@ -52,6 +64,8 @@ export default abstract class Scene {
      * @param {Map<string | number, Animation>} animations - The animations to play.
      * @param {number} duration - The duration of the animation in milliseconds.
      * @param {Easing} easing - The easing function to use.
+     * @param {Map<ValueTracker, ValueTracker>} trackers - The value trackers to animate, mapped to trackers holding their final values.
+     * @param {Camera | null} camera - The camera to animate the current camera to, or null to keep it.
      * @returns {Promise<void>} - A promise that resolves when the animation has finished.
      * @async
      */
@@ -59,31 +73,59 @@ export default abstract class Scene {
         animations: Map<string | number, Animation>,
         duration: number,
         easing: Easing,
+        trackers: Map<ValueTracker, ValueTracker> = new Map(),
+        camera: Camera | null = null,
     ): Promise<void> {
         if (this.stopped) {
             return;
         }
         this.oldObjects = this.objects.map(object => object.clone());
+        const trackerStarts = new Map([...trackers.keys()].map(tracker => [tracker, tracker.clone()]));
+        const cameraStart = this.camera?.clone() ?? new Camera(this.width, this.height);
         const start = performance.now();
+        let previous = start;
         const animate = async () => {
             if (this.stopped) {
                 return;
             }
-            const t = (performance.now() - start) / duration;
+            const now = performance.now();
+            const t = (now - start) / duration;
+            const dt = (now - previous) / 1000;
+            previous = now;
             if (t < 1) {
                 const progress = easing(t);
+                this.animateTrackers(trackers, trackerStarts, progress);
+                if (camera !== null) {
+                    this.camera = Camera.lerp(cameraStart, camera, progress);
+                }
                 this.animate(animations, progress);
+                this.update(dt);
                 this.render();
                 await new Promise(resolve => requestAnimationFrame(() => animate().then(resolve)));
             } else {
                 const progress = easing(1);
+                this.animateTrackers(trackers, trackerStarts, progress);
+                if (camera !== null) {
+                    this.camera = Camera.lerp(cameraStart, camera, progress);
+                }
                 this.animate(animations, progress);
+                this.update(dt);
                 this.render();
             }
         };
         await animate();
     };
 
+    /**
+     * Waits for a duration while the updaters keep running.
+     * @param {number} duration - The duration to wait in milliseconds.
+     * @returns {Promise<void>} - A promise that resolves when the duration has passed.
+     * @async
+     */
+    async wait(duration: number): Promise<void> {
+        await this.play(new Map(), duration, t => t);
+    }
+
     /**
      * Stops the scene.
      * @returns {void}
@@ -122,6 +164,46 @@ export default abstract class Scene {
         }
     }
 
+    /**
+     * Animates the value trackers. Internal use only.
+     * @param {Map<ValueTracker, ValueTracker>} trackers - The value trackers mapped to trackers holding their final values.
+     * @param {Map<ValueTracker, ValueTracker>} starts - The value trackers mapped to trackers holding their initial values.
+     * @param {number} t - The progress of the animation.
+     * @returns {void}
+     * @private
+     */
+    private animateTrackers(trackers: Map<ValueTracker, ValueTracker>, starts: Map<ValueTracker, ValueTracker>, t: number): void {
+        for (const [tracker, end] of trackers.entries()) {
+            tracker.interpolate(starts.get(tracker)!, end, t);
+        }
+    }
+
+    /**
+     * Runs the updaters once. Internal use only.
+     * @param {number} dt - The time in seconds since the last update.
+     * @returns {void}
+     * @private
+     */
+    private update(dt: number): void {
+        if (this.updaters.is_empty()) {
+            return;
+        }
+        // The updaters take ownership of the objects they're given, so they get clones and the scene keeps its objects if one throws.
+        this.objects = this.updaters.update(this.objects.map(object => object.clone()), dt);
+    }
+
+    /**
+     * Gets the objects as they must be rendered, mapped to pixels by the camera if there is one.
+     * @returns {VectorObject[]} - The objects to render.
+     * @protected
+     */
+    protected renderedObjects(): VectorObject[] {
+        if (this.camera === null) {
+            return this.objects;
+        }
+        return this.objects.map(object => this.camera!.apply(object));
+    }
+
     /**
      * Finds an old object by name. Internal use only.
      * @param {string} name - The name of the object.
@@ -1,4 +1,4 @@
-import { Style, VectorObject } from "@mathlikeanim-rs/mathlikeanim-rs";
+import { BoundingBox, ImageResampling, SpreadMethod, Style, VectorObject } from "@mathlikeanim-rs/mathlikeanim-rs";
 import Scene from "./scene";
 /**
  * A scene that renders to a SVG element.
@@ -32,7 +32,7 @@ export default class SVGScene extends Scene {
         this.svg.innerHTML = "";
         const defs = document.createElementNS("http://www.w3.org/2000/svg", "defs");
         this.svg.appendChild(defs);
-        for (const [index, object] of this.objects.entries()) {
+        for (const [index, object] of this.renderedObjects().entries()) {
             this.renderObject(object, [index], defs, this.svg);
         }
     }
@@ -112,13 +112,15 @@ export default class SVGScene extends Scene {
 
     /**
      * Renders the fill of a VectorObject. Internal use only.
-     * @param {VectorObject} object - The object to render the fill of.
+     * @param {Style} style - The style to render.
+     * @param {VectorObject} object - The object the style belongs to.
      * @param {SVGDefsElement} defs - The defs element.
      * @param {number[]} index - The index of the object.
+     * @param {string} prefix - The prefix of the ids of the defs.
      * @returns {string} - The fill.
      * @private
      */
-    private renderStyle(style: Style, defs: SVGDefsElement, index: number[], prefix: string): string {
+    private renderStyle(style: Style, object: VectorObject, defs: SVGDefsElement, index: number[], prefix: string): string {
         if (style.color) {
             return `rgba(${style.color.red}, ${style.color.green}, ${style.color.blue}, ${style.color.alpha})`;
         }
@@ -130,7 +132,9 @@ export default class SVGScene extends Scene {
             linearGradient.setAttribute("y1", linear_gradient.p1.y.toString());
             linearGradient.setAttribute("x2", linear_gradient.p2.x.toString());
             linearGradient.setAttribute("y2", linear_gradient.p2.y.toString());
-            linear_gradient.color_stops.forEach(stop => {
+            linearGradient.setAttribute("spreadMethod", SpreadMethod[linear_gradient.spread_method].toLowerCase());
+            linearGradient.setAttribute("gradientTransform", `matrix(${linear_gradient.transform.a} ${linear_gradient.transform.b} ${linear_gradient.transform.c} ${linear_gradient.transform.d} ${linear_gradient.transform.e} ${linear_gradient.transform.f})`);
+            linear_gradient.sampled_color_stops().forEach(stop => {
                 const stopElement = document.createElementNS("http://www.w3.org/2000/svg", "stop");
                 stopElement.setAttribute("offset", stop.position.toString());
                 stopElement.setAttribute("stop-color", `rgba(${stop.color.red}, ${stop.color.green}, ${stop.color.blue}, ${stop.color.alpha})`);
@@ -148,7 +152,9 @@ export default class SVGScene extends Scene {
             radialGradient.setAttribute("r", radial_gradient.r.toString());
             radialGradient.setAttribute("fx", radial_gradient.f.x.toString());
             radialGradient.setAttribute("fy", radial_gradient.f.y.toString());
-            radial_gradient.color_stops.forEach(stop => {
+            radialGradient.setAttribute("spreadMethod", SpreadMethod[radial_gradient.spread_method].toLowerCase());
+            radialGradient.setAttribute("gradientTransform", `matrix(${radial_gradient.transform.a} ${radial_gradient.transform.b} ${radial_gradient.transform.c} ${radial_gradient.transform.d} ${radial_gradient.transform.e} ${radial_gradient.transform.f})`);
+            radial_gradient.sampled_color_stops().forEach(stop => {
                 const stopElement = document.createElementNS("http://www.w3.org/2000/svg", "stop");
                 stopElement.setAttribute("offset", stop.position.toString());
                 stopElement.setAttribute("stop-color", `rgba(${stop.color.red}, ${stop.color.green}, ${stop.color.blue}, ${stop.color.alpha})`);
@@ -157,6 +163,52 @@ export default class SVGScene extends Scene {
             defs.appendChild(radialGradient);
             return `url(#${prefix}-radial-gradient-${index.join("-")})`;
         }
+        if (style.conic_gradient) {
+            // SVG has no conic gradients, so they are rasterized over the bounding box of the untransformed path.
+            const bbox = BoundingBox.from_path(object.path);
+            if (!bbox) {
+                return "none";
+            }
+            const dataWidth = Math.max(1, Math.ceil(bbox.width));
+            const dataHeight = Math.max(1, Math.ceil(bbox.height));
+            const bitmap = style.to_image_bitmap(bbox.min_x, bbox.min_y, bbox.width, bbox.height, dataWidth, dataHeight);
+            const pattern = document.createElementNS("http://www.w3.org/2000/svg", "pattern");
+            pattern.setAttribute("id", `${prefix}-conic-gradient-${index.join("-")}`);
+            pattern.setAttribute("patternUnits", "userSpaceOnUse");
+            pattern.setAttribute("x", bbox.min_x.toString());
+            pattern.setAttribute("y", bbox.min_y.toString());
+            pattern.setAttribute("width", bbox.width.toString());
+            pattern.setAttribute("height", bbox.height.toString());
+            const image = document.createElementNS("http://www.w3.org/2000/svg", "image");
+            image.setAttribute("x", "0");
+            image.setAttribute("y", "0");
+            image.setAttribute("width", bbox.width.toString());
+            image.setAttribute("height", bbox.height.toString());
+            image.setAttribute("preserveAspectRatio", "none");
+            image.setAttribute("href", `data:image/png;base64,${bitmap.base64}`);
+            pattern.appendChild(image);
+            defs.appendChild(pattern);
+            return `url(#${prefix}-conic-gradient-${index.join("-")})`;
+        }
+        if (style.pattern) {
+            const tile_pattern = style.pattern;
+            const pattern = document.createElementNS("http://www.w3.org/2000/svg", "pattern");
+            pattern.setAttribute("id", `${prefix}-tile-pattern-${index.join("-")}`);
+            pattern.setAttribute("patternUnits", "userSpaceOnUse");
+            pattern.setAttribute("x", tile_pattern.origin.x.toString());
+            pattern.setAttribute("y", tile_pattern.origin.y.toString());
+            pattern.setAttribute("width", (tile_pattern.width + tile_pattern.spacing_x).toString());
+            pattern.setAttribute("height", (tile_pattern.height + tile_pattern.spacing_y).toString());
+            pattern.setAttribute("patternTransform", `matrix(${tile_pattern.transform.a} ${tile_pattern.transform.b} ${tile_pattern.transform.c} ${tile_pattern.transform.d} ${tile_pattern.transform.e} ${tile_pattern.transform.f})`);
+            // A nested SVG clips the tile to its size, leaving the spacing transparent.
+            const tile = document.createElementNS("http://www.w3.org/2000/svg", "svg");
+            tile.setAttribute("width", tile_pattern.width.toString());
+            tile.setAttribute("height", tile_pattern.height.toString());
+            this.renderObject(tile_pattern.tile, [...index, prefix === "fill" ? -1 : -2], defs, tile);
+            pattern.appendChild(tile);
+            defs.appendChild(pattern);
+            return `url(#${prefix}-tile-pattern-${index.join("-")})`;
+        }
         if (style.image) {
             const pattern = document.createElementNS("http://www.w3.org/2000/svg", "pattern");
             pattern.setAttribute("id", `${prefix}-pattern-${index.join("-")}`);
@@ -171,6 +223,7 @@ export default class SVGScene extends Scene {
             image.setAttribute("width", style.image.width.toString());
             image.setAttribute("height", style.image.height.toString());
             image.setAttribute("href", `data:image/png;base64,${base64}`);
+            image.setAttribute("image-rendering", this.renderImageRendering(style.image.resampling));
             pattern.appendChild(image);
             defs.appendChild(pattern);
             return `url(#${prefix}-pattern-${index.join("-")})`;
@@ -178,6 +231,23 @@ export default class SVGScene extends Scene {
         return "none";
     }
 
+    /**
+     * Renders the image-rendering attribute of an image resampling. Internal use only.
+     * @param {ImageResampling} resampling - The resampling of the image.
+     * @returns {string} - The image-rendering attribute.
+     * @private
+     */
+    private renderImageRendering(resampling: ImageResampling): string {
+        switch (resampling) {
+            case ImageResampling.Nearest:
+                return "pixelated";
+            case ImageResampling.Bicubic:
+                return "smooth";
+            default:
+                return "auto";
+        }
+    }
+
     /**
      * Renders the stroke of a VectorObject. Internal use only.
      * @param {VectorObject} object - The object to render the stroke of.
@@ -187,7 +257,7 @@ export default class SVGScene extends Scene {
      * @private
      */
     private renderFill(object: VectorObject, defs: SVGDefsElement, index: number[]): string {
-        return this.renderStyle(object.fill, defs, index, "fill");
+        return this.renderStyle(object.fill, object, defs, index, "fill");
     }
 
     /**
@@ -199,6 +269,6 @@ export default class SVGScene extends Scene {
      * @private
      */
     private renderStroke(object: VectorObject, defs: SVGDefsElement, index: number[]): string {
-        return this.renderStyle(object.stroke, defs, index, "stroke");
+        return this.renderStyle(object.stroke, object, defs, index, "stroke");
     }
 }
\ No newline at end of file